@@ -5,6 +5,27 @@ mod m20231228_000002_create_users;
 mod m20231228_000003_create_journeys;
 mod m20231228_000004_create_bookings;
 mod m20260114_000001_add_google_oauth;
+mod m20261015_000001_add_booking_updated_at;
+mod m20261015_000002_add_city_operating_hours;
+mod m20261015_000003_create_revoked_tokens;
+mod m20261015_000004_add_journey_updated_at;
+mod m20261015_000005_add_journey_status;
+mod m20261015_000006_add_fares;
+mod m20261015_000007_add_booking_status;
+mod m20261015_000008_add_foreign_key_indexes;
+mod m20261015_000009_add_driver_application;
+mod m20261015_000010_create_booking_idempotency_keys;
+mod m20261015_000011_add_user_deleted_at;
+mod m20261015_000012_add_user_email_lower_index;
+mod m20261015_000013_add_journey_booking_close;
+mod m20261015_000014_add_booking_reference_code;
+mod m20261015_000015_add_user_phone;
+mod m20261015_000016_create_seat_holds;
+mod m20261015_000017_add_city_pickup_polygon;
+mod m20261015_000018_create_role_change_audits;
+mod m20261015_000019_add_journey_created_by;
+mod m20261015_000020_create_journey_stops;
+mod m20261015_000021_add_booking_cancellation;
 
 pub struct Migrator;
 
@@ -17,6 +38,27 @@ impl MigratorTrait for Migrator {
             Box::new(m20231228_000003_create_journeys::Migration),
             Box::new(m20231228_000004_create_bookings::Migration),
             Box::new(m20260114_000001_add_google_oauth::Migration),
+            Box::new(m20261015_000001_add_booking_updated_at::Migration),
+            Box::new(m20261015_000002_add_city_operating_hours::Migration),
+            Box::new(m20261015_000003_create_revoked_tokens::Migration),
+            Box::new(m20261015_000004_add_journey_updated_at::Migration),
+            Box::new(m20261015_000005_add_journey_status::Migration),
+            Box::new(m20261015_000006_add_fares::Migration),
+            Box::new(m20261015_000007_add_booking_status::Migration),
+            Box::new(m20261015_000008_add_foreign_key_indexes::Migration),
+            Box::new(m20261015_000009_add_driver_application::Migration),
+            Box::new(m20261015_000010_create_booking_idempotency_keys::Migration),
+            Box::new(m20261015_000011_add_user_deleted_at::Migration),
+            Box::new(m20261015_000012_add_user_email_lower_index::Migration),
+            Box::new(m20261015_000013_add_journey_booking_close::Migration),
+            Box::new(m20261015_000014_add_booking_reference_code::Migration),
+            Box::new(m20261015_000015_add_user_phone::Migration),
+            Box::new(m20261015_000016_create_seat_holds::Migration),
+            Box::new(m20261015_000017_add_city_pickup_polygon::Migration),
+            Box::new(m20261015_000018_create_role_change_audits::Migration),
+            Box::new(m20261015_000019_add_journey_created_by::Migration),
+            Box::new(m20261015_000020_create_journey_stops::Migration),
+            Box::new(m20261015_000021_add_booking_cancellation::Migration),
         ]
     }
 }
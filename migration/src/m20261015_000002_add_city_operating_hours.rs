@@ -0,0 +1,55 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // IANA timezone used to interpret operating hours
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(City::Table)
+                    .add_column(
+                        string_len(City::Timezone, 64)
+                            .not_null()
+                            .default("Asia/Jakarta"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Local time-of-day departure window (null = 24/7)
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(City::Table)
+                    .add_column(time_null(City::OperatingStart))
+                    .add_column(time_null(City::OperatingEnd))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(City::Table)
+                    .drop_column(City::OperatingEnd)
+                    .drop_column(City::OperatingStart)
+                    .drop_column(City::Timezone)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum City {
+    Table,
+    Timezone,
+    OperatingStart,
+    OperatingEnd,
+}
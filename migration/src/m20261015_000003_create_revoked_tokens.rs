@@ -0,0 +1,44 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RevokedToken::Table)
+                    .if_not_exists()
+                    .col(uuid(RevokedToken::Jti).primary_key())
+                    .col(timestamp_with_time_zone(RevokedToken::ExpiresAt).not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Cleanup deletes by expiry
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_revoked_token_expires_at")
+                    .table(RevokedToken::Table)
+                    .col(RevokedToken::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RevokedToken::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RevokedToken {
+    Table,
+    Jti,
+    ExpiresAt,
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::{prelude::*, sea_orm::sea_query::extension::postgres::Type};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(JourneyStatus::Enum)
+                    .values([
+                        JourneyStatus::Scheduled,
+                        JourneyStatus::InProgress,
+                        JourneyStatus::Completed,
+                        JourneyStatus::Cancelled,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing journeys start out scheduled
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Journey::Table)
+                    .add_column(
+                        ColumnDef::new(Journey::Status)
+                            .custom(JourneyStatus::Enum)
+                            .not_null()
+                            .default("scheduled"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Journey::Table)
+                    .drop_column(Journey::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(JourneyStatus::Enum).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Journey {
+    Table,
+    Status,
+}
+
+#[derive(DeriveIden)]
+enum JourneyStatus {
+    #[sea_orm(iden = "journey_status")]
+    Enum,
+    #[sea_orm(iden = "scheduled")]
+    Scheduled,
+    #[sea_orm(iden = "in_progress")]
+    InProgress,
+    #[sea_orm(iden = "completed")]
+    Completed,
+    #[sea_orm(iden = "cancelled")]
+    Cancelled,
+}
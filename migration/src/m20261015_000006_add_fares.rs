@@ -0,0 +1,60 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Fares are stored in cents; existing journeys and bookings start free
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Journey::Table)
+                    .add_column(big_integer(Journey::BaseFare).not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .add_column(big_integer(Booking::TotalFare).not_null().default(0))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .drop_column(Booking::TotalFare)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Journey::Table)
+                    .drop_column(Journey::BaseFare)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Journey {
+    Table,
+    BaseFare,
+}
+
+#[derive(DeriveIden)]
+enum Booking {
+    Table,
+    TotalFare,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::{prelude::*, sea_orm::sea_query::extension::postgres::Type};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(BookingStatus::Enum)
+                    .values([BookingStatus::Confirmed, BookingStatus::Waitlisted])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Every existing booking holds its seats
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .add_column(
+                        ColumnDef::new(Booking::Status)
+                            .custom(BookingStatus::Enum)
+                            .not_null()
+                            .default("confirmed"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .drop_column(Booking::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_type(Type::drop().name(BookingStatus::Enum).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Booking {
+    Table,
+    Status,
+}
+
+#[derive(DeriveIden)]
+enum BookingStatus {
+    #[sea_orm(iden = "booking_status")]
+    Enum,
+    #[sea_orm(iden = "confirmed")]
+    Confirmed,
+    #[sea_orm(iden = "waitlisted")]
+    Waitlisted,
+}
@@ -0,0 +1,72 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Seat counts, passenger lists and waitlist promotion filter bookings by journey
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_booking_journey_id")
+                    .table(Booking::Table)
+                    .col(Booking::JourneyId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // "My bookings", trip summaries and account deletion filter bookings by user
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_booking_user_id")
+                    .table(Booking::Table)
+                    .col(Booking::UserId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Driver journey listings and driver cleanup filter journeys by driver
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_journey_driver_id")
+                    .table(Journey::Table)
+                    .col(Journey::DriverId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, table) in [
+            ("idx_journey_driver_id", Journey::Table.into_iden()),
+            ("idx_booking_user_id", Booking::Table.into_iden()),
+            ("idx_booking_journey_id", Booking::Table.into_iden()),
+        ] {
+            manager
+                .drop_index(Index::drop().name(name).table(table).to_owned())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Booking {
+    Table,
+    JourneyId,
+    UserId,
+}
+
+#[derive(DeriveIden)]
+enum Journey {
+    Table,
+    DriverId,
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20231228_000004_create_bookings::Booking;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Maps a client's Idempotency-Key to the booking its first request created
+        manager
+            .create_table(
+                Table::create()
+                    .table(BookingIdempotencyKey::Table)
+                    .if_not_exists()
+                    .col(uuid(BookingIdempotencyKey::UserId).not_null())
+                    .col(string(BookingIdempotencyKey::IdempotencyKey).not_null())
+                    .col(uuid(BookingIdempotencyKey::BookingId).not_null())
+                    .col(timestamp_with_time_zone(BookingIdempotencyKey::ExpiresAt).not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(BookingIdempotencyKey::UserId)
+                            .col(BookingIdempotencyKey::IdempotencyKey),
+                    )
+                    // Cancelling the booking frees the key
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_booking_idempotency_key_booking")
+                            .from(
+                                BookingIdempotencyKey::Table,
+                                BookingIdempotencyKey::BookingId,
+                            )
+                            .to(Booking::Table, Booking::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Cleanup deletes by expiry
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_booking_idempotency_key_expires_at")
+                    .table(BookingIdempotencyKey::Table)
+                    .col(BookingIdempotencyKey::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BookingIdempotencyKey::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum BookingIdempotencyKey {
+    Table,
+    UserId,
+    IdempotencyKey,
+    BookingId,
+    ExpiresAt,
+}
@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Accounts created before emails were normalized; rows whose lowercase form is
+        // already taken are left alone, so the index below fails until they're merged
+        db.execute_unprepared(
+            r#"UPDATE "user" u SET email = lower(u.email)
+               WHERE u.email <> lower(u.email)
+                 AND NOT EXISTS (
+                     SELECT 1 FROM "user" o
+                     WHERE o.id <> u.id AND lower(o.email) = lower(u.email)
+                 )"#,
+        )
+        .await?;
+
+        // The plain unique constraint on email is case-sensitive
+        db.execute_unprepared(
+            r#"CREATE UNIQUE INDEX IF NOT EXISTS idx_user_email_lower ON "user" (lower(email))"#,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_user_email_lower")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Minutes before departure when booking closes; existing journeys stay open until departure
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Journey::Table)
+                    .add_column(integer(Journey::BookingCloseMinutes).not_null().default(0))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Journey::Table)
+                    .drop_column(Journey::BookingCloseMinutes)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Journey {
+    Table,
+    BookingCloseMinutes,
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .add_column(string_len_null(Booking::ReferenceCode, 6))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Give existing bookings a code from the same alphabet the app uses (Crockford
+        // base32), drawing again whenever one is already taken
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"DO $$
+                DECLARE
+                    b RECORD;
+                    code TEXT;
+                BEGIN
+                    FOR b IN SELECT id FROM booking WHERE reference_code IS NULL LOOP
+                        LOOP
+                            SELECT string_agg(
+                                substr(
+                                    '0123456789ABCDEFGHJKMNPQRSTVWXYZ',
+                                    floor(random() * 32)::int + 1,
+                                    1
+                                ),
+                                ''
+                            ) INTO code
+                            FROM generate_series(1, 6);
+                            EXIT WHEN NOT EXISTS (
+                                SELECT 1 FROM booking WHERE reference_code = code
+                            );
+                        END LOOP;
+                        UPDATE booking SET reference_code = code WHERE id = b.id;
+                    END LOOP;
+                END $$"#,
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .modify_column(string_len(Booking::ReferenceCode, 6))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_booking_reference_code")
+                    .table(Booking::Table)
+                    .col(Booking::ReferenceCode)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_booking_reference_code")
+                    .table(Booking::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .drop_column(Booking::ReferenceCode)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Booking {
+    Table,
+    ReferenceCode,
+}
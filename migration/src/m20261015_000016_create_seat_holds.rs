@@ -0,0 +1,90 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20231228_000002_create_users::User;
+use super::m20231228_000003_create_journeys::Journey;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Seats set aside while a traveller completes checkout
+        manager
+            .create_table(
+                Table::create()
+                    .table(SeatHold::Table)
+                    .if_not_exists()
+                    .col(uuid(SeatHold::Id).primary_key())
+                    .col(uuid(SeatHold::JourneyId).not_null())
+                    .col(uuid(SeatHold::UserId).not_null())
+                    .col(integer(SeatHold::Seats).not_null())
+                    .col(double(SeatHold::PickupLat).not_null())
+                    .col(double(SeatHold::PickupLng).not_null())
+                    .col(timestamp_with_time_zone(SeatHold::ExpiresAt).not_null())
+                    .col(
+                        timestamp_with_time_zone(SeatHold::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_seat_hold_journey")
+                            .from(SeatHold::Table, SeatHold::JourneyId)
+                            .to(Journey::Table, Journey::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_seat_hold_user")
+                            .from(SeatHold::Table, SeatHold::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Availability sums a journey's unexpired holds
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_seat_hold_journey_expires_at")
+                    .table(SeatHold::Table)
+                    .col(SeatHold::JourneyId)
+                    .col(SeatHold::ExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Cleanup deletes by expiry
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_seat_hold_expires_at")
+                    .table(SeatHold::Table)
+                    .col(SeatHold::ExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SeatHold::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SeatHold {
+    Table,
+    Id,
+    JourneyId,
+    UserId,
+    Seats,
+    PickupLat,
+    PickupLng,
+    ExpiresAt,
+    CreatedAt,
+}
@@ -0,0 +1,36 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Optional service area outline, `[[lat, lng], ...]`; replaces the radius check when set
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(City::Table)
+                    .add_column(json_binary_null(City::PickupPolygon))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(City::Table)
+                    .drop_column(City::PickupPolygon)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum City {
+    Table,
+    PickupPolygon,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20231228_000002_create_users::{User, UserRole};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per admin role change. User references are cleared rather than
+        // cascaded when an account is deleted, so the history survives.
+        manager
+            .create_table(
+                Table::create()
+                    .table(RoleChangeAudit::Table)
+                    .if_not_exists()
+                    .col(uuid(RoleChangeAudit::Id).primary_key())
+                    .col(uuid_null(RoleChangeAudit::ActorId))
+                    .col(uuid_null(RoleChangeAudit::TargetUserId))
+                    .col(
+                        ColumnDef::new(RoleChangeAudit::OldRole)
+                            .custom(UserRole::Enum)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RoleChangeAudit::NewRole)
+                            .custom(UserRole::Enum)
+                            .not_null(),
+                    )
+                    .col(
+                        timestamp_with_time_zone(RoleChangeAudit::CreatedAt)
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_role_change_audit_actor")
+                            .from(RoleChangeAudit::Table, RoleChangeAudit::ActorId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_role_change_audit_target_user")
+                            .from(RoleChangeAudit::Table, RoleChangeAudit::TargetUserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The review endpoint lists newest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_role_change_audit_created_at")
+                    .table(RoleChangeAudit::Table)
+                    .col(RoleChangeAudit::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RoleChangeAudit::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RoleChangeAudit {
+    Table,
+    Id,
+    ActorId,
+    TargetUserId,
+    OldRole,
+    NewRole,
+    CreatedAt,
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20231228_000002_create_users::User;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Admin who created the journey; unknown for journeys created before this
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Journey::Table)
+                    .add_column(uuid_null(Journey::CreatedBy))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_journey_created_by")
+                            .from_tbl(Journey::Table)
+                            .from_col(Journey::CreatedBy)
+                            .to_tbl(User::Table)
+                            .to_col(User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Journey::Table)
+                    .drop_foreign_key(Alias::new("fk_journey_created_by"))
+                    .drop_column(Journey::CreatedBy)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Journey {
+    Table,
+    CreatedBy,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+use super::m20231228_000001_create_cities::City;
+use super::m20231228_000003_create_journeys::Journey;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Intermediate cities a journey calls at between its origin and destination
+        manager
+            .create_table(
+                Table::create()
+                    .table(JourneyStop::Table)
+                    .if_not_exists()
+                    .col(uuid(JourneyStop::Id).primary_key())
+                    .col(uuid(JourneyStop::JourneyId).not_null())
+                    .col(integer(JourneyStop::CityId).not_null())
+                    .col(integer(JourneyStop::StopOrder).not_null())
+                    .col(timestamp_with_time_zone(JourneyStop::ScheduledTime).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_journey_stop_journey")
+                            .from(JourneyStop::Table, JourneyStop::JourneyId)
+                            .to(Journey::Table, Journey::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_journey_stop_city")
+                            .from(JourneyStop::Table, JourneyStop::CityId)
+                            .to(City::Table, City::Id)
+                            .on_delete(ForeignKeyAction::Restrict),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Stops are read per journey in order; one stop per position
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_journey_stop_journey_order")
+                    .table(JourneyStop::Table)
+                    .col(JourneyStop::JourneyId)
+                    .col(JourneyStop::StopOrder)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // City deletion checks whether any stop still uses the city
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_journey_stop_city_id")
+                    .table(JourneyStop::Table)
+                    .col(JourneyStop::CityId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(JourneyStop::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum JourneyStop {
+    Table,
+    Id,
+    JourneyId,
+    CityId,
+    StopOrder,
+    ScheduledTime,
+}
@@ -0,0 +1,66 @@
+use sea_orm_migration::{prelude::*, schema::*, sea_orm::sea_query::extension::postgres::Type};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Cancelled bookings stay in the traveller's history instead of being deleted
+        manager
+            .alter_type(
+                Type::alter()
+                    .name(BookingStatus::Enum)
+                    .add_value(BookingStatus::Cancelled)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .add_column(timestamp_with_time_zone_null(Booking::CancelledAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Postgres can't drop an enum value; remove the rows that use it instead,
+        // as they would have been deleted before this migration
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Booking::Table)
+                    .and_where(Expr::col(Booking::Status).eq("cancelled"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Booking::Table)
+                    .drop_column(Booking::CancelledAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Booking {
+    Table,
+    Status,
+    CancelledAt,
+}
+
+#[derive(DeriveIden)]
+enum BookingStatus {
+    #[sea_orm(iden = "booking_status")]
+    Enum,
+    #[sea_orm(iden = "cancelled")]
+    Cancelled,
+}
@@ -1,32 +1,281 @@
 use std::env;
+use std::time::Duration;
+
+use axum::http::{HeaderValue, header::InvalidHeaderValue};
+use sea_orm::ConnectOptions;
+
+use crate::entities::user::UserRole;
+use crate::utils::password::HashCost;
+
+/// Shortest accepted JWT signing secret; HS256 keys should carry at least 256 bits
+pub const MIN_JWT_SECRET_BYTES: usize = 32;
+
+/// How log lines are written, from `LOG_FORMAT`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable lines, for development
+    #[default]
+    Pretty,
+    /// One JSON object per line, for log collectors
+    Json,
+}
+
+impl LogFormat {
+    /// Parse a `LOG_FORMAT` value; unset or blank means `pretty`
+    pub fn from_env_value(value: Option<&str>) -> Result<Self, String> {
+        match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("pretty") => Ok(Self::Pretty),
+            Some("json") => Ok(Self::Json),
+            Some(other) => Err(format!("unknown log format {:?}", other)),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Config {
     pub database_url: String,
+    /// Connection pool bounds and timeouts
+    pub db_max_connections: u32,
+    pub db_min_connections: u32,
+    pub db_connect_timeout_secs: u64,
+    pub db_idle_timeout_secs: u64,
     pub jwt_secret: String,
+    /// Argon2id cost for new password hashes; weaker stored hashes are upgraded at login
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+    /// Written to `iss`/`aud` and required when verifying tokens
+    pub jwt_issuer: String,
+    pub jwt_audience: String,
     pub jwt_expiration_hours: i64,
+    /// Per-role access token lifetimes; each defaults to `jwt_expiration_hours`
+    pub jwt_expiration_hours_admin: i64,
+    pub jwt_expiration_hours_driver: i64,
+    pub jwt_expiration_hours_traveller: i64,
+    pub refresh_token_expiration_days: i64,
     pub server_host: String,
     pub server_port: u16,
     pub oauth_client_id: String,
+    pub force_https: bool,
+    pub https_redirect: bool,
+    /// CORS origins; `None` allows any origin
+    pub allowed_origins: Option<Vec<HeaderValue>>,
+    /// Rate limit bursts; buckets refill at half this many requests per minute
+    pub rate_limit_traveller_per_min: u32,
+    pub rate_limit_driver_per_min: u32,
+    pub rate_limit_global_burst: u32,
+    /// Requests still running after this long are answered with 504
+    pub request_timeout_secs: u64,
+    /// Most seats a traveller may take in a single booking
+    pub max_seats_per_booking: i32,
+    /// Most bookings one traveller may hold on the same journey
+    pub max_bookings_per_user_per_journey: u32,
+    /// Longest straight-line distance between a journey's origin and destination
+    pub max_journey_km: f64,
+    /// Bookings can't be cancelled once departure is this close; 0 allows it until departure
+    pub cancellation_cutoff_hours: u32,
+    pub log_format: LogFormat,
+    /// Credentials for the admin account seeded at startup; seeding is skipped unless both are set
+    pub admin_email: Option<String>,
+    pub admin_password: Option<String>,
+    /// Serve `/metrics` on this address instead of the main listener
+    pub metrics_addr: Option<String>,
 }
 
 impl Config {
     pub fn from_env() -> Self {
         dotenvy::dotenv().ok();
 
-        Self {
+        let jwt_expiration_hours: i64 = env::var("JWT_EXPIRATION_HOURS")
+            .unwrap_or_else(|_| "24".to_string())
+            .parse()
+            .expect("JWT_EXPIRATION_HOURS must be a number");
+        let role_expiration_hours = |name: &str| -> i64 {
+            env::var(name)
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .map_or(jwt_expiration_hours, |v| {
+                    v.trim()
+                        .parse()
+                        .unwrap_or_else(|_| panic!("{} must be a number", name))
+                })
+        };
+
+        let config = Self {
             database_url: env::var("DATABASE_URL").expect("DATABASE_URL must be set"),
+            db_max_connections: env::var("DB_MAX_CONNECTIONS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .expect("DB_MAX_CONNECTIONS must be a number"),
+            db_min_connections: env::var("DB_MIN_CONNECTIONS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .expect("DB_MIN_CONNECTIONS must be a number"),
+            db_connect_timeout_secs: env::var("DB_CONNECT_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .expect("DB_CONNECT_TIMEOUT_SECS must be a number"),
+            db_idle_timeout_secs: env::var("DB_IDLE_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .expect("DB_IDLE_TIMEOUT_SECS must be a number"),
             jwt_secret: env::var("JWT_SECRET").expect("JWT_SECRET must be set"),
-            jwt_expiration_hours: env::var("JWT_EXPIRATION_HOURS")
-                .unwrap_or_else(|_| "24".to_string())
+            argon2_memory_kib: env::var("ARGON2_MEMORY_KIB")
+                .unwrap_or_else(|_| HashCost::default().memory_kib.to_string())
+                .parse()
+                .expect("ARGON2_MEMORY_KIB must be a number"),
+            argon2_iterations: env::var("ARGON2_ITERATIONS")
+                .unwrap_or_else(|_| HashCost::default().iterations.to_string())
+                .parse()
+                .expect("ARGON2_ITERATIONS must be a number"),
+            argon2_parallelism: env::var("ARGON2_PARALLELISM")
+                .unwrap_or_else(|_| HashCost::default().parallelism.to_string())
+                .parse()
+                .expect("ARGON2_PARALLELISM must be a number"),
+            jwt_issuer: env::var("JWT_ISSUER").unwrap_or_else(|_| "bus-travel-backend".to_string()),
+            jwt_audience: env::var("JWT_AUDIENCE").unwrap_or_else(|_| "bus-travel-api".to_string()),
+            jwt_expiration_hours,
+            jwt_expiration_hours_admin: role_expiration_hours("JWT_EXPIRATION_HOURS_ADMIN"),
+            jwt_expiration_hours_driver: role_expiration_hours("JWT_EXPIRATION_HOURS_DRIVER"),
+            jwt_expiration_hours_traveller: role_expiration_hours("JWT_EXPIRATION_HOURS_TRAVELLER"),
+            refresh_token_expiration_days: env::var("REFRESH_TOKEN_EXPIRATION_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
                 .parse()
-                .expect("JWT_EXPIRATION_HOURS must be a number"),
+                .expect("REFRESH_TOKEN_EXPIRATION_DAYS must be a number"),
             server_host: env::var("SERVER_HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
             server_port: env::var("SERVER_PORT")
                 .unwrap_or_else(|_| "3000".to_string())
                 .parse()
                 .expect("SERVER_PORT must be a number"),
             oauth_client_id: env::var("OAUTH_CLIENT_ID").expect("OAUTH_CLIENT_ID must be set"),
+            force_https: env::var("FORCE_HTTPS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .expect("FORCE_HTTPS must be true or false"),
+            https_redirect: env::var("HTTPS_REDIRECT")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .expect("HTTPS_REDIRECT must be true or false"),
+            allowed_origins: env::var("ALLOWED_ORIGINS")
+                .ok()
+                .map(|v| {
+                    parse_allowed_origins(&v).expect("ALLOWED_ORIGINS contains an invalid origin")
+                })
+                .filter(|origins| !origins.is_empty()),
+            rate_limit_traveller_per_min: env::var("RATE_LIMIT_TRAVELLER_PER_MIN")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .expect("RATE_LIMIT_TRAVELLER_PER_MIN must be a number"),
+            rate_limit_driver_per_min: env::var("RATE_LIMIT_DRIVER_PER_MIN")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()
+                .expect("RATE_LIMIT_DRIVER_PER_MIN must be a number"),
+            rate_limit_global_burst: env::var("RATE_LIMIT_GLOBAL_BURST")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .expect("RATE_LIMIT_GLOBAL_BURST must be a number"),
+            request_timeout_secs: env::var("REQUEST_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .expect("REQUEST_TIMEOUT_SECS must be a number"),
+            max_seats_per_booking: env::var("MAX_SEATS_PER_BOOKING")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .expect("MAX_SEATS_PER_BOOKING must be a number"),
+            max_bookings_per_user_per_journey: env::var("MAX_BOOKINGS_PER_USER_PER_JOURNEY")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .expect("MAX_BOOKINGS_PER_USER_PER_JOURNEY must be a number"),
+            max_journey_km: env::var("MAX_JOURNEY_KM")
+                .unwrap_or_else(|_| "1500".to_string())
+                .parse()
+                .expect("MAX_JOURNEY_KM must be a number"),
+            cancellation_cutoff_hours: env::var("CANCELLATION_CUTOFF_HOURS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .expect("CANCELLATION_CUTOFF_HOURS must be a non-negative number"),
+            log_format: LogFormat::from_env_value(env::var("LOG_FORMAT").ok().as_deref())
+                .expect("LOG_FORMAT must be pretty or json"),
+            admin_email: env::var("ADMIN_EMAIL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            admin_password: env::var("ADMIN_PASSWORD").ok().filter(|v| !v.is_empty()),
+            metrics_addr: env::var("METRICS_ADDR")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+        };
+
+        if let Err(message) = config.validate() {
+            panic!("{}", message);
+        }
+
+        config
+    }
+
+    /// Check settings that parse fine but are unsafe to run with
+    pub fn validate(&self) -> Result<(), String> {
+        if self.jwt_secret.len() < MIN_JWT_SECRET_BYTES {
+            return Err(format!(
+                "JWT_SECRET must be at least {} bytes long (got {})",
+                MIN_JWT_SECRET_BYTES,
+                self.jwt_secret.len()
+            ));
+        }
+
+        if let Err(e) = self.password_hash_cost().params() {
+            return Err(format!(
+                "ARGON2_MEMORY_KIB, ARGON2_ITERATIONS and ARGON2_PARALLELISM are invalid: {}",
+                e
+            ));
+        }
+
+        if self.db_max_connections == 0 {
+            return Err("DB_MAX_CONNECTIONS must be at least 1".to_string());
+        }
+        if self.db_min_connections > self.db_max_connections {
+            return Err(format!(
+                "DB_MIN_CONNECTIONS ({}) must not exceed DB_MAX_CONNECTIONS ({})",
+                self.db_min_connections, self.db_max_connections
+            ));
+        }
+
+        if self.request_timeout_secs == 0 {
+            return Err("REQUEST_TIMEOUT_SECS must be at least 1".to_string());
+        }
+
+        if self.max_bookings_per_user_per_journey == 0 {
+            return Err("MAX_BOOKINGS_PER_USER_PER_JOURNEY must be at least 1".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Database URL plus the configured pool settings
+    pub fn db_connect_options(&self) -> ConnectOptions {
+        let mut options = ConnectOptions::new(&self.database_url);
+        options
+            .max_connections(self.db_max_connections)
+            .min_connections(self.db_min_connections)
+            .connect_timeout(Duration::from_secs(self.db_connect_timeout_secs))
+            .idle_timeout(Duration::from_secs(self.db_idle_timeout_secs));
+        options
+    }
+
+    /// Argon2id cost new password hashes are made with
+    pub fn password_hash_cost(&self) -> HashCost {
+        HashCost {
+            memory_kib: self.argon2_memory_kib,
+            iterations: self.argon2_iterations,
+            parallelism: self.argon2_parallelism,
+        }
+    }
+
+    /// How long an access token issued to `role` stays valid
+    pub fn jwt_expiration_hours_for(&self, role: &UserRole) -> i64 {
+        match role {
+            UserRole::Admin => self.jwt_expiration_hours_admin,
+            UserRole::Driver => self.jwt_expiration_hours_driver,
+            UserRole::Traveller => self.jwt_expiration_hours_traveller,
         }
     }
 
@@ -34,3 +283,199 @@ impl Config {
         format!("{}:{}", self.server_host, self.server_port)
     }
 }
+
+/// Parse a comma-separated list of origins, ignoring blank entries
+pub fn parse_allowed_origins(value: &str) -> Result<Vec<HeaderValue>, InvalidHeaderValue> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .map(HeaderValue::from_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_secret(secret: &str) -> Config {
+        Config {
+            database_url: "postgres://localhost/bus_travel".to_string(),
+            db_max_connections: 10,
+            db_min_connections: 0,
+            db_connect_timeout_secs: 30,
+            db_idle_timeout_secs: 600,
+            jwt_secret: secret.to_string(),
+            argon2_memory_kib: 19456,
+            argon2_iterations: 2,
+            argon2_parallelism: 1,
+            jwt_issuer: "bus-travel-backend".to_string(),
+            jwt_audience: "bus-travel-api".to_string(),
+            jwt_expiration_hours: 24,
+            jwt_expiration_hours_admin: 24,
+            jwt_expiration_hours_driver: 24,
+            jwt_expiration_hours_traveller: 24,
+            refresh_token_expiration_days: 30,
+            server_host: "0.0.0.0".to_string(),
+            server_port: 3000,
+            oauth_client_id: "client-id".to_string(),
+            force_https: false,
+            https_redirect: false,
+            allowed_origins: None,
+            rate_limit_traveller_per_min: 100,
+            rate_limit_driver_per_min: 500,
+            rate_limit_global_burst: 1000,
+            request_timeout_secs: 30,
+            max_seats_per_booking: 10,
+            max_bookings_per_user_per_journey: 1,
+            max_journey_km: 1500.0,
+            cancellation_cutoff_hours: 0,
+            log_format: LogFormat::Pretty,
+            admin_email: None,
+            admin_password: None,
+            metrics_addr: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_short_jwt_secret() {
+        for secret in ["", "abcd", &"x".repeat(MIN_JWT_SECRET_BYTES - 1)] {
+            let err = config_with_secret(secret).validate().unwrap_err();
+            assert!(err.contains("JWT_SECRET"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_long_jwt_secret() {
+        assert!(
+            config_with_secret(&"x".repeat(MIN_JWT_SECRET_BYTES))
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            config_with_secret("your-secret-key-change-in-production")
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_db_connect_options() {
+        let config = Config {
+            db_max_connections: 25,
+            db_min_connections: 5,
+            db_connect_timeout_secs: 8,
+            db_idle_timeout_secs: 120,
+            ..config_with_secret(&"x".repeat(MIN_JWT_SECRET_BYTES))
+        };
+
+        let options = config.db_connect_options();
+        assert_eq!(options.get_url(), "postgres://localhost/bus_travel");
+        assert_eq!(options.get_max_connections(), Some(25));
+        assert_eq!(options.get_min_connections(), Some(5));
+        assert_eq!(options.get_connect_timeout(), Some(Duration::from_secs(8)));
+        assert_eq!(options.get_idle_timeout(), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_pool_bounds() {
+        let secret = "x".repeat(MIN_JWT_SECRET_BYTES);
+        let empty_pool = Config {
+            db_max_connections: 0,
+            ..config_with_secret(&secret)
+        };
+        assert!(empty_pool.validate().is_err());
+
+        let inverted = Config {
+            db_min_connections: 11,
+            ..config_with_secret(&secret)
+        };
+        let err = inverted.validate().unwrap_err();
+        assert!(err.contains("DB_MIN_CONNECTIONS"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_bookings_per_journey() {
+        let config = Config {
+            max_bookings_per_user_per_journey: 0,
+            ..config_with_secret(&"x".repeat(MIN_JWT_SECRET_BYTES))
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("MAX_BOOKINGS_PER_USER_PER_JOURNEY"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_request_timeout() {
+        let config = Config {
+            request_timeout_secs: 0,
+            ..config_with_secret(&"x".repeat(MIN_JWT_SECRET_BYTES))
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("REQUEST_TIMEOUT_SECS"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_argon2_cost() {
+        let config = Config {
+            argon2_iterations: 0,
+            ..config_with_secret(&"x".repeat(MIN_JWT_SECRET_BYTES))
+        };
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("ARGON2_ITERATIONS"), "{}", err);
+    }
+
+    #[test]
+    fn test_jwt_expiration_per_role() {
+        let config = Config {
+            jwt_expiration_hours_admin: 1,
+            jwt_expiration_hours_driver: 12,
+            ..config_with_secret(&"x".repeat(MIN_JWT_SECRET_BYTES))
+        };
+
+        assert_eq!(config.jwt_expiration_hours_for(&UserRole::Admin), 1);
+        assert_eq!(config.jwt_expiration_hours_for(&UserRole::Driver), 12);
+        assert_eq!(config.jwt_expiration_hours_for(&UserRole::Traveller), 24);
+    }
+
+    #[test]
+    fn test_parse_allowed_origins_trims_whitespace() {
+        let origins =
+            parse_allowed_origins(" https://app.example.com ,https://admin.example.com,, ")
+                .unwrap();
+
+        assert_eq!(
+            origins,
+            vec![
+                HeaderValue::from_static("https://app.example.com"),
+                HeaderValue::from_static("https://admin.example.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_allowed_origins_empty() {
+        assert!(parse_allowed_origins("").unwrap().is_empty());
+        assert!(parse_allowed_origins(" , ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_allowed_origins_rejects_invalid_value() {
+        assert!(parse_allowed_origins("https://ok.example.com,bad\norigin").is_err());
+    }
+
+    #[test]
+    fn test_log_format_selection() {
+        assert_eq!(LogFormat::from_env_value(None), Ok(LogFormat::Pretty));
+        assert_eq!(LogFormat::from_env_value(Some("")), Ok(LogFormat::Pretty));
+        assert_eq!(
+            LogFormat::from_env_value(Some("pretty")),
+            Ok(LogFormat::Pretty)
+        );
+        assert_eq!(LogFormat::from_env_value(Some("json")), Ok(LogFormat::Json));
+        assert_eq!(
+            LogFormat::from_env_value(Some(" JSON ")),
+            Ok(LogFormat::Json)
+        );
+        assert!(LogFormat::from_env_value(Some("xml")).is_err());
+    }
+}
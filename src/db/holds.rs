@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter,
+    QuerySelect, QueryTrait,
+};
+use uuid::Uuid;
+
+use crate::entities::seat_hold;
+use crate::error::AppResult;
+
+/// How long held seats stay reserved before they go back on sale
+pub const SEAT_HOLD_MINUTES: i64 = 10;
+
+#[derive(Debug, FromQueryResult)]
+struct JourneyHeld {
+    journey_id: Uuid,
+    held: i64,
+}
+
+/// Seats in unexpired holds per journey, in a single grouped query.
+/// Journeys without holds are absent from the map.
+pub async fn held_seats_by_journey(
+    db: &DatabaseConnection,
+    journey_ids: &[Uuid],
+) -> AppResult<HashMap<Uuid, i32>> {
+    if journey_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = seat_hold::Entity::find()
+        .select_only()
+        .column(seat_hold::Column::JourneyId)
+        .column_as(seat_hold::Column::Seats.sum(), "held")
+        .filter(seat_hold::Column::JourneyId.is_in(journey_ids.iter().copied()))
+        .filter(seat_hold::Column::ExpiresAt.gt(Utc::now()))
+        .group_by(seat_hold::Column::JourneyId)
+        .into_model::<JourneyHeld>()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| (r.journey_id, r.held as i32))
+        .collect())
+}
+
+/// Seats in a journey's unexpired holds, optionally leaving one hold out
+pub async fn held_seats<C: ConnectionTrait>(
+    db: &C,
+    journey_id: Uuid,
+    excluding: Option<Uuid>,
+) -> AppResult<i32> {
+    Ok(seat_hold::Entity::find()
+        .filter(seat_hold::Column::JourneyId.eq(journey_id))
+        .filter(seat_hold::Column::ExpiresAt.gt(Utc::now()))
+        .apply_if(excluding, |q, id| q.filter(seat_hold::Column::Id.ne(id)))
+        .all(db)
+        .await?
+        .iter()
+        .map(|h| h.seats)
+        .sum())
+}
+
+/// Delete holds past their expiry. Returns the number removed.
+pub async fn purge_expired_holds(db: &DatabaseConnection) -> AppResult<u64> {
+    let result = seat_hold::Entity::delete_many()
+        .filter(seat_hold::Column::ExpiresAt.lt(Utc::now()))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected)
+}
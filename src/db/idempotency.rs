@@ -0,0 +1,86 @@
+use chrono::{Duration, Utc};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, SqlErr,
+};
+use uuid::Uuid;
+
+use crate::entities::booking_idempotency_key;
+use crate::error::{AppError, AppResult};
+
+/// How long a retry with the same `Idempotency-Key` returns the original booking
+pub const IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+
+/// The booking an earlier request with this key created, unless the key has expired
+pub async fn find_idempotent_booking<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    key: &str,
+) -> AppResult<Option<Uuid>> {
+    Ok(
+        booking_idempotency_key::Entity::find_by_id((user_id, key.to_string()))
+            .filter(booking_idempotency_key::Column::ExpiresAt.gt(Utc::now()))
+            .one(db)
+            .await?
+            .map(|k| k.booking_id),
+    )
+}
+
+/// Record that `key` created `booking_id`, replacing an expired mapping.
+/// A live mapping means a concurrent request with the same key got there first.
+pub async fn remember_idempotency_key<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    key: &str,
+    booking_id: Uuid,
+) -> AppResult<()> {
+    let now = Utc::now();
+
+    booking_idempotency_key::Entity::delete_many()
+        .filter(booking_idempotency_key::Column::UserId.eq(user_id))
+        .filter(booking_idempotency_key::Column::IdempotencyKey.eq(key))
+        .filter(booking_idempotency_key::Column::ExpiresAt.lte(now))
+        .exec(db)
+        .await?;
+
+    let result = booking_idempotency_key::Entity::insert(booking_idempotency_key::ActiveModel {
+        user_id: Set(user_id),
+        idempotency_key: Set(key.to_string()),
+        booking_id: Set(booking_id),
+        expires_at: Set((now + Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS)).into()),
+    })
+    .exec(db)
+    .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+            Err(AppError::Conflict(
+                "Another request with this Idempotency-Key is in progress; retry it".to_string(),
+            ))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Free the keys that created `booking_id`, so a cancelled booking isn't replayed
+pub async fn forget_idempotency_keys<C: ConnectionTrait>(
+    db: &C,
+    booking_id: Uuid,
+) -> AppResult<()> {
+    booking_idempotency_key::Entity::delete_many()
+        .filter(booking_idempotency_key::Column::BookingId.eq(booking_id))
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Delete idempotency keys past their expiry. Returns the number removed.
+pub async fn purge_expired_idempotency_keys(db: &DatabaseConnection) -> AppResult<u64> {
+    let result = booking_idempotency_key::Entity::delete_many()
+        .filter(booking_idempotency_key::Column::ExpiresAt.lt(Utc::now()))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected)
+}
@@ -1,10 +1,228 @@
-use sea_orm::{Database, DatabaseConnection};
+pub mod holds;
+pub mod idempotency;
+pub mod revocation;
+pub mod seed;
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, Database, DatabaseConnection, EntityTrait,
+    FromQueryResult, QueryFilter, QueryOrder, QuerySelect, Set, SqlErr, TransactionTrait,
+    sea_query::{Expr, Func, Query, SimpleExpr},
+};
+use uuid::Uuid;
 
 use crate::config::Config;
+use crate::entities::booking::{self, BookingStatus};
+use crate::entities::{journey, journey_stop, seat_hold};
 use crate::error::{AppError, AppResult};
 
 pub async fn connect(config: &Config) -> AppResult<DatabaseConnection> {
-    Database::connect(&config.database_url)
+    Database::connect(config.db_connect_options())
         .await
         .map_err(|e| AppError::Internal(format!("Failed to connect to database: {}", e)))
 }
+
+#[derive(Debug, FromQueryResult)]
+struct JourneySeats {
+    journey_id: Uuid,
+    booked: i64,
+}
+
+/// Total confirmed seats per journey, in a single grouped query.
+/// Journeys without bookings are absent from the map.
+pub async fn booked_seats_by_journey(
+    db: &DatabaseConnection,
+    journey_ids: &[Uuid],
+) -> AppResult<HashMap<Uuid, i32>> {
+    if journey_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = booking::Entity::find()
+        .select_only()
+        .column(booking::Column::JourneyId)
+        .column_as(booking::Column::Seats.sum(), "booked")
+        .filter(booking::Column::JourneyId.is_in(journey_ids.iter().copied()))
+        .filter(booking::Column::Status.eq(BookingStatus::Confirmed))
+        .group_by(booking::Column::JourneyId)
+        .into_model::<JourneySeats>()
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|r| (r.journey_id, r.booked as i32))
+        .collect())
+}
+
+/// Intermediate stops per journey in route order, in a single query.
+/// Journeys without stops are absent from the map.
+pub async fn stops_by_journey(
+    db: &DatabaseConnection,
+    journey_ids: &[Uuid],
+) -> AppResult<HashMap<Uuid, Vec<journey_stop::Model>>> {
+    if journey_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let stops = journey_stop::Entity::find()
+        .filter(journey_stop::Column::JourneyId.is_in(journey_ids.iter().copied()))
+        .order_by_asc(journey_stop::Column::StopOrder)
+        .all(db)
+        .await?;
+
+    let mut by_journey: HashMap<Uuid, Vec<journey_stop::Model>> = HashMap::new();
+    for stop in stops {
+        by_journey.entry(stop.journey_id).or_default().push(stop);
+    }
+    Ok(by_journey)
+}
+
+/// Draws of a fresh reference code before giving up on inserting a booking
+const MAX_REFERENCE_CODE_ATTEMPTS: usize = 5;
+
+/// Insert a booking under a reference code from `next_code`, drawing again when the code
+/// is already taken. Each attempt runs in a savepoint so a clash doesn't abort `db`'s
+/// enclosing transaction.
+pub async fn insert_booking<C>(
+    db: &C,
+    booking: booking::ActiveModel,
+    mut next_code: impl FnMut() -> String,
+) -> AppResult<booking::Model>
+where
+    C: ConnectionTrait + TransactionTrait,
+{
+    for attempt in 1.. {
+        let mut candidate = booking.clone();
+        candidate.reference_code = Set(next_code());
+
+        let savepoint = db.begin().await?;
+        match candidate.insert(&savepoint).await {
+            Ok(inserted) => {
+                savepoint.commit().await?;
+                return Ok(inserted);
+            }
+            Err(e)
+                if attempt < MAX_REFERENCE_CODE_ATTEMPTS
+                    && matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) =>
+            {
+                savepoint.rollback().await?;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+    unreachable!()
+}
+
+/// Seats in the journey row's confirmed bookings, as a correlated subquery
+fn booked_seats_expr() -> SimpleExpr {
+    let booked = Query::select()
+        .expr(Func::coalesce([
+            Expr::col((booking::Entity, booking::Column::Seats)).sum(),
+            Expr::val(0).into(),
+        ]))
+        .from(booking::Entity)
+        .and_where(
+            Expr::col((booking::Entity, booking::Column::JourneyId))
+                .equals((journey::Entity, journey::Column::Id)),
+        )
+        .and_where(booking::Column::Status.eq(BookingStatus::Confirmed))
+        .to_owned();
+    SimpleExpr::SubQuery(None, Box::new(booked.into_sub_query_statement()))
+}
+
+/// Seats in the journey row's unexpired holds, as a correlated subquery
+fn held_seats_expr() -> SimpleExpr {
+    let held = Query::select()
+        .expr(Func::coalesce([
+            Expr::col((seat_hold::Entity, seat_hold::Column::Seats)).sum(),
+            Expr::val(0).into(),
+        ]))
+        .from(seat_hold::Entity)
+        .and_where(
+            Expr::col((seat_hold::Entity, seat_hold::Column::JourneyId))
+                .equals((journey::Entity, journey::Column::Id)),
+        )
+        .and_where(seat_hold::Column::ExpiresAt.gt(Utc::now()))
+        .to_owned();
+    SimpleExpr::SubQuery(None, Box::new(held.into_sub_query_statement()))
+}
+
+/// Filter for journey queries: confirmed bookings and unexpired holds leave at least one
+/// seat free. Runs as correlated subqueries so it can be combined with pagination.
+pub fn has_free_seats() -> SimpleExpr {
+    let taken = booked_seats_expr().add(held_seats_expr());
+    Expr::col((journey::Entity, journey::Column::TotalSeats)).gt(taken)
+}
+
+/// A journey's capacity, confirmed seats and held seats
+#[derive(Debug, FromQueryResult)]
+pub struct SeatCounts {
+    pub total_seats: i32,
+    pub booked: i64,
+    pub held: i64,
+}
+
+impl SeatCounts {
+    /// Seats neither booked nor held; never negative, even when an admin has overbooked
+    pub fn available(&self) -> i32 {
+        (i64::from(self.total_seats) - self.booked - self.held).max(0) as i32
+    }
+}
+
+/// Seat counts for one journey in a single query, or `None` if it doesn't exist
+pub async fn seat_counts(
+    db: &DatabaseConnection,
+    journey_id: Uuid,
+) -> AppResult<Option<SeatCounts>> {
+    Ok(journey::Entity::find_by_id(journey_id)
+        .select_only()
+        .column(journey::Column::TotalSeats)
+        .expr_as(booked_seats_expr(), "booked")
+        .expr_as(held_seats_expr(), "held")
+        .into_model::<SeatCounts>()
+        .one(db)
+        .await?)
+}
+
+/// Confirm waitlisted bookings in booking order while they fit in the free seats.
+/// Seats in unexpired holds are not free. Call inside a transaction holding a lock on
+/// the journey row.
+pub async fn promote_waitlisted<C: ConnectionTrait>(
+    db: &C,
+    journey: &journey::Model,
+) -> AppResult<Vec<booking::Model>> {
+    let bookings = booking::Entity::find()
+        .filter(booking::Column::JourneyId.eq(journey.id))
+        .order_by_asc(booking::Column::CreatedAt)
+        .all(db)
+        .await?;
+
+    let confirmed: i32 = bookings
+        .iter()
+        .filter(|b| b.status == BookingStatus::Confirmed)
+        .map(|b| b.seats)
+        .sum();
+    let mut available =
+        journey.total_seats - confirmed - holds::held_seats(db, journey.id, None).await?;
+
+    let mut promoted = Vec::new();
+    for b in bookings
+        .into_iter()
+        .filter(|b| b.status == BookingStatus::Waitlisted)
+    {
+        // First come, first served: nobody jumps ahead of a larger party that doesn't fit yet
+        if b.seats > available {
+            break;
+        }
+        available -= b.seats;
+
+        let mut active: booking::ActiveModel = b.into();
+        active.status = Set(BookingStatus::Confirmed);
+        promoted.push(active.update(db).await?);
+    }
+
+    Ok(promoted)
+}
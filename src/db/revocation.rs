@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::entities::revoked_token;
+use crate::error::{AppError, AppResult};
+use crate::utils::jwt::Claims;
+
+/// Revoke a token until its natural expiry. Revoking twice is a no-op.
+pub async fn revoke_token(db: &DatabaseConnection, claims: &Claims) -> AppResult<()> {
+    let expires_at = DateTime::<Utc>::from_timestamp(claims.exp, 0)
+        .ok_or_else(|| AppError::Internal("Invalid token expiry".to_string()))?;
+
+    revoked_token::Entity::insert(revoked_token::ActiveModel {
+        jti: Set(claims.jti),
+        expires_at: Set(expires_at.into()),
+    })
+    .on_conflict(
+        OnConflict::column(revoked_token::Column::Jti)
+            .do_nothing()
+            .to_owned(),
+    )
+    .do_nothing()
+    .exec(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Whether a token has been revoked
+pub async fn is_token_revoked(db: &DatabaseConnection, jti: Uuid) -> AppResult<bool> {
+    Ok(revoked_token::Entity::find_by_id(jti)
+        .one(db)
+        .await?
+        .is_some())
+}
+
+/// Delete revocations for tokens that have expired anyway. Returns the number removed.
+pub async fn purge_expired_revocations(db: &DatabaseConnection) -> AppResult<u64> {
+    let result = revoked_token::Entity::delete_many()
+        .filter(revoked_token::Column::ExpiresAt.lt(Utc::now()))
+        .exec(db)
+        .await?;
+
+    Ok(result.rows_affected)
+}
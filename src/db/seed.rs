@@ -0,0 +1,52 @@
+use sea_orm::{DatabaseConnection, EntityTrait, Set, TryInsertResult, sea_query::OnConflict};
+use uuid::Uuid;
+
+use crate::entities::user::{self, UserRole};
+use crate::error::AppResult;
+use crate::utils::password::{HashCost, hash_password};
+use crate::utils::validation::normalize_email;
+
+/// The password older releases seeded the admin with; refused outside debug builds
+pub const WELL_KNOWN_ADMIN_PASSWORD: &str = "admin123";
+
+/// Create the admin account unless an account with this email already exists.
+/// Returns the new account, or `None` when there was nothing to do. Safe to run from
+/// several instances at once: only one insert wins, the others find the account there.
+pub async fn seed_admin(
+    db: &DatabaseConnection,
+    email: &str,
+    password: &str,
+    cost: &HashCost,
+) -> AppResult<Option<user::Model>> {
+    let email = normalize_email(email);
+
+    // Deleted accounts count too, so a removed admin isn't silently recreated.
+    // Checking first also skips hashing the password on every start.
+    if user::Entity::find_by_email(&email).one(db).await?.is_some() {
+        return Ok(None);
+    }
+
+    let id = Uuid::new_v4();
+    let admin = user::ActiveModel {
+        id: Set(id),
+        email: Set(email),
+        password_hash: Set(Some(hash_password(password, cost)?)),
+        google_id: Set(None),
+        name: Set("Admin".to_string()),
+        role: Set(UserRole::Admin),
+        ..Default::default()
+    };
+
+    // Another instance may have created the account since the check above; any
+    // unique index (including the case-insensitive email one) makes this a no-op then
+    let inserted = user::Entity::insert(admin)
+        .on_conflict(OnConflict::new().do_nothing().to_owned())
+        .do_nothing()
+        .exec_without_returning(db)
+        .await?;
+
+    match inserted {
+        TryInsertResult::Inserted(1) => Ok(user::Entity::find_by_id(id).one(db).await?),
+        _ => Ok(None),
+    }
+}
@@ -1,17 +1,55 @@
+use sea_orm::Set;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "booking_status")]
+#[serde(rename_all = "snake_case")]
+pub enum BookingStatus {
+    /// Holds its seats on the journey
+    #[sea_orm(string_value = "confirmed")]
+    Confirmed,
+    /// Waiting for seats to free up; promoted in booking order
+    #[sea_orm(string_value = "waitlisted")]
+    Waitlisted,
+    /// Cancelled by the traveller; kept for their history but holds no seats
+    #[sea_orm(string_value = "cancelled")]
+    Cancelled,
+}
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "booking")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
+    /// Short code travellers can read out instead of the UUID, e.g. `7K3QZ9`
+    #[sea_orm(unique)]
+    pub reference_code: String,
     pub journey_id: Uuid,
     pub user_id: Uuid,
     pub seats: i32,
     pub pickup_lat: f64,
     pub pickup_lng: f64,
+    /// Fare charged for all seats in cents, fixed at booking time
+    pub total_fare: i64,
+    pub status: BookingStatus,
+    /// When the booking was cancelled, if it has been
+    pub cancelled_at: Option<DateTimeWithTimeZone>,
     pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -42,4 +80,16 @@ impl Related<super::user::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// Bump `updated_at` on every update so it can serve as a version marker
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert {
+            self.updated_at = Set(chrono::Utc::now().into());
+        }
+        Ok(self)
+    }
+}
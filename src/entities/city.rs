@@ -1,6 +1,17 @@
-use sea_orm::entity::prelude::*;
+use sea_orm::{FromJsonQueryResult, entity::prelude::*};
 use serde::{Deserialize, Serialize};
 
+/// Outline of a city's pickup area as `[lat, lng]` vertices, in order around the edge
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult)]
+pub struct PickupPolygon(pub Vec<[f64; 2]>);
+
+impl PickupPolygon {
+    /// Vertices as `(lat, lng)` pairs
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        self.0.iter().map(|&[lat, lng]| (lat, lng)).collect()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "city")]
 pub struct Model {
@@ -11,9 +22,33 @@ pub struct Model {
     pub center_lat: f64,
     pub center_lng: f64,
     pub pickup_radius_km: f64,
+    pub timezone: String,
+    pub operating_start: Option<Time>,
+    pub operating_end: Option<Time>,
+    /// Pickup area, used instead of `pickup_radius_km` when set
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub pickup_polygon: Option<PickupPolygon>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
+
+#[cfg(test)]
+impl Model {
+    /// An unsaved city with a 10 km pickup radius, for unit tests
+    pub(crate) fn test_at(name: &str, lat: f64, lng: f64) -> Self {
+        Self {
+            id: 0,
+            name: name.to_string(),
+            center_lat: lat,
+            center_lng: lng,
+            pickup_radius_km: 10.0,
+            timezone: "Asia/Jakarta".to_string(),
+            operating_start: None,
+            operating_end: None,
+            pickup_polygon: None,
+        }
+    }
+}
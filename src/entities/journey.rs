@@ -1,5 +1,33 @@
+use sea_orm::Set;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "journey_status")]
+#[serde(rename_all = "snake_case")]
+pub enum JourneyStatus {
+    #[sea_orm(string_value = "scheduled")]
+    Scheduled,
+    #[sea_orm(string_value = "in_progress")]
+    InProgress,
+    #[sea_orm(string_value = "completed")]
+    Completed,
+    #[sea_orm(string_value = "cancelled")]
+    Cancelled,
+}
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "journey")]
@@ -11,7 +39,15 @@ pub struct Model {
     pub departure_time: DateTimeWithTimeZone,
     pub total_seats: i32,
     pub driver_id: Option<Uuid>,
+    pub status: JourneyStatus,
+    /// Price per seat in cents
+    pub base_fare: i64,
+    /// Booking closes this many minutes before departure
+    pub booking_close_minutes: i32,
+    /// Admin who created the journey; `None` for older journeys or a deleted account
+    pub created_by: Option<Uuid>,
     pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -44,4 +80,16 @@ impl Related<super::booking::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    /// Bump `updated_at` on every update
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        if !insert {
+            self.updated_at = Set(chrono::Utc::now().into());
+        }
+        Ok(self)
+    }
+}
@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// An intermediate city a journey calls at. `stop_order` counts from 1 along the route.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "journey_stop")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub journey_id: Uuid,
+    pub city_id: i32,
+    pub stop_order: i32,
+    pub scheduled_time: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::journey::Entity",
+        from = "Column::JourneyId",
+        to = "super::journey::Column::Id"
+    )]
+    Journey,
+    #[sea_orm(
+        belongs_to = "super::city::Entity",
+        from = "Column::CityId",
+        to = "super::city::Column::Id"
+    )]
+    City,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,5 +1,10 @@
 pub mod booking;
+pub mod booking_idempotency_key;
 pub mod city;
 pub mod journey;
+pub mod journey_stop;
 pub mod prelude;
+pub mod revoked_token;
+pub mod role_change_audit;
+pub mod seat_hold;
 pub mod user;
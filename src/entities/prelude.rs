@@ -1,4 +1,9 @@
 pub use super::booking::Entity as Booking;
+pub use super::booking_idempotency_key::Entity as BookingIdempotencyKey;
 pub use super::city::Entity as City;
 pub use super::journey::Entity as Journey;
+pub use super::journey_stop::Entity as JourneyStop;
+pub use super::revoked_token::Entity as RevokedToken;
+pub use super::role_change_audit::Entity as RoleChangeAudit;
+pub use super::seat_hold::Entity as SeatHold;
 pub use super::user::Entity as User;
@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Seats reserved on a journey for a few minutes while the traveller checks out.
+/// Expired holds are ignored by availability checks until the cleanup task deletes them.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "seat_hold")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub journey_id: Uuid,
+    pub user_id: Uuid,
+    pub seats: i32,
+    pub pickup_lat: f64,
+    pub pickup_lng: f64,
+    pub expires_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::journey::Entity",
+        from = "Column::JourneyId",
+        to = "super::journey::Column::Id"
+    )]
+    Journey,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::UserId",
+        to = "super::user::Column::Id"
+    )]
+    User,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
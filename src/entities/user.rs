@@ -1,14 +1,22 @@
 use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Func, SimpleExpr};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema,
+)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "user_role")]
 pub enum UserRole {
+    // Aliases accept the lowercase spelling used in query strings and the API docs
     #[sea_orm(string_value = "admin")]
+    #[serde(alias = "admin")]
     Admin,
     #[sea_orm(string_value = "driver")]
+    #[serde(alias = "driver")]
     Driver,
     #[sea_orm(string_value = "traveller")]
+    #[serde(alias = "traveller")]
     Traveller,
 }
 
@@ -26,6 +34,12 @@ pub struct Model {
     pub name: String,
     pub role: UserRole,
     pub created_at: DateTimeWithTimeZone,
+    /// Registered via the driver sign-up and waiting for an admin to approve
+    pub driver_application_pending: bool,
+    /// Soft-delete marker; deleted users can't sign in and are hidden from listings
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+    /// Contact number; a driver's is revealed to their confirmed passengers
+    pub phone: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -40,4 +54,26 @@ impl Related<super::booking::Entity> for Entity {
     }
 }
 
+/// `lower(email) = lower(<email>)`, the form `idx_user_email_lower` keeps unique
+pub fn email_eq(email: &str) -> SimpleExpr {
+    Expr::expr(Func::lower(Expr::col(Column::Email))).eq(email.to_lowercase())
+}
+
+impl Entity {
+    /// Users that haven't been soft-deleted
+    pub fn find_active() -> Select<Entity> {
+        Self::find().filter(Column::DeletedAt.is_null())
+    }
+
+    /// A user by id, unless soft-deleted
+    pub fn find_active_by_id(id: Uuid) -> Select<Entity> {
+        Self::find_by_id(id).filter(Column::DeletedAt.is_null())
+    }
+
+    /// Users whose email matches ignoring case
+    pub fn find_by_email(email: &str) -> Select<Entity> {
+        Self::find().filter(email_eq(email))
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
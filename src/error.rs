@@ -1,10 +1,17 @@
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use sea_orm::{ConnAcquireErr, DbErr};
 use serde_json::json;
 use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::middleware::request_id::current_request_id;
+
+/// Seconds clients are asked to wait before retrying when the connection pool is exhausted
+const POOL_EXHAUSTED_RETRY_AFTER_SECS: u64 = 5;
 
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -26,12 +33,89 @@ pub enum AppError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Precondition required: {0}")]
+    PreconditionRequired(String),
+
     #[error("Internal server error: {0}")]
     Internal(String),
 }
 
+impl AppError {
+    /// Stable machine-readable code for the error kind, sent alongside the message
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Database(e) if is_pool_exhausted(e) => "SERVICE_UNAVAILABLE",
+            AppError::Database(_) | AppError::Internal(_) => "INTERNAL_ERROR",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::BadRequest(_) => "BAD_REQUEST",
+            AppError::Unauthorized(_) => "UNAUTHORIZED",
+            AppError::Forbidden(_) => "FORBIDDEN",
+            AppError::Conflict(_) => "CONFLICT",
+            AppError::PreconditionFailed(_) => "PRECONDITION_FAILED",
+            AppError::PreconditionRequired(_) => "PRECONDITION_REQUIRED",
+        }
+    }
+}
+
+/// Body of every error response, as built by `error_body` (for the OpenAPI spec)
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct ErrorResponse {
+    error: ErrorDetail,
+}
+
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct ErrorDetail {
+    /// e.g. `NOT_FOUND`
+    code: String,
+    message: String,
+    request_id: Option<String>,
+}
+
+/// Body shared by every error response: `{ "error": { "code": ..., "message": ... } }`,
+/// plus `request_id` when raised while handling a request
+pub(crate) fn error_body(code: &str, message: &str) -> Json<serde_json::Value> {
+    let mut body = json!({
+        "error": {
+            "code": code,
+            "message": message,
+        }
+    });
+    if let Some(request_id) = current_request_id() {
+        body["error"]["request_id"] = request_id.into();
+    }
+    Json(body)
+}
+
+/// Whether a database error means no pooled connection could be acquired
+/// (as opposed to a query or data error)
+fn is_pool_exhausted(err: &DbErr) -> bool {
+    matches!(
+        err,
+        DbErr::ConnectionAcquire(ConnAcquireErr::Timeout | ConnAcquireErr::ConnectionClosed)
+    )
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        // Pool exhaustion is transient: tell the client to back off instead of reporting a bug
+        if let AppError::Database(e) = &self
+            && is_pool_exhausted(e)
+        {
+            tracing::warn!("Database pool exhausted: {:?}", e);
+            let body = error_body(self.code(), "Service temporarily unavailable, please retry");
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::RETRY_AFTER, POOL_EXHAUSTED_RETRY_AFTER_SECS.to_string())],
+                body,
+            )
+                .into_response();
+        }
+
         let (status, message) = match &self {
             AppError::Database(e) => {
                 tracing::error!("Database error: {:?}", e);
@@ -42,18 +126,68 @@ impl IntoResponse for AppError {
             AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
             AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg.clone()),
             AppError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            AppError::PreconditionFailed(msg) => (StatusCode::PRECONDITION_FAILED, msg.clone()),
+            AppError::PreconditionRequired(msg) => (StatusCode::PRECONDITION_REQUIRED, msg.clone()),
             AppError::Internal(msg) => {
                 tracing::error!("Internal error: {}", msg);
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
             }
         };
 
-        let body = Json(json!({
-            "error": message
-        }));
-
-        (status, body).into_response()
+        (status, error_body(self.code(), &message)).into_response()
     }
 }
 
 pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_timeout_maps_to_503() {
+        let err = AppError::Database(DbErr::ConnectionAcquire(ConnAcquireErr::Timeout));
+        let response = err.into_response();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers().get(header::RETRY_AFTER).unwrap(),
+            &POOL_EXHAUSTED_RETRY_AFTER_SECS.to_string()
+        );
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_not_found_carries_code() {
+        let response = AppError::NotFound("Journey not found".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let body = body_json(response).await;
+        assert_eq!(body["error"]["code"], "NOT_FOUND");
+        assert_eq!(body["error"]["message"], "Journey not found");
+        // Outside a request there is no id to report
+        assert!(body["error"].get("request_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pool_timeout_carries_code() {
+        let err = AppError::Database(DbErr::ConnectionAcquire(ConnAcquireErr::Timeout));
+        let body = body_json(err.into_response()).await;
+        assert_eq!(body["error"]["code"], "SERVICE_UNAVAILABLE");
+    }
+
+    #[test]
+    fn test_other_db_errors_map_to_500() {
+        let err = AppError::Database(DbErr::Custom("boom".to_string()));
+        let response = err.into_response();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
+    }
+}
@@ -1,18 +1,470 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Extension, Path, Query, State},
+    http::{HeaderMap, HeaderName, header},
+    response::{IntoResponse, Response},
     Json,
 };
-use chrono::{DateTime, Utc};
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbBackend, EntityTrait,
+    FromQueryResult, JoinType, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    QueryTrait, RelationTrait, Set, Statement, TransactionTrait,
+    sea_query::{Expr, IntoCondition},
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::handlers::traveller::CityInfo;
-use crate::entities::{booking, city, journey, user};
+use crate::handlers::traveller::{
+    CityInfo, JourneyStopInfo, calculate_fare, cancellation_allowed, local_departure, stop_infos,
+};
+use crate::db::idempotency::forget_idempotency_keys;
+use crate::db::{booked_seats_by_journey, promote_waitlisted, stops_by_journey};
+use crate::entities::{booking, city, journey, journey_stop, role_change_audit, user};
+use crate::entities::booking::BookingStatus;
+use crate::entities::city::PickupPolygon;
+use crate::entities::journey::JourneyStatus;
 use crate::entities::user::UserRole;
 use crate::error::{AppError, AppResult};
+use crate::handlers::auth::insert_account;
+use crate::utils::csv::{CsvResponse, parse_csv_line, wants_csv};
+use crate::utils::etag::{booking_etag, check_if_match};
+use crate::utils::geo::haversine_distance;
+use crate::utils::jwt::Claims;
+use crate::utils::pagination::{Cursor, CursorPage, CursorQuery, Paginated, Pagination};
+use crate::utils::password::hash_password;
+use crate::utils::sort::{SortOption, SortQuery};
+use crate::utils::time::{is_within_operating_hours, local_time_of_day, parse_timezone};
+use crate::utils::validation::{
+    normalize_email, normalize_name, validate_coordinates, validate_email, validate_password,
+    validate_phone,
+};
 use crate::AppState;
 
+// ============ City Management ============
+
+/// List all cities
+pub async fn list_cities(State(state): State<AppState>) -> AppResult<Json<Vec<CityInfo>>> {
+    let cities = city::Entity::find().all(&state.db).await?;
+
+    let responses: Vec<CityInfo> = cities
+        .into_iter()
+        .map(|c| CityInfo {
+            id: c.id,
+            name: c.name,
+            center_lat: c.center_lat,
+            center_lng: c.center_lng,
+            pickup_radius_km: c.pickup_radius_km,
+            timezone: c.timezone,
+            pickup_polygon: c.pickup_polygon.map(|p| p.0),
+        })
+        .collect();
+
+    Ok(Json(responses))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCityRequest {
+    pub name: String,
+    pub center_lat: f64,
+    pub center_lng: f64,
+    pub pickup_radius_km: f64,
+    /// IANA timezone name; defaults to "Asia/Jakarta"
+    pub timezone: Option<String>,
+    /// `[lat, lng]` vertices of the pickup zone; the radius applies when absent
+    pub pickup_polygon: Option<Vec<[f64; 2]>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCityRequest {
+    pub name: Option<String>,
+    pub center_lat: Option<f64>,
+    pub center_lng: Option<f64>,
+    pub pickup_radius_km: Option<f64>,
+    pub timezone: Option<String>,
+    /// Replaces the pickup zone; an empty list removes it
+    pub pickup_polygon: Option<Vec<[f64; 2]>>,
+}
+
+/// Check a city's center coordinates and pickup radius
+fn validate_city_geometry(lat: f64, lng: f64, radius_km: f64) -> AppResult<()> {
+    validate_coordinates(lat, lng)?;
+
+    if !(radius_km > 0.0 && radius_km.is_finite()) {
+        return Err(AppError::BadRequest(
+            "Pickup radius must be positive".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check a pickup zone has at least three valid vertices; an empty list means no zone
+fn validate_pickup_polygon(vertices: Vec<[f64; 2]>) -> AppResult<Option<PickupPolygon>> {
+    if vertices.is_empty() {
+        return Ok(None);
+    }
+
+    if vertices.len() < 3 {
+        return Err(AppError::BadRequest(
+            "Pickup polygon needs at least 3 vertices".to_string(),
+        ));
+    }
+
+    for &[lat, lng] in &vertices {
+        validate_coordinates(lat, lng)?;
+    }
+
+    Ok(Some(PickupPolygon(vertices)))
+}
+
+/// Reject names chrono-tz doesn't know, rather than silently falling back to UTC
+fn validate_timezone(name: &str) -> AppResult<String> {
+    name.parse::<Tz>()
+        .map(|tz| tz.name().to_string())
+        .map_err(|_| AppError::BadRequest(format!("Unknown timezone: {}", name)))
+}
+
+/// Longest city name the `city.name` column holds
+const MAX_CITY_NAME_LEN: usize = 50;
+
+/// Trim a city name and make sure no other city already uses it
+async fn validate_city_name<C: ConnectionTrait>(
+    db: &C,
+    name: &str,
+    existing_id: Option<i32>,
+) -> AppResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::BadRequest("City name is required".to_string()));
+    }
+    if name.chars().count() > MAX_CITY_NAME_LEN {
+        return Err(AppError::BadRequest(format!(
+            "City name must be at most {} characters",
+            MAX_CITY_NAME_LEN
+        )));
+    }
+
+    let taken = city::Entity::find()
+        .filter(city::Column::Name.eq(name))
+        .one(db)
+        .await?;
+
+    if let Some(other) = taken
+        && Some(other.id) != existing_id
+    {
+        return Err(AppError::Conflict("City name already exists".to_string()));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Create a city (admin)
+pub async fn create_city(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateCityRequest>,
+) -> AppResult<Json<city::Model>> {
+    validate_city_geometry(
+        payload.center_lat,
+        payload.center_lng,
+        payload.pickup_radius_km,
+    )?;
+    let name = validate_city_name(&state.db, &payload.name, None).await?;
+
+    let mut city = city::ActiveModel {
+        name: Set(name),
+        center_lat: Set(payload.center_lat),
+        center_lng: Set(payload.center_lng),
+        pickup_radius_km: Set(payload.pickup_radius_km),
+        ..Default::default()
+    };
+
+    if let Some(timezone) = payload.timezone {
+        city.timezone = Set(validate_timezone(&timezone)?);
+    }
+
+    if let Some(vertices) = payload.pickup_polygon {
+        city.pickup_polygon = Set(validate_pickup_polygon(vertices)?);
+    }
+
+    let result = city.insert(&state.db).await?;
+    Ok(Json(result))
+}
+
+/// Update a city (admin)
+pub async fn update_city(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateCityRequest>,
+) -> AppResult<Json<city::Model>> {
+    let city = city::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("City not found".to_string()))?;
+
+    validate_city_geometry(
+        payload.center_lat.unwrap_or(city.center_lat),
+        payload.center_lng.unwrap_or(city.center_lng),
+        payload.pickup_radius_km.unwrap_or(city.pickup_radius_km),
+    )?;
+
+    let mut active: city::ActiveModel = city.into();
+
+    if let Some(name) = payload.name {
+        active.name = Set(validate_city_name(&state.db, &name, Some(id)).await?);
+    }
+
+    if let Some(lat) = payload.center_lat {
+        active.center_lat = Set(lat);
+    }
+
+    if let Some(lng) = payload.center_lng {
+        active.center_lng = Set(lng);
+    }
+
+    if let Some(radius) = payload.pickup_radius_km {
+        active.pickup_radius_km = Set(radius);
+    }
+
+    if let Some(timezone) = payload.timezone {
+        active.timezone = Set(validate_timezone(&timezone)?);
+    }
+
+    if let Some(vertices) = payload.pickup_polygon {
+        active.pickup_polygon = Set(validate_pickup_polygon(vertices)?);
+    }
+
+    let result = active.update(&state.db).await?;
+    Ok(Json(result))
+}
+
+/// Replace a city's center with the configured geocoder's coordinates for its name (admin)
+pub async fn refresh_city_geocode(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<city::Model>> {
+    let city = city::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("City not found".to_string()))?;
+
+    let (lat, lng) = state.geocoder.geocode(&city.name).await?;
+    validate_city_geometry(lat, lng, city.pickup_radius_km)?;
+
+    let mut active: city::ActiveModel = city.into();
+    active.center_lat = Set(lat);
+    active.center_lng = Set(lng);
+
+    let result = active.update(&state.db).await?;
+    Ok(Json(result))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CityImportQuery {
+    /// Reject the whole file if any row is invalid
+    #[serde(default)]
+    pub strict: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CityImportStatus {
+    Created,
+    /// A city with this name already exists, or appeared earlier in the file
+    Skipped,
+    Error,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CityImportRow {
+    /// 1-based line number in the uploaded file
+    pub line: usize,
+    pub name: Option<String>,
+    pub status: CityImportStatus,
+    pub city_id: Option<i32>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CityImportResponse {
+    pub created: usize,
+    pub skipped: usize,
+    pub errors: usize,
+    pub rows: Vec<CityImportRow>,
+}
+
+const CITY_IMPORT_COLUMNS: [&str; 4] = ["name", "center_lat", "center_lng", "pickup_radius_km"];
+
+/// Split a data row into name, center and radius, checking the geometry
+fn parse_city_row(line: &str) -> Result<(String, f64, f64, f64), String> {
+    let fields = parse_csv_line(line)?;
+    if fields.len() != CITY_IMPORT_COLUMNS.len() {
+        return Err(format!(
+            "Expected {} columns, found {}",
+            CITY_IMPORT_COLUMNS.len(),
+            fields.len()
+        ));
+    }
+
+    let mut numbers = [0.0; 3];
+    for (i, number) in numbers.iter_mut().enumerate() {
+        let raw = fields[i + 1].trim();
+        *number = raw
+            .parse()
+            .map_err(|_| format!("Invalid {}: '{}'", CITY_IMPORT_COLUMNS[i + 1], raw))?;
+    }
+    let [lat, lng, radius_km] = numbers;
+
+    validate_city_geometry(lat, lng, radius_km).map_err(|e| match e {
+        AppError::BadRequest(msg) => msg,
+        other => other.to_string(),
+    })?;
+
+    Ok((fields[0].clone(), lat, lng, radius_km))
+}
+
+/// Create cities from a CSV body with a `name,center_lat,center_lng,pickup_radius_km`
+/// header (admin). Every row is reported; valid ones are inserted in one transaction.
+pub async fn import_cities(
+    State(state): State<AppState>,
+    Query(query): Query<CityImportQuery>,
+    body: String,
+) -> AppResult<Json<CityImportResponse>> {
+    let mut lines = body
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+
+    let header = lines
+        .next()
+        .map(|(_, l)| parse_csv_line(l).unwrap_or_default())
+        .unwrap_or_default();
+    if !header.iter().map(|h| h.trim()).eq(CITY_IMPORT_COLUMNS) {
+        return Err(AppError::BadRequest(format!(
+            "CSV header must be: {}",
+            CITY_IMPORT_COLUMNS.join(",")
+        )));
+    }
+
+    let txn = state.db.begin().await?;
+    let mut seen = std::collections::HashSet::new();
+    let mut rows = Vec::new();
+
+    for (index, line) in lines {
+        let mut row = CityImportRow {
+            line: index + 1,
+            name: None,
+            status: CityImportStatus::Error,
+            city_id: None,
+            message: None,
+        };
+
+        let parsed = match parse_city_row(line) {
+            Ok(parsed) => parsed,
+            Err(msg) => {
+                row.message = Some(msg);
+                rows.push(row);
+                continue;
+            }
+        };
+        let (name, lat, lng, radius_km) = parsed;
+        row.name = Some(name.trim().to_string());
+
+        match validate_city_name(&txn, &name, None).await {
+            Ok(name) => {
+                seen.insert(name.clone());
+                let created = city::ActiveModel {
+                    name: Set(name),
+                    center_lat: Set(lat),
+                    center_lng: Set(lng),
+                    pickup_radius_km: Set(radius_km),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+                row.status = CityImportStatus::Created;
+                row.city_id = Some(created.id);
+            }
+            // Earlier rows are already inserted in this transaction, so they conflict too
+            Err(AppError::Conflict(msg)) => {
+                row.status = CityImportStatus::Skipped;
+                row.message = Some(if seen.contains(name.trim()) {
+                    "Duplicate name earlier in file".to_string()
+                } else {
+                    msg
+                });
+            }
+            Err(AppError::BadRequest(msg)) => row.message = Some(msg),
+            Err(e) => return Err(e),
+        }
+        rows.push(row);
+    }
+
+    let count = |status| rows.iter().filter(|r| r.status == status).count();
+    let (created, skipped, errors) = (
+        count(CityImportStatus::Created),
+        count(CityImportStatus::Skipped),
+        count(CityImportStatus::Error),
+    );
+
+    if query.strict
+        && let Some(failed) = rows.iter().find(|r| r.status == CityImportStatus::Error)
+    {
+        // Dropping the transaction rolls back the rows already inserted
+        return Err(AppError::BadRequest(format!(
+            "Line {}: {}",
+            failed.line,
+            failed.message.as_deref().unwrap_or("Invalid row")
+        )));
+    }
+
+    txn.commit().await?;
+
+    Ok(Json(CityImportResponse {
+        created,
+        skipped,
+        errors,
+        rows,
+    }))
+}
+
+/// Delete a city that no journey references (admin)
+pub async fn delete_city(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<serde_json::Value>> {
+    let endpoints = journey::Entity::find()
+        .filter(
+            Condition::any()
+                .add(journey::Column::OriginCityId.eq(id))
+                .add(journey::Column::DestinationCityId.eq(id)),
+        )
+        .count(&state.db)
+        .await?;
+    let stops = journey_stop::Entity::find()
+        .filter(journey_stop::Column::CityId.eq(id))
+        .count(&state.db)
+        .await?;
+    let in_use = endpoints + stops;
+
+    if in_use > 0 {
+        return Err(AppError::Conflict(format!(
+            "City is used by {} journey(s)",
+            in_use
+        )));
+    }
+
+    let result = city::Entity::delete_by_id(id).exec(&state.db).await?;
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("City not found".to_string()));
+    }
+
+    Ok(Json(serde_json::json!({ "message": "City deleted" })))
+}
+
 // ============ Journey Management ============
 
 #[derive(Debug, Deserialize)]
@@ -21,6 +473,20 @@ pub struct CreateJourneyRequest {
     pub destination_city_id: i32,
     pub departure_time: DateTime<Utc>,
     pub total_seats: i32,
+    /// Price per seat in cents
+    pub base_fare: i64,
+    /// Stop taking bookings this many minutes before departure
+    #[serde(default)]
+    pub booking_close_minutes: i32,
+    /// Intermediate stops in route order
+    #[serde(default)]
+    pub stops: Vec<JourneyStopRequest>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JourneyStopRequest {
+    pub city_id: i32,
+    pub scheduled_time: DateTime<Utc>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -29,6 +495,8 @@ pub struct UpdateJourneyRequest {
     pub destination_city_id: Option<i32>,
     pub departure_time: Option<DateTime<Utc>>,
     pub total_seats: Option<i32>,
+    pub base_fare: Option<i64>,
+    pub booking_close_minutes: Option<i32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -37,9 +505,19 @@ pub struct JourneyResponse {
     pub origin_city: String,
     pub destination_city: String,
     pub departure_time: DateTime<Utc>,
+    /// `departure_time` in the origin city's timezone
+    pub departure_time_local: DateTime<FixedOffset>,
     pub total_seats: i32,
     pub booked_seats: i32,
+    pub base_fare: i64,
+    pub booking_close_minutes: i32,
     pub driver: Option<DriverInfo>,
+    pub status: JourneyStatus,
+    /// Name of the admin who created the journey, when known
+    pub created_by_name: Option<String>,
+    pub stops: Vec<JourneyStopInfo>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize)]
@@ -49,45 +527,98 @@ pub struct DriverInfo {
     pub email: String,
 }
 
-/// List all cities
-pub async fn list_cities(State(state): State<AppState>) -> AppResult<Json<Vec<CityInfo>>> {
-    let cities = city::Entity::find().all(&state.db).await?;
-
-    let responses: Vec<CityInfo> = cities
-        .into_iter()
-        .map(|c| CityInfo {
-            id: c.id,
-            name: c.name,
-            center_lat: c.center_lat,
-            center_lng: c.center_lng,
-            pickup_radius_km: c.pickup_radius_km,
-        })
-        .collect();
-
-    Ok(Json(responses))
+/// `?sort=` keys for the admin journey list; soonest departure first by default
+const JOURNEY_SORTS: [SortOption<journey::Column>; 4] = [
+    SortOption {
+        key: "departure_time_asc",
+        column: journey::Column::DepartureTime,
+        descending: false,
+    },
+    SortOption {
+        key: "departure_time_desc",
+        column: journey::Column::DepartureTime,
+        descending: true,
+    },
+    SortOption {
+        key: "created_at_desc",
+        column: journey::Column::CreatedAt,
+        descending: true,
+    },
+    SortOption {
+        key: "created_at_asc",
+        column: journey::Column::CreatedAt,
+        descending: false,
+    },
+];
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AdminJourneyFilter {
+    /// `false` for journeys still waiting for a driver, `true` for assigned ones
+    pub has_driver: Option<bool>,
+    /// Earliest departure (RFC 3339), inclusive
+    pub departure_from: Option<DateTime<Utc>>,
+    /// Latest departure (RFC 3339), inclusive
+    pub departure_to: Option<DateTime<Utc>>,
 }
 
 /// List all journeys (admin)
-pub async fn list_journeys(State(state): State<AppState>) -> AppResult<Json<Vec<JourneyResponse>>> {
-    let journeys = journey::Entity::find().all(&state.db).await?;
+pub async fn list_journeys(
+    State(state): State<AppState>,
+    Query(pagination): Query<Pagination>,
+    Query(sort): Query<SortQuery>,
+    Query(filter): Query<AdminJourneyFilter>,
+) -> AppResult<Json<Paginated<JourneyResponse>>> {
+    let (column, order) = sort.resolve(&JOURNEY_SORTS)?;
+
+    if let (Some(from), Some(to)) = (filter.departure_from, filter.departure_to)
+        && from > to
+    {
+        return Err(AppError::BadRequest(
+            "departure_from must not be after departure_to".to_string(),
+        ));
+    }
+
+    // Tie-break on id so pages don't shuffle between requests
+    let paginator = journey::Entity::find()
+        .apply_if(filter.has_driver, |q, has_driver| {
+            q.filter(if has_driver {
+                journey::Column::DriverId.is_not_null()
+            } else {
+                journey::Column::DriverId.is_null()
+            })
+        })
+        .apply_if(filter.departure_from, |q, from| {
+            q.filter(journey::Column::DepartureTime.gte(from))
+        })
+        .apply_if(filter.departure_to, |q, to| {
+            q.filter(journey::Column::DepartureTime.lte(to))
+        })
+        .order_by(column, order)
+        .order_by_asc(journey::Column::Id)
+        .paginate(&state.db, pagination.per_page());
+    let total = paginator.num_items().await?;
+    let journeys = paginator.fetch_page(pagination.page() - 1).await?;
+    let ids: Vec<Uuid> = journeys.iter().map(|j| j.id).collect();
+    let booked_by_journey = booked_seats_by_journey(&state.db, &ids).await?;
+    let stops = stops_by_journey(&state.db, &ids).await?;
+
     let cities = city::Entity::find().all(&state.db).await?;
     let drivers = user::Entity::find()
         .filter(user::Column::Role.eq(UserRole::Driver))
         .all(&state.db)
         .await?;
+    let creator_ids: Vec<Uuid> = journeys.iter().filter_map(|j| j.created_by).collect();
+    let creators = user::Entity::find()
+        .filter(user::Column::Id.is_in(creator_ids))
+        .all(&state.db)
+        .await?;
 
     let mut responses = Vec::new();
     for j in journeys {
         let origin = cities.iter().find(|c| c.id == j.origin_city_id);
         let dest = cities.iter().find(|c| c.id == j.destination_city_id);
 
-        let booked: i32 = booking::Entity::find()
-            .filter(booking::Column::JourneyId.eq(j.id))
-            .all(&state.db)
-            .await?
-            .iter()
-            .map(|b| b.seats)
-            .sum();
+        let booked = booked_by_journey.get(&j.id).copied().unwrap_or(0);
 
         let driver = j.driver_id.and_then(|did| {
             drivers.iter().find(|d| d.id == did).map(|d| DriverInfo {
@@ -97,26 +628,424 @@ pub async fn list_journeys(State(state): State<AppState>) -> AppResult<Json<Vec<
             })
         });
 
-        responses.push(JourneyResponse {
-            id: j.id,
-            origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
-            destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
-            departure_time: j.departure_time.with_timezone(&Utc),
-            total_seats: j.total_seats,
-            booked_seats: booked,
-            driver,
-        });
+        responses.push(JourneyResponse {
+            id: j.id,
+            origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
+            destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
+            departure_time: j.departure_time.with_timezone(&Utc),
+            departure_time_local: local_departure(&j, origin),
+            total_seats: j.total_seats,
+            booked_seats: booked,
+            base_fare: j.base_fare,
+            booking_close_minutes: j.booking_close_minutes,
+            driver,
+            status: j.status,
+            created_by_name: j
+                .created_by
+                .and_then(|id| creators.iter().find(|u| u.id == id))
+                .map(|u| u.name.clone()),
+            stops: stop_infos(stops.get(&j.id).map_or(&[], Vec::as_slice), &cities),
+            created_at: j.created_at.with_timezone(&Utc),
+            updated_at: j.updated_at.with_timezone(&Utc),
+        });
+    }
+
+    Ok(Json(Paginated {
+        items: responses,
+        total,
+        page: pagination.page(),
+        per_page: pagination.per_page(),
+    }))
+}
+
+/// Ensure a departure falls within the origin city's local operating hours
+fn validate_operating_hours(origin: &city::Model, departure: DateTime<Utc>) -> AppResult<()> {
+    let local = local_time_of_day(departure, parse_timezone(&origin.timezone));
+
+    if !is_within_operating_hours(local, origin.operating_start, origin.operating_end) {
+        return Err(AppError::BadRequest(format!(
+            "Departure at {} local time is outside {} operating hours ({}-{})",
+            local.format("%H:%M"),
+            origin.name,
+            origin.operating_start.map(|t| t.format("%H:%M").to_string()).unwrap_or_default(),
+            origin.operating_end.map(|t| t.format("%H:%M").to_string()).unwrap_or_default(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check the departure is in the future and the seat count is positive, for whichever are given
+fn validate_journey_schedule(
+    departure: Option<DateTime<Utc>>,
+    total_seats: Option<i32>,
+    now: DateTime<Utc>,
+) -> AppResult<()> {
+    if let Some(departure) = departure
+        && departure <= now
+    {
+        return Err(AppError::BadRequest(
+            "Departure time must be in the future".to_string(),
+        ));
+    }
+
+    if let Some(seats) = total_seats
+        && seats < 1
+    {
+        return Err(AppError::BadRequest(
+            "Total seats must be at least 1".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Fares are in cents and may be zero for free journeys
+fn validate_base_fare(base_fare: i64) -> AppResult<()> {
+    if base_fare < 0 {
+        return Err(AppError::BadRequest(
+            "Base fare cannot be negative".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_booking_close_minutes(minutes: i32) -> AppResult<()> {
+    if minutes < 0 {
+        return Err(AppError::BadRequest(
+            "booking_close_minutes cannot be negative".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Straight-line distance between two cities in kilometers, rejected above `max_km`
+pub fn validate_journey_distance(
+    origin: &city::Model,
+    dest: &city::Model,
+    max_km: f64,
+) -> AppResult<f64> {
+    let distance_km = haversine_distance(
+        origin.center_lat,
+        origin.center_lng,
+        dest.center_lat,
+        dest.center_lng,
+    );
+
+    if distance_km > max_km {
+        return Err(AppError::BadRequest(format!(
+            "{} to {} is {:.0} km, more than the {:.0} km limit",
+            origin.name, dest.name, distance_km, max_km
+        )));
+    }
+
+    Ok(distance_km)
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateJourneyResponse {
+    #[serde(flatten)]
+    pub journey: journey::Model,
+    /// Straight-line distance between the origin and destination city centers
+    pub distance_km: f64,
+    pub stops: Vec<JourneyStopInfo>,
+}
+
+/// Create a new journey (admin)
+pub async fn create_journey(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateJourneyRequest>,
+) -> AppResult<Json<CreateJourneyResponse>> {
+    let (distance_km, stop_cities) = validate_journey_request(&state, &payload).await?;
+
+    let journey = journey::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        origin_city_id: Set(payload.origin_city_id),
+        destination_city_id: Set(payload.destination_city_id),
+        departure_time: Set(payload.departure_time.into()),
+        total_seats: Set(payload.total_seats),
+        base_fare: Set(payload.base_fare),
+        booking_close_minutes: Set(payload.booking_close_minutes),
+        driver_id: Set(None),
+        created_by: Set(Some(claims.sub)),
+        ..Default::default()
+    };
+
+    let txn = state.db.begin().await?;
+    let result = journey.insert(&txn).await?;
+    let stops = insert_stops(&txn, result.id, &payload.stops).await?;
+    txn.commit().await?;
+
+    Ok(Json(CreateJourneyResponse {
+        journey: result,
+        distance_km,
+        stops: stop_infos(&stops, &stop_cities),
+    }))
+}
+
+/// Replace a journey's route, schedule, seats, fare and stops in full (admin).
+/// The assigned driver, status and creator are kept.
+pub async fn replace_journey(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateJourneyRequest>,
+) -> AppResult<Json<CreateJourneyResponse>> {
+    let journey = journey::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    let (distance_km, stop_cities) = validate_journey_request(&state, &payload).await?;
+    ensure_seats_cover_bookings(&state, id, payload.total_seats).await?;
+
+    let mut active: journey::ActiveModel = journey.into();
+    active.origin_city_id = Set(payload.origin_city_id);
+    active.destination_city_id = Set(payload.destination_city_id);
+    active.departure_time = Set(payload.departure_time.into());
+    active.total_seats = Set(payload.total_seats);
+    active.base_fare = Set(payload.base_fare);
+    active.booking_close_minutes = Set(payload.booking_close_minutes);
+
+    let txn = state.db.begin().await?;
+    let result = active.update(&txn).await?;
+    journey_stop::Entity::delete_many()
+        .filter(journey_stop::Column::JourneyId.eq(id))
+        .exec(&txn)
+        .await?;
+    let stops = insert_stops(&txn, id, &payload.stops).await?;
+    txn.commit().await?;
+
+    Ok(Json(CreateJourneyResponse {
+        journey: result,
+        distance_km,
+        stops: stop_infos(&stops, &stop_cities),
+    }))
+}
+
+/// Checks shared by creating and replacing a journey. Returns the distance between the
+/// origin and destination and the stops' cities.
+async fn validate_journey_request(
+    state: &AppState,
+    payload: &CreateJourneyRequest,
+) -> AppResult<(f64, Vec<city::Model>)> {
+    // Validate cities
+    let origin = city::Entity::find_by_id(payload.origin_city_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Invalid origin city".to_string()))?;
+
+    let dest = city::Entity::find_by_id(payload.destination_city_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Invalid destination city".to_string()))?;
+
+    if origin.id == dest.id {
+        return Err(AppError::BadRequest(
+            "Origin and destination must be different".to_string(),
+        ));
+    }
+
+    let distance_km = validate_journey_distance(&origin, &dest, state.config.max_journey_km)?;
+    validate_journey_schedule(
+        Some(payload.departure_time),
+        Some(payload.total_seats),
+        Utc::now(),
+    )?;
+    validate_base_fare(payload.base_fare)?;
+    validate_booking_close_minutes(payload.booking_close_minutes)?;
+    validate_operating_hours(&origin, payload.departure_time)?;
+    validate_stops(&payload.stops, origin.id, dest.id, payload.departure_time)?;
+
+    let stop_city_ids: Vec<i32> = payload.stops.iter().map(|s| s.city_id).collect();
+    let stop_cities = city::Entity::find()
+        .filter(city::Column::Id.is_in(stop_city_ids.clone()))
+        .all(&state.db)
+        .await?;
+    if let Some(missing) = stop_city_ids
+        .iter()
+        .find(|id| !stop_cities.iter().any(|c| c.id == **id))
+    {
+        return Err(AppError::BadRequest(format!(
+            "Invalid stop city {}",
+            missing
+        )));
+    }
+
+    Ok((distance_km, stop_cities))
+}
+
+/// Insert a journey's stops, numbered from 1 in the order given
+async fn insert_stops<C: ConnectionTrait>(
+    db: &C,
+    journey_id: Uuid,
+    stops: &[JourneyStopRequest],
+) -> AppResult<Vec<journey_stop::Model>> {
+    let mut inserted = Vec::with_capacity(stops.len());
+    for (position, stop) in stops.iter().enumerate() {
+        let stop = journey_stop::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            journey_id: Set(journey_id),
+            city_id: Set(stop.city_id),
+            stop_order: Set(position as i32 + 1),
+            scheduled_time: Set(stop.scheduled_time.into()),
+        }
+        .insert(db)
+        .await?;
+        inserted.push(stop);
+    }
+    Ok(inserted)
+}
+
+/// Shrinking the bus below its existing bookings would leave negative availability
+async fn ensure_seats_cover_bookings(
+    state: &AppState,
+    journey_id: Uuid,
+    total_seats: i32,
+) -> AppResult<()> {
+    let booked = booked_seats_by_journey(&state.db, &[journey_id])
+        .await?
+        .get(&journey_id)
+        .copied()
+        .unwrap_or(0);
+    if total_seats < booked {
+        return Err(AppError::BadRequest(format!(
+            "Cannot set total_seats below {} already booked",
+            booked
+        )));
+    }
+    Ok(())
+}
+
+/// Stops must be at distinct cities other than the journey's origin and destination,
+/// each scheduled after departure and after the stop before it
+fn validate_stops(
+    stops: &[JourneyStopRequest],
+    origin_city_id: i32,
+    destination_city_id: i32,
+    departure: DateTime<Utc>,
+) -> AppResult<()> {
+    let mut previous = departure;
+    for (i, stop) in stops.iter().enumerate() {
+        if stop.city_id == origin_city_id || stop.city_id == destination_city_id {
+            return Err(AppError::BadRequest(
+                "Stops must differ from the origin and destination".to_string(),
+            ));
+        }
+        if stops[..i].iter().any(|s| s.city_id == stop.city_id) {
+            return Err(AppError::BadRequest(format!(
+                "City {} appears more than once in stops",
+                stop.city_id
+            )));
+        }
+        if stop.scheduled_time <= previous {
+            return Err(AppError::BadRequest(
+                "Stops must be scheduled in route order, after departure".to_string(),
+            ));
+        }
+        previous = stop.scheduled_time;
+    }
+
+    Ok(())
+}
+
+/// A journey's current stops in route order, in the shape `validate_stops` checks
+async fn existing_stops<C: ConnectionTrait>(
+    db: &C,
+    journey_id: Uuid,
+) -> AppResult<Vec<JourneyStopRequest>> {
+    let stops = journey_stop::Entity::find()
+        .filter(journey_stop::Column::JourneyId.eq(journey_id))
+        .order_by_asc(journey_stop::Column::StopOrder)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|s| JourneyStopRequest {
+            city_id: s.city_id,
+            scheduled_time: s.scheduled_time.with_timezone(&Utc),
+        })
+        .collect();
+    Ok(stops)
+}
+
+/// Upper bound on journeys a single recurring schedule may create
+const MAX_RECURRING_JOURNEYS: usize = 366;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRecurringJourneysRequest {
+    pub origin_city_id: i32,
+    pub destination_city_id: i32,
+    pub total_seats: i32,
+    /// Price per seat in cents
+    pub base_fare: i64,
+    #[serde(default)]
+    pub booking_close_minutes: i32,
+    /// First and last day of the schedule (inclusive)
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Departure time in the origin city's local time
+    pub time_of_day: NaiveTime,
+    /// e.g. `["Mon", "Fri"]`
+    pub days_of_week: Vec<Weekday>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecurringJourneysResponse {
+    pub journey_ids: Vec<Uuid>,
+}
+
+/// UTC departures for every day in `start..=end` falling on one of `days`, at `time_of_day` in `tz`
+fn recurring_departures(
+    start: NaiveDate,
+    end: NaiveDate,
+    time_of_day: NaiveTime,
+    days: &[Weekday],
+    tz: Tz,
+) -> AppResult<Vec<DateTime<Utc>>> {
+    if end < start {
+        return Err(AppError::BadRequest(
+            "end_date must not be before start_date".to_string(),
+        ));
+    }
+
+    if days.is_empty() {
+        return Err(AppError::BadRequest(
+            "days_of_week must not be empty".to_string(),
+        ));
+    }
+
+    let mut departures = Vec::new();
+    for date in start.iter_days().take_while(|d| *d <= end) {
+        if !days.contains(&date.weekday()) {
+            continue;
+        }
+
+        // Stop as soon as the cap is exceeded rather than walking an absurd range
+        if departures.len() == MAX_RECURRING_JOURNEYS {
+            return Err(AppError::BadRequest(format!(
+                "Schedule would create more than {} journeys",
+                MAX_RECURRING_JOURNEYS
+            )));
+        }
+
+        let local = tz
+            .from_local_datetime(&date.and_time(time_of_day))
+            .earliest()
+            .ok_or_else(|| {
+                AppError::BadRequest(format!("{} {} does not exist in {}", date, time_of_day, tz))
+            })?;
+        departures.push(local.with_timezone(&Utc));
     }
 
-    Ok(Json(responses))
+    Ok(departures)
 }
 
-/// Create a new journey (admin)
-pub async fn create_journey(
+/// Create one journey per matching day of a weekly schedule (admin), all or nothing
+pub async fn create_recurring_journeys(
     State(state): State<AppState>,
-    Json(payload): Json<CreateJourneyRequest>,
-) -> AppResult<Json<journey::Model>> {
-    // Validate cities
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateRecurringJourneysRequest>,
+) -> AppResult<Json<RecurringJourneysResponse>> {
     let origin = city::Entity::find_by_id(payload.origin_city_id)
         .one(&state.db)
         .await?
@@ -133,21 +1062,55 @@ pub async fn create_journey(
         ));
     }
 
-    let journey = journey::ActiveModel {
-        id: Set(Uuid::new_v4()),
-        origin_city_id: Set(payload.origin_city_id),
-        destination_city_id: Set(payload.destination_city_id),
-        departure_time: Set(payload.departure_time.into()),
-        total_seats: Set(payload.total_seats),
-        driver_id: Set(None),
-        ..Default::default()
-    };
+    validate_journey_distance(&origin, &dest, state.config.max_journey_km)?;
+    validate_base_fare(payload.base_fare)?;
+    validate_booking_close_minutes(payload.booking_close_minutes)?;
 
-    let result = journey.insert(&state.db).await?;
-    Ok(Json(result))
+    let departures = recurring_departures(
+        payload.start_date,
+        payload.end_date,
+        payload.time_of_day,
+        &payload.days_of_week,
+        parse_timezone(&origin.timezone),
+    )?;
+
+    if departures.is_empty() {
+        return Err(AppError::BadRequest(
+            "No day in the range falls on the given days_of_week".to_string(),
+        ));
+    }
+
+    let now = Utc::now();
+    for departure in &departures {
+        validate_journey_schedule(Some(*departure), Some(payload.total_seats), now)?;
+        validate_operating_hours(&origin, *departure)?;
+    }
+
+    let journeys: Vec<journey::ActiveModel> = departures
+        .into_iter()
+        .map(|departure| journey::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            origin_city_id: Set(origin.id),
+            destination_city_id: Set(dest.id),
+            departure_time: Set(departure.into()),
+            total_seats: Set(payload.total_seats),
+            base_fare: Set(payload.base_fare),
+            booking_close_minutes: Set(payload.booking_close_minutes),
+            driver_id: Set(None),
+            created_by: Set(Some(claims.sub)),
+            ..Default::default()
+        })
+        .collect();
+    let journey_ids = journeys.iter().map(|j| *j.id.as_ref()).collect();
+
+    let txn = state.db.begin().await?;
+    journey::Entity::insert_many(journeys).exec(&txn).await?;
+    txn.commit().await?;
+
+    Ok(Json(RecurringJourneysResponse { journey_ids }))
 }
 
-/// Update a journey (admin)
+/// Update only the given fields of a journey (admin)
 pub async fn update_journey(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
@@ -158,13 +1121,44 @@ pub async fn update_journey(
         .await?
         .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
 
+    validate_journey_schedule(payload.departure_time, payload.total_seats, Utc::now())?;
+    if let Some(fare) = payload.base_fare {
+        validate_base_fare(fare)?;
+    }
+    if let Some(minutes) = payload.booking_close_minutes {
+        validate_booking_close_minutes(minutes)?;
+    }
+
+    // Operating hours depend on both the origin and the departure time, so check the merged result
+    let origin_id = payload.origin_city_id.unwrap_or(journey.origin_city_id);
+    let departure = payload
+        .departure_time
+        .unwrap_or_else(|| journey.departure_time.with_timezone(&Utc));
+
+    let origin = city::Entity::find_by_id(origin_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("Invalid origin city".to_string()))?;
+
+    if payload.origin_city_id.is_some() || payload.departure_time.is_some() {
+        validate_operating_hours(&origin, departure)?;
+    }
+
+    // Existing stops must still fit between the (possibly moved) ends of the route
+    if payload.origin_city_id.is_some()
+        || payload.destination_city_id.is_some()
+        || payload.departure_time.is_some()
+    {
+        let stops = existing_stops(&state.db, id).await?;
+        let dest_id = payload
+            .destination_city_id
+            .unwrap_or(journey.destination_city_id);
+        validate_stops(&stops, origin_id, dest_id, departure)?;
+    }
+
     let mut active: journey::ActiveModel = journey.into();
 
     if let Some(origin_id) = payload.origin_city_id {
-        city::Entity::find_by_id(origin_id)
-            .one(&state.db)
-            .await?
-            .ok_or_else(|| AppError::BadRequest("Invalid origin city".to_string()))?;
         active.origin_city_id = Set(origin_id);
     }
 
@@ -181,9 +1175,19 @@ pub async fn update_journey(
     }
 
     if let Some(seats) = payload.total_seats {
+        ensure_seats_cover_bookings(&state, id, seats).await?;
         active.total_seats = Set(seats);
     }
 
+    // Existing bookings keep the fare they were booked at
+    if let Some(fare) = payload.base_fare {
+        active.base_fare = Set(fare);
+    }
+
+    if let Some(minutes) = payload.booking_close_minutes {
+        active.booking_close_minutes = Set(minutes);
+    }
+
     let result = active.update(&state.db).await?;
     Ok(Json(result))
 }
@@ -214,7 +1218,7 @@ pub async fn assign_driver(
     Json(payload): Json<AssignDriverRequest>,
 ) -> AppResult<Json<journey::Model>> {
     // Validate driver exists and has driver role
-    let driver = user::Entity::find_by_id(payload.driver_id)
+    let driver = user::Entity::find_active_by_id(payload.driver_id)
         .one(&state.db)
         .await?
         .ok_or_else(|| AppError::NotFound("Driver not found".to_string()))?;
@@ -236,6 +1240,249 @@ pub async fn assign_driver(
     Ok(Json(result))
 }
 
+/// Remove the driver from a journey (admin); succeeds unchanged if none is assigned
+pub async fn unassign_driver(
+    State(state): State<AppState>,
+    Path(journey_id): Path<Uuid>,
+) -> AppResult<Json<journey::Model>> {
+    let journey = journey::Entity::find_by_id(journey_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    if journey.driver_id.is_none() {
+        return Ok(Json(journey));
+    }
+
+    let mut active: journey::ActiveModel = journey.into();
+    active.driver_id = Set(None);
+
+    let result = active.update(&state.db).await?;
+    Ok(Json(result))
+}
+
+// ============ Journey Rescheduling ============
+
+#[derive(Debug, Deserialize)]
+pub struct RescheduleRequest {
+    pub departure_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ReschedulePreview {
+    pub journey_id: Uuid,
+    pub current_departure_time: DateTime<Utc>,
+    pub new_departure_time: DateTime<Utc>,
+    pub affected_bookings: usize,
+    pub affected_seats: i32,
+    /// Bookings whose passengers could cancel now but no longer could after the change
+    pub cancellation_window_violations: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RescheduleResponse {
+    pub journey: journey::Model,
+    pub notified_passengers: usize,
+}
+
+/// Compute how moving a journey to `new_time` affects its bookings
+fn build_reschedule_preview(
+    journey: &journey::Model,
+    bookings: &[booking::Model],
+    new_time: DateTime<Utc>,
+    now: DateTime<Utc>,
+    cancellation_cutoff: Duration,
+) -> ReschedulePreview {
+    let current_time = journey.departure_time.with_timezone(&Utc);
+
+    // Moving the journey to within the cancellation cutoff (or into the past) closes the window
+    let can_cancel_now = cancellation_allowed(current_time, now, cancellation_cutoff);
+    let can_cancel_after = cancellation_allowed(new_time, now, cancellation_cutoff);
+    let cancellation_window_violations = if can_cancel_now && !can_cancel_after {
+        bookings.iter().map(|b| b.id).collect()
+    } else {
+        Vec::new()
+    };
+
+    ReschedulePreview {
+        journey_id: journey.id,
+        current_departure_time: current_time,
+        new_departure_time: new_time,
+        affected_bookings: bookings.len(),
+        affected_seats: bookings.iter().map(|b| b.seats).sum(),
+        cancellation_window_violations,
+    }
+}
+
+/// Preview the effect of changing a journey's departure time (admin)
+pub async fn reschedule_preview(
+    State(state): State<AppState>,
+    Path(journey_id): Path<Uuid>,
+    Json(payload): Json<RescheduleRequest>,
+) -> AppResult<Json<ReschedulePreview>> {
+    let journey = journey::Entity::find_by_id(journey_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    let bookings = booking::Entity::find()
+        .filter(booking::Column::JourneyId.eq(journey_id))
+        .filter(booking::Column::Status.ne(BookingStatus::Cancelled))
+        .all(&state.db)
+        .await?;
+
+    Ok(Json(build_reschedule_preview(
+        &journey,
+        &bookings,
+        payload.departure_time,
+        Utc::now(),
+        Duration::hours(state.config.cancellation_cutoff_hours.into()),
+    )))
+}
+
+/// Change a journey's departure time and notify its passengers (admin)
+pub async fn reschedule_journey(
+    State(state): State<AppState>,
+    Path(journey_id): Path<Uuid>,
+    Json(payload): Json<RescheduleRequest>,
+) -> AppResult<Json<RescheduleResponse>> {
+    let new_time = payload.departure_time;
+    validate_journey_schedule(Some(new_time), None, Utc::now())?;
+
+    // Lock the journey so the passengers notified are the ones booked at the new time
+    let txn = state.db.begin().await?;
+
+    let journey = journey::Entity::find_by_id(journey_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    let origin = city::Entity::find_by_id(journey.origin_city_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::Internal("Origin city not found".to_string()))?;
+    validate_operating_hours(&origin, new_time)?;
+
+    let stops = existing_stops(&txn, journey_id).await?;
+    validate_stops(
+        &stops,
+        journey.origin_city_id,
+        journey.destination_city_id,
+        new_time,
+    )?;
+
+    let old_time = journey.departure_time.with_timezone(&Utc);
+
+    let mut active: journey::ActiveModel = journey.into();
+    active.departure_time = Set(new_time.into());
+    let updated = active.update(&txn).await?;
+
+    let bookings = booking::Entity::find()
+        .filter(booking::Column::JourneyId.eq(journey_id))
+        .filter(booking::Column::Status.ne(BookingStatus::Cancelled))
+        .all(&txn)
+        .await?;
+    let passenger_ids: Vec<Uuid> = bookings.iter().map(|b| b.user_id).collect();
+    let users = user::Entity::find()
+        .filter(user::Column::Id.is_in(passenger_ids))
+        .all(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    for b in &bookings {
+        if let Some(u) = users.iter().find(|u| u.id == b.user_id) {
+            state
+                .notifier
+                .journey_rescheduled(&u.email, b.id, old_time, new_time)
+                .await;
+        }
+    }
+
+    Ok(Json(RescheduleResponse {
+        journey: updated,
+        notified_passengers: bookings.len(),
+    }))
+}
+
+// ============ Journey Cancellation ============
+
+#[derive(Debug, Serialize)]
+pub struct AffectedBooking {
+    pub booking_id: Uuid,
+    pub passenger_email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CancelJourneyResponse {
+    pub journey: journey::Model,
+    pub affected_bookings: Vec<AffectedBooking>,
+}
+
+/// Cancel a journey, keeping its bookings, and report who needs notifying (admin)
+pub async fn cancel_journey(
+    State(state): State<AppState>,
+    Path(journey_id): Path<Uuid>,
+) -> AppResult<Json<CancelJourneyResponse>> {
+    // Lock the journey so no booking slips in unreported while it's being cancelled
+    let txn = state.db.begin().await?;
+
+    let journey = journey::Entity::find_by_id(journey_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    match journey.status {
+        JourneyStatus::Scheduled | JourneyStatus::InProgress => {}
+        JourneyStatus::Completed => {
+            return Err(AppError::Conflict(
+                "Cannot cancel a completed journey".to_string(),
+            ));
+        }
+        JourneyStatus::Cancelled => {
+            return Err(AppError::Conflict(
+                "Journey is already cancelled".to_string(),
+            ));
+        }
+    }
+
+    let mut active: journey::ActiveModel = journey.into();
+    active.status = Set(JourneyStatus::Cancelled);
+    let updated = active.update(&txn).await?;
+
+    let bookings = booking::Entity::find()
+        .filter(booking::Column::JourneyId.eq(journey_id))
+        .filter(booking::Column::Status.ne(BookingStatus::Cancelled))
+        .all(&txn)
+        .await?;
+    let passenger_ids: Vec<Uuid> = bookings.iter().map(|b| b.user_id).collect();
+    let users = user::Entity::find()
+        .filter(user::Column::Id.is_in(passenger_ids))
+        .all(&txn)
+        .await?;
+
+    txn.commit().await?;
+
+    let affected_bookings: Vec<AffectedBooking> = bookings
+        .iter()
+        .map(|b| AffectedBooking {
+            booking_id: b.id,
+            passenger_email: users
+                .iter()
+                .find(|u| u.id == b.user_id)
+                .map(|u| u.email.clone())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(Json(CancelJourneyResponse {
+        journey: updated,
+        affected_bookings,
+    }))
+}
+
 // ============ User Management ============
 
 #[derive(Debug, Serialize)]
@@ -247,22 +1494,72 @@ pub struct UserResponse {
     pub created_at: DateTime<Utc>,
 }
 
-/// List all users (admin)
-pub async fn list_all_users(State(state): State<AppState>) -> AppResult<Json<Vec<UserResponse>>> {
-    let users = user::Entity::find().all(&state.db).await?;
+#[derive(Debug, Default, Deserialize)]
+pub struct UserFilter {
+    pub role: Option<UserRole>,
+    /// Also list soft-deleted users
+    #[serde(default)]
+    pub include_deleted: bool,
+}
 
-    let responses: Vec<UserResponse> = users
-        .into_iter()
-        .map(|u| UserResponse {
-            id: u.id,
-            email: u.email,
-            name: u.name,
-            role: u.role,
-            created_at: u.created_at.with_timezone(&Utc),
-        })
-        .collect();
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct UserListItem {
+    pub id: Uuid,
+    pub email: String,
+    pub name: String,
+    pub role: UserRole,
+    pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Confirmed and waitlisted bookings; cancelled ones aren't counted
+    pub booking_count: i64,
+}
 
-    Ok(Json(responses))
+/// List users with their booking counts, oldest account first (admin, paginated)
+pub async fn list_all_users(
+    State(state): State<AppState>,
+    Query(pagination): Query<Pagination>,
+    Query(filter): Query<UserFilter>,
+) -> AppResult<Json<Paginated<UserListItem>>> {
+    // One grouped join instead of a count query per user
+    let paginator = user::Entity::find()
+        .select_only()
+        .columns([
+            user::Column::Id,
+            user::Column::Email,
+            user::Column::Name,
+            user::Column::Role,
+            user::Column::CreatedAt,
+            user::Column::DeletedAt,
+        ])
+        .column_as(booking::Column::Id.count(), "booking_count")
+        // Filtering in the join keeps users whose bookings are all cancelled, at 0
+        .join(
+            JoinType::LeftJoin,
+            user::Relation::Bookings.def().on_condition(|_, _| {
+                booking::Column::Status
+                    .ne(BookingStatus::Cancelled)
+                    .into_condition()
+            }),
+        )
+        .apply_if(filter.role, |q, role| q.filter(user::Column::Role.eq(role)))
+        .apply_if(
+            (!filter.include_deleted).then_some(user::Column::DeletedAt.is_null()),
+            |q, active| q.filter(active),
+        )
+        .group_by(user::Column::Id)
+        .order_by_asc(user::Column::CreatedAt)
+        .order_by_asc(user::Column::Id)
+        .into_model::<UserListItem>()
+        .paginate(&state.db, pagination.per_page());
+    let total = paginator.num_items().await?;
+    let items = paginator.fetch_page(pagination.page() - 1).await?;
+
+    Ok(Json(Paginated {
+        items,
+        total,
+        page: pagination.page(),
+        per_page: pagination.per_page(),
+    }))
 }
 
 /// List all drivers (admin)
@@ -271,13 +1568,29 @@ pub struct DriverResponse {
     pub id: Uuid,
     pub email: String,
     pub name: String,
+    pub phone: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DriverFilter {
+    /// Also list soft-deleted drivers
+    #[serde(default)]
+    pub include_deleted: bool,
 }
 
 /// List all drivers (admin)
-pub async fn list_drivers(State(state): State<AppState>) -> AppResult<Json<Vec<DriverResponse>>> {
+pub async fn list_drivers(
+    State(state): State<AppState>,
+    Query(filter): Query<DriverFilter>,
+) -> AppResult<Json<Vec<DriverResponse>>> {
     let drivers = user::Entity::find()
         .filter(user::Column::Role.eq(UserRole::Driver))
+        .apply_if(
+            (!filter.include_deleted).then_some(user::Column::DeletedAt.is_null()),
+            |q, active| q.filter(active),
+        )
         .all(&state.db)
         .await?;
 
@@ -287,56 +1600,244 @@ pub async fn list_drivers(State(state): State<AppState>) -> AppResult<Json<Vec<D
             id: d.id,
             email: d.email,
             name: d.name,
+            phone: d.phone,
             created_at: d.created_at.with_timezone(&Utc),
+            deleted_at: d.deleted_at.map(|t| t.with_timezone(&Utc)),
         })
         .collect();
 
     Ok(Json(responses))
 }
 
+/// Create a driver account (admin)
+#[derive(Debug, Deserialize)]
+pub struct CreateDriverRequest {
+    pub email: String,
+    pub password: String,
+    pub name: String,
+    /// Shown to passengers with a confirmed booking on the driver's journeys
+    pub phone: Option<String>,
+}
+
+pub async fn create_driver(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateDriverRequest>,
+) -> AppResult<Json<DriverResponse>> {
+    let email = normalize_email(&payload.email);
+    validate_email(&email)?;
+    let name = normalize_name(&payload.name)?;
+    let phone = payload.phone.map(|p| p.trim().to_string());
+    if let Some(phone) = &phone {
+        validate_phone(phone)?;
+    }
+
+    // Check if email already exists
+    let existing = user::Entity::find_by_email(&email).one(&state.db).await?;
+
+    if existing.is_some() {
+        return Err(AppError::Conflict("Email already registered".to_string()));
+    }
+
+    let driver = user::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        email: Set(email),
+        password_hash: Set(Some(hash_password(
+            &payload.password,
+            &state.config.password_hash_cost(),
+        )?)),
+        google_id: Set(None),
+        name: Set(name),
+        role: Set(UserRole::Driver),
+        phone: Set(phone),
+        ..Default::default()
+    };
+
+    let driver = insert_account(&state.db, driver).await?;
+
+    Ok(Json(DriverResponse {
+        id: driver.id,
+        email: driver.email,
+        name: driver.name,
+        phone: driver.phone,
+        created_at: driver.created_at.with_timezone(&Utc),
+        deleted_at: None,
+    }))
+}
+
+/// Delete a driver account (admin)
+pub async fn delete_driver(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<serde_json::Value>> {
+    let driver = user::Entity::find_active_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Driver not found".to_string()))?;
+
+    if driver.role != UserRole::Driver {
+        return Err(AppError::BadRequest("User is not a driver".to_string()));
+    }
+
+    // Unassign from upcoming journeys; past ones keep their driver for history
+    let journeys = journey::Entity::find()
+        .filter(journey::Column::DriverId.eq(id))
+        .filter(journey::Column::DepartureTime.gte(Utc::now()))
+        .all(&state.db)
+        .await?;
+    for j in journeys {
+        let mut active: journey::ActiveModel = j.into();
+        active.driver_id = Set(None);
+        active.update(&state.db).await?;
+    }
+
+    // Soft delete, like any other account
+    let mut active: user::ActiveModel = driver.into();
+    active.deleted_at = Set(Some(Utc::now().into()));
+    active.update(&state.db).await?;
+
+    Ok(Json(serde_json::json!({ "message": "Driver deleted" })))
+}
+
+/// Reset a user's password (admin), e.g. for a locked-out traveller
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub new_password: String,
+}
+
+pub async fn reset_user_password(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> AppResult<Json<serde_json::Value>> {
+    let user = user::Entity::find_active_by_id(user_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    // One admin account must not be able to take over another
+    if user.role == UserRole::Admin {
+        return Err(AppError::Forbidden(
+            "Admin passwords cannot be reset here".to_string(),
+        ));
+    }
+
+    validate_password(&payload.new_password)?;
+
+    let mut active: user::ActiveModel = user.into();
+    active.password_hash = Set(Some(hash_password(
+        &payload.new_password,
+        &state.config.password_hash_cost(),
+    )?));
+    active.update(&state.db).await?;
+
+    Ok(Json(serde_json::json!({ "message": "Password reset" })))
+}
+
 /// Update user role (admin)
 #[derive(Debug, Deserialize)]
 pub struct UpdateRoleRequest {
-    pub role: UserRole,
+    /// Parsed by the handler, so an unknown role is a 400 rather than a body rejection
+    pub role: String,
+}
+
+/// A role name in either spelling the API accepts, e.g. "Driver" or "driver"
+fn parse_role(name: &str) -> AppResult<UserRole> {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .map_err(|_| AppError::BadRequest(format!("Unknown role: {}", name)))
+}
+
+/// Taking away an admin's role must leave at least one other active admin
+fn ensure_admin_remains(
+    old_role: &UserRole,
+    new_role: &UserRole,
+    other_admins: usize,
+) -> AppResult<()> {
+    if *old_role == UserRole::Admin && *new_role != UserRole::Admin && other_admins == 0 {
+        return Err(AppError::Conflict(
+            "Cannot demote the last admin".to_string(),
+        ));
+    }
+
+    Ok(())
 }
 
 pub async fn update_user_role(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Path(user_id): Path<Uuid>,
     Json(payload): Json<UpdateRoleRequest>,
 ) -> AppResult<Json<UserResponse>> {
-    let user = user::Entity::find_by_id(user_id)
-        .one(&state.db)
+    let new_role = parse_role(&payload.role)?;
+    let txn = state.db.begin().await?;
+
+    // Lock every admin, in id order, so concurrent demotions can't both see another
+    // admin left and remove the last two
+    let admins = if new_role == UserRole::Admin {
+        Vec::new()
+    } else {
+        user::Entity::find_active()
+            .filter(user::Column::Role.eq(UserRole::Admin))
+            .order_by_asc(user::Column::Id)
+            .lock_exclusive()
+            .all(&txn)
+            .await?
+    };
+
+    let user = user::Entity::find_active_by_id(user_id)
+        .lock_exclusive()
+        .one(&txn)
         .await?
         .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
 
+    let other_admins = admins.iter().filter(|a| a.id != user_id).count();
+    ensure_admin_remains(&user.role, &new_role, other_admins)?;
+
     let old_role = user.role.clone();
 
     // Handle role change side effects
-    if old_role == UserRole::Driver && payload.role != UserRole::Driver {
+    if old_role == UserRole::Driver && new_role != UserRole::Driver {
         // Unassign from all journeys
         let journeys = journey::Entity::find()
             .filter(journey::Column::DriverId.eq(user_id))
-            .all(&state.db)
+            .all(&txn)
             .await?;
         for j in journeys {
             let mut active: journey::ActiveModel = j.into();
             active.driver_id = Set(None);
-            active.update(&state.db).await?;
+            active.update(&txn).await?;
         }
     }
 
-    if old_role == UserRole::Traveller && payload.role != UserRole::Traveller {
+    if old_role == UserRole::Traveller && new_role != UserRole::Traveller {
         // Delete all bookings (bookings belong to travellers)
         booking::Entity::delete_many()
             .filter(booking::Column::UserId.eq(user_id))
-            .exec(&state.db)
+            .exec(&txn)
             .await?;
     }
 
-    let mut active: user::ActiveModel = user.into();
-    active.role = Set(payload.role.clone());
-    let updated = active.update(&state.db).await?;
+    let mut active: user::ActiveModel = user.into();
+    active.role = Set(new_role.clone());
+    // Any change away from traveller settles a pending driver application
+    if new_role != UserRole::Traveller {
+        active.driver_application_pending = Set(false);
+    }
+    let updated = active.update(&txn).await?;
+
+    if old_role != updated.role {
+        role_change_audit::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            actor_id: Set(Some(claims.sub)),
+            target_user_id: Set(Some(user_id)),
+            old_role: Set(old_role),
+            new_role: Set(updated.role.clone()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+    }
+
+    txn.commit().await?;
 
     Ok(Json(UserResponse {
         id: updated.id,
@@ -347,42 +1848,219 @@ pub async fn update_user_role(
     }))
 }
 
-/// Delete any user account (admin)
+#[derive(Debug, Serialize)]
+pub struct RoleChangeAuditItem {
+    pub id: Uuid,
+    /// Admin who made the change; `null` once their account is deleted
+    pub actor_id: Option<Uuid>,
+    pub actor_email: Option<String>,
+    /// `null` once the user's account is deleted
+    pub target_user_id: Option<Uuid>,
+    pub target_email: Option<String>,
+    pub old_role: UserRole,
+    pub new_role: UserRole,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Review role changes, newest first (admin, paginated)
+pub async fn list_role_change_audits(
+    State(state): State<AppState>,
+    Query(pagination): Query<Pagination>,
+) -> AppResult<Json<Paginated<RoleChangeAuditItem>>> {
+    let paginator = role_change_audit::Entity::find()
+        .order_by_desc(role_change_audit::Column::CreatedAt)
+        .order_by_desc(role_change_audit::Column::Id)
+        .paginate(&state.db, pagination.per_page());
+    let total = paginator.num_items().await?;
+    let audits = paginator.fetch_page(pagination.page() - 1).await?;
+
+    // Soft-deleted users included: the emails are for the record
+    let user_ids: Vec<Uuid> = audits
+        .iter()
+        .flat_map(|a| [a.actor_id, a.target_user_id])
+        .flatten()
+        .collect();
+    let users = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids))
+        .all(&state.db)
+        .await?;
+    let email_of = |id: Option<Uuid>| {
+        id.and_then(|id| users.iter().find(|u| u.id == id))
+            .map(|u| u.email.clone())
+    };
+
+    let items = audits
+        .into_iter()
+        .map(|a| RoleChangeAuditItem {
+            id: a.id,
+            actor_id: a.actor_id,
+            actor_email: email_of(a.actor_id),
+            target_user_id: a.target_user_id,
+            target_email: email_of(a.target_user_id),
+            old_role: a.old_role,
+            new_role: a.new_role,
+            created_at: a.created_at.with_timezone(&Utc),
+        })
+        .collect();
+
+    Ok(Json(Paginated {
+        items,
+        total,
+        page: pagination.page(),
+        per_page: pagination.per_page(),
+    }))
+}
+
+/// Cancel a user's bookings on journeys that haven't departed yet, handing
+/// confirmed seats to each journey's waitlist. Past bookings are left alone.
+async fn cancel_upcoming_bookings<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+    now: DateTime<Utc>,
+) -> AppResult<()> {
+    let upcoming = booking::Entity::find()
+        .inner_join(journey::Entity)
+        .filter(booking::Column::UserId.eq(user_id))
+        .filter(booking::Column::Status.ne(BookingStatus::Cancelled))
+        .filter(journey::Column::DepartureTime.gte(now))
+        .all(db)
+        .await?;
+    for b in upcoming {
+        let journey = journey::Entity::find_by_id(b.journey_id)
+            .lock_exclusive()
+            .one(db)
+            .await?;
+
+        let previous_status = b.status;
+        let booking_id = b.id;
+        let mut active: booking::ActiveModel = b.into();
+        active.status = Set(BookingStatus::Cancelled);
+        active.cancelled_at = Set(Some(now.into()));
+        active.update(db).await?;
+        forget_idempotency_keys(db, booking_id).await?;
+
+        if let Some(j) = &journey
+            && previous_status == BookingStatus::Confirmed
+            && j.status == JourneyStatus::Scheduled
+        {
+            promote_waitlisted(db, j).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Soft-delete a user account (admin)
+///
+/// The row and bookings on past journeys stay for history; upcoming bookings are
+/// cancelled so their seats go to the waitlist, and upcoming journeys lose their driver.
 pub async fn delete_user(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> AppResult<Json<serde_json::Value>> {
-    let user = user::Entity::find_by_id(id)
-        .one(&state.db)
+    let now = Utc::now();
+    let txn = state.db.begin().await?;
+
+    let user = user::Entity::find_active_by_id(id)
+        .lock_exclusive()
+        .one(&txn)
         .await?
         .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
 
     // Handle cleanup based on role
     if user.role == UserRole::Driver {
-        // Unassign from all journeys
+        // Unassign from journeys that haven't departed yet
         let journeys = journey::Entity::find()
             .filter(journey::Column::DriverId.eq(id))
-            .all(&state.db)
+            .filter(journey::Column::DepartureTime.gte(now))
+            .all(&txn)
             .await?;
         for j in journeys {
             let mut active: journey::ActiveModel = j.into();
             active.driver_id = Set(None);
-            active.update(&state.db).await?;
+            active.update(&txn).await?;
         }
     }
 
-    // Delete user's bookings (if any - travellers will have bookings)
-    booking::Entity::delete_many()
-        .filter(booking::Column::UserId.eq(id))
-        .exec(&state.db)
-        .await?;
+    // Cancel the user's upcoming bookings (if any - travellers will have bookings),
+    // keeping the rows for history
+    cancel_upcoming_bookings(&txn, id, now).await?;
+
+    let mut active: user::ActiveModel = user.into();
+    active.deleted_at = Set(Some(now.into()));
+    active.driver_application_pending = Set(false);
+    active.update(&txn).await?;
 
-    // Delete user
-    user::Entity::delete_by_id(id).exec(&state.db).await?;
+    txn.commit().await?;
 
     Ok(Json(serde_json::json!({ "message": "User deleted" })))
 }
 
+// ============ Driver Applications ============
+
+/// List travellers waiting for driver approval, oldest application first (admin)
+pub async fn list_driver_applications(
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<UserResponse>>> {
+    let applicants = user::Entity::find_active()
+        .filter(user::Column::DriverApplicationPending.eq(true))
+        .filter(user::Column::Role.eq(UserRole::Traveller))
+        .order_by_asc(user::Column::CreatedAt)
+        .all(&state.db)
+        .await?;
+
+    let responses: Vec<UserResponse> = applicants
+        .into_iter()
+        .map(|u| UserResponse {
+            id: u.id,
+            email: u.email,
+            name: u.name,
+            role: u.role,
+            created_at: u.created_at.with_timezone(&Utc),
+        })
+        .collect();
+
+    Ok(Json(responses))
+}
+
+/// Approve a pending driver application, making the applicant a driver (admin)
+pub async fn approve_driver(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> AppResult<Json<UserResponse>> {
+    let txn = state.db.begin().await?;
+
+    let user = user::Entity::find_active_by_id(user_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if !user.driver_application_pending || user.role != UserRole::Traveller {
+        return Err(AppError::BadRequest(
+            "User has no pending driver application".to_string(),
+        ));
+    }
+
+    // Upcoming trips are released to the waitlist; past ones stay for history
+    cancel_upcoming_bookings(&txn, user_id, Utc::now()).await?;
+
+    let mut active: user::ActiveModel = user.into();
+    active.role = Set(UserRole::Driver);
+    active.driver_application_pending = Set(false);
+    let updated = active.update(&txn).await?;
+
+    txn.commit().await?;
+
+    Ok(Json(UserResponse {
+        id: updated.id,
+        email: updated.email,
+        name: updated.name,
+        role: updated.role,
+        created_at: updated.created_at.with_timezone(&Utc),
+    }))
+}
+
 // ============ Bookings Management (Admin) ============
 
 #[derive(Debug, Serialize)]
@@ -394,17 +2072,101 @@ pub struct BookingInfo {
     pub seats: i32,
     pub pickup_lat: f64,
     pub pickup_lng: f64,
+    pub total_fare: i64,
+    pub status: BookingStatus,
+    pub cancelled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// `?sort=` keys for the admin booking list; newest first by default
+const BOOKING_SORTS: [SortOption<booking::Column>; 4] = [
+    SortOption {
+        key: "created_at_desc",
+        column: booking::Column::CreatedAt,
+        descending: true,
+    },
+    SortOption {
+        key: "created_at_asc",
+        column: booking::Column::CreatedAt,
+        descending: false,
+    },
+    SortOption {
+        key: "updated_at_desc",
+        column: booking::Column::UpdatedAt,
+        descending: true,
+    },
+    SortOption {
+        key: "updated_at_asc",
+        column: booking::Column::UpdatedAt,
+        descending: false,
+    },
+];
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BookingFilter {
+    pub status: Option<BookingStatus>,
 }
 
-/// List all bookings (admin)
+/// List all bookings a page at a time (admin). Pages are keyed on `(sort column, id)`
+/// rather than an offset, so deep pages cost the same as the first.
 pub async fn list_all_bookings(
     State(state): State<AppState>,
-) -> AppResult<Json<Vec<BookingInfo>>> {
-    let bookings = booking::Entity::find().all(&state.db).await?;
-    let users = user::Entity::find().all(&state.db).await?;
+    Query(sort): Query<SortQuery>,
+    Query(page): Query<CursorQuery>,
+    Query(filter): Query<BookingFilter>,
+) -> AppResult<Json<CursorPage<BookingInfo>>> {
+    let (column, order) = sort.resolve(&BOOKING_SORTS)?;
+    let limit = page.limit();
+
+    let mut query = booking::Entity::find()
+        .apply_if(filter.status, |q, status| {
+            q.filter(booking::Column::Status.eq(status))
+        })
+        .order_by(column, order.clone())
+        .order_by(booking::Column::Id, order.clone())
+        .limit(limit + 1);
+
+    if let Some(cursor) = page.cursor()? {
+        let position = Expr::tuple([
+            Expr::col(column).into(),
+            Expr::col(booking::Column::Id).into(),
+        ]);
+        let after = Expr::tuple([
+            Expr::val(cursor.timestamp).into(),
+            Expr::val(cursor.id).into(),
+        ]);
+        query = query.filter(match order {
+            Order::Desc => position.lt(after),
+            _ => position.gt(after),
+        });
+    }
+
+    let mut bookings = query.all(&state.db).await?;
+    let next_cursor = if bookings.len() as u64 > limit {
+        bookings.truncate(limit as usize);
+        bookings.last().map(|b| {
+            let timestamp = match column {
+                booking::Column::UpdatedAt => b.updated_at,
+                _ => b.created_at,
+            };
+            Cursor {
+                timestamp: timestamp.with_timezone(&Utc),
+                id: b.id,
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    let user_ids: Vec<Uuid> = bookings.iter().map(|b| b.user_id).collect();
+    let users = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids))
+        .all(&state.db)
+        .await?;
 
-    let responses: Vec<BookingInfo> = bookings
+    let items: Vec<BookingInfo> = bookings
         .into_iter()
         .map(|b| {
             let user = users.iter().find(|u| u.id == b.user_id);
@@ -416,28 +2178,219 @@ pub async fn list_all_bookings(
                 seats: b.seats,
                 pickup_lat: b.pickup_lat,
                 pickup_lng: b.pickup_lng,
+                total_fare: b.total_fare,
+                status: b.status,
+                cancelled_at: b.cancelled_at.map(|t| t.with_timezone(&Utc)),
                 created_at: b.created_at.with_timezone(&Utc),
+                updated_at: b.updated_at.with_timezone(&Utc),
             }
         })
         .collect();
 
+    Ok(Json(CursorPage { items, next_cursor }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserBookingInfo {
+    #[serde(flatten)]
+    pub booking: BookingInfo,
+    pub origin_city: String,
+    pub destination_city: String,
+    pub departure_time: DateTime<Utc>,
+}
+
+/// List one user's bookings with their journey details, newest first (admin)
+pub async fn list_user_bookings(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> AppResult<Json<Vec<UserBookingInfo>>> {
+    // Soft-deleted users are included so support can still look up their history
+    let user = user::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let bookings = booking::Entity::find()
+        .filter(booking::Column::UserId.eq(user_id))
+        .order_by_desc(booking::Column::CreatedAt)
+        .order_by_asc(booking::Column::Id)
+        .all(&state.db)
+        .await?;
+
+    let journey_ids: Vec<Uuid> = bookings.iter().map(|b| b.journey_id).collect();
+    let journeys = journey::Entity::find()
+        .filter(journey::Column::Id.is_in(journey_ids))
+        .all(&state.db)
+        .await?;
+    let cities = city::Entity::find().all(&state.db).await?;
+
+    let responses: Vec<UserBookingInfo> = bookings
+        .into_iter()
+        .filter_map(|b| {
+            let journey = journeys.iter().find(|j| j.id == b.journey_id)?;
+            let origin = cities.iter().find(|c| c.id == journey.origin_city_id);
+            let dest = cities.iter().find(|c| c.id == journey.destination_city_id);
+
+            Some(UserBookingInfo {
+                booking: BookingInfo {
+                    id: b.id,
+                    journey_id: b.journey_id,
+                    user_name: user.name.clone(),
+                    user_email: user.email.clone(),
+                    seats: b.seats,
+                    pickup_lat: b.pickup_lat,
+                    pickup_lng: b.pickup_lng,
+                    total_fare: b.total_fare,
+                    status: b.status,
+                    cancelled_at: b.cancelled_at.map(|t| t.with_timezone(&Utc)),
+                    created_at: b.created_at.with_timezone(&Utc),
+                    updated_at: b.updated_at.with_timezone(&Utc),
+                },
+                origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
+                destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
+                departure_time: journey.departure_time.with_timezone(&Utc),
+            })
+        })
+        .collect();
+
     Ok(Json(responses))
 }
 
-/// Delete any booking (admin)
+/// What was deleted, for the admin's audit trail
+#[derive(Debug, Serialize)]
+pub struct DeletedBookingResponse {
+    pub message: String,
+    pub booking_id: Uuid,
+    pub reference_code: String,
+    pub journey_id: Uuid,
+    pub seats: i32,
+    pub status: BookingStatus,
+    pub passenger_name: String,
+    pub passenger_email: String,
+}
+
+/// Delete any booking (admin), returning who it belonged to
 pub async fn delete_booking(
     State(state): State<AppState>,
     Path(booking_id): Path<Uuid>,
-) -> AppResult<Json<serde_json::Value>> {
-    let result = booking::Entity::delete_by_id(booking_id)
-        .exec(&state.db)
+) -> AppResult<Json<DeletedBookingResponse>> {
+    let txn = state.db.begin().await?;
+
+    let booking = booking::Entity::find_by_id(booking_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Booking not found".to_string()))?;
+    // Deleted accounts keep their bookings, so look the passenger up regardless
+    let passenger = user::Entity::find_by_id(booking.user_id).one(&txn).await?;
+
+    // Same as a traveller cancelling: freed seats go to the waitlist
+    let journey = journey::Entity::find_by_id(booking.journey_id)
+        .lock_exclusive()
+        .one(&txn)
         .await?;
 
-    if result.rows_affected == 0 {
-        return Err(AppError::NotFound("Booking not found".to_string()));
+    booking::Entity::delete_by_id(booking_id).exec(&txn).await?;
+
+    if let Some(j) = &journey
+        && booking.status == BookingStatus::Confirmed
+        && j.status == JourneyStatus::Scheduled
+    {
+        promote_waitlisted(&txn, j).await?;
+    }
+
+    txn.commit().await?;
+
+    let (passenger_name, passenger_email) =
+        passenger.map(|u| (u.name, u.email)).unwrap_or_default();
+    Ok(Json(DeletedBookingResponse {
+        message: "Booking deleted".to_string(),
+        booking_id: booking.id,
+        reference_code: booking.reference_code,
+        journey_id: booking.journey_id,
+        seats: booking.seats,
+        status: booking.status,
+        passenger_name,
+        passenger_email,
+    }))
+}
+
+/// Most bookings one bulk delete may name
+const MAX_BULK_DELETE: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct BulkDeleteBookingsRequest {
+    pub booking_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkDeleteBookingsResponse {
+    pub deleted: usize,
+    pub not_found: usize,
+    pub not_found_ids: Vec<Uuid>,
+}
+
+/// Delete many bookings at once (admin), all in one transaction. Unknown ids are
+/// reported rather than failing the request.
+pub async fn bulk_delete_bookings(
+    State(state): State<AppState>,
+    Json(payload): Json<BulkDeleteBookingsRequest>,
+) -> AppResult<Json<BulkDeleteBookingsResponse>> {
+    if payload.booking_ids.is_empty() {
+        return Err(AppError::BadRequest(
+            "booking_ids must not be empty".to_string(),
+        ));
+    }
+    if payload.booking_ids.len() > MAX_BULK_DELETE {
+        return Err(AppError::BadRequest(format!(
+            "At most {} bookings can be deleted at once",
+            MAX_BULK_DELETE
+        )));
+    }
+
+    let mut ids = payload.booking_ids;
+    ids.sort();
+    ids.dedup();
+
+    let txn = state.db.begin().await?;
+    let bookings = booking::Entity::find()
+        .filter(booking::Column::Id.is_in(ids.clone()))
+        .all(&txn)
+        .await?;
+
+    // Lock every affected journey, in id order so concurrent bulk deletes can't deadlock
+    let journey_ids: Vec<Uuid> = bookings.iter().map(|b| b.journey_id).collect();
+    let journeys = journey::Entity::find()
+        .filter(journey::Column::Id.is_in(journey_ids))
+        .order_by_asc(journey::Column::Id)
+        .lock_exclusive()
+        .all(&txn)
+        .await?;
+
+    let found: Vec<Uuid> = bookings.iter().map(|b| b.id).collect();
+    let deleted = booking::Entity::delete_many()
+        .filter(booking::Column::Id.is_in(found.clone()))
+        .exec(&txn)
+        .await?
+        .rows_affected as usize;
+
+    // Same as a traveller cancelling: freed seats go to the waitlist
+    for j in journeys.iter().filter(|j| {
+        j.status == JourneyStatus::Scheduled
+            && bookings
+                .iter()
+                .any(|b| b.journey_id == j.id && b.status == BookingStatus::Confirmed)
+    }) {
+        promote_waitlisted(&txn, j).await?;
     }
 
-    Ok(Json(serde_json::json!({ "message": "Booking deleted" })))
+    txn.commit().await?;
+
+    let not_found_ids: Vec<Uuid> = ids.into_iter().filter(|id| !found.contains(id)).collect();
+    Ok(Json(BulkDeleteBookingsResponse {
+        deleted,
+        not_found: not_found_ids.len(),
+        not_found_ids,
+    }))
 }
 
 /// Update booking (admin) - can change pickup point and/or seats
@@ -451,13 +2404,39 @@ pub struct UpdateBookingRequest {
 pub async fn update_booking(
     State(state): State<AppState>,
     Path(booking_id): Path<Uuid>,
+    headers: HeaderMap,
     Json(payload): Json<UpdateBookingRequest>,
-) -> AppResult<Json<BookingInfo>> {
+) -> AppResult<([(HeaderName, String); 1], Json<BookingInfo>)> {
+    let txn = state.db.begin().await?;
+
+    let journey_id = booking::Entity::find_by_id(booking_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Booking not found".to_string()))?
+        .journey_id;
+
+    // Lock the journey first, as traveller edits do, so seats freed here go to the
+    // waitlist without racing new bookings
+    let journey = journey::Entity::find_by_id(journey_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?;
+
     let booking_record = booking::Entity::find_by_id(booking_id)
-        .one(&state.db)
+        .lock_exclusive()
+        .one(&txn)
         .await?
         .ok_or_else(|| AppError::NotFound("Booking not found".to_string()))?;
 
+    // Reject edits based on a stale copy of the booking
+    check_if_match(&headers, &booking_etag(&booking_record))?;
+
+    if booking_record.status == BookingStatus::Cancelled {
+        return Err(AppError::BadRequest(
+            "Cancelled bookings can't be modified".to_string(),
+        ));
+    }
+
     let mut active: booking::ActiveModel = booking_record.clone().into();
 
     // Update pickup point (no validation - admin can set any location)
@@ -475,26 +2454,47 @@ pub async fn update_booking(
             return Err(AppError::BadRequest("Seats must be positive".to_string()));
         }
 
+        // Keep the per-seat price the traveller booked at
+        let per_seat = booking_record.total_fare / i64::from(booking_record.seats);
         active.seats = Set(new_seats);
+        active.total_fare = Set(calculate_fare(per_seat, new_seats)?);
+    }
+
+    let updated = active.update(&txn).await?;
+
+    // Same as a traveller giving up seats: they go to the waitlist
+    if let Some(j) = &journey
+        && updated.status == BookingStatus::Confirmed
+        && updated.seats < booking_record.seats
+        && j.status == JourneyStatus::Scheduled
+    {
+        promote_waitlisted(&txn, j).await?;
     }
 
-    let updated = active.update(&state.db).await?;
+    txn.commit().await?;
 
     // Get user info for response
     let user = user::Entity::find_by_id(updated.user_id)
         .one(&state.db)
         .await?;
 
-    Ok(Json(BookingInfo {
-        id: updated.id,
-        journey_id: updated.journey_id,
-        user_name: user.as_ref().map(|u| u.name.clone()).unwrap_or_default(),
-        user_email: user.as_ref().map(|u| u.email.clone()).unwrap_or_default(),
-        seats: updated.seats,
-        pickup_lat: updated.pickup_lat,
-        pickup_lng: updated.pickup_lng,
-        created_at: updated.created_at.with_timezone(&Utc),
-    }))
+    Ok((
+        [(header::ETAG, booking_etag(&updated))],
+        Json(BookingInfo {
+            id: updated.id,
+            journey_id: updated.journey_id,
+            user_name: user.as_ref().map(|u| u.name.clone()).unwrap_or_default(),
+            user_email: user.as_ref().map(|u| u.email.clone()).unwrap_or_default(),
+            seats: updated.seats,
+            pickup_lat: updated.pickup_lat,
+            pickup_lng: updated.pickup_lng,
+            total_fare: updated.total_fare,
+            status: updated.status,
+            cancelled_at: updated.cancelled_at.map(|t| t.with_timezone(&Utc)),
+            created_at: updated.created_at.with_timezone(&Utc),
+            updated_at: updated.updated_at.with_timezone(&Utc),
+        }),
+    ))
 }
 
 // ============ Journey Passengers (for admin view) ============
@@ -504,6 +2504,7 @@ pub async fn update_booking(
 pub struct PassengerPickupInfo {
     pub booking_id: Uuid,
     pub passenger_name: String,
+    pub passenger_email: String,
     pub seats: i32,
     pub pickup_lat: f64,
     pub pickup_lng: f64,
@@ -518,12 +2519,30 @@ pub struct JourneyPassengersResponse {
     pub passengers: Vec<PassengerPickupInfo>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ManifestQuery {
+    pub format: Option<String>,
+}
+
+/// Column order of the CSV passenger manifest
+const MANIFEST_CSV_COLUMNS: [&str; 6] = [
+    "booking_id",
+    "passenger_name",
+    "passenger_email",
+    "seats",
+    "pickup_lat",
+    "pickup_lng",
+];
+
 /// Get passenger pickup points for a specific journey (admin)
-/// Unlike the driver version, this doesn't check if the admin is assigned to the journey
+/// Unlike the driver version, this doesn't check if the admin is assigned to the journey.
+/// Returns a CSV manifest instead of JSON when asked via `?format=csv` or `Accept: text/csv`.
 pub async fn journey_passengers(
     State(state): State<AppState>,
     Path(journey_id): Path<Uuid>,
-) -> AppResult<Json<JourneyPassengersResponse>> {
+    Query(query): Query<ManifestQuery>,
+    headers: HeaderMap,
+) -> AppResult<Response> {
     // Get the journey
     let journey = journey::Entity::find_by_id(journey_id)
         .one(&state.db)
@@ -534,14 +2553,19 @@ pub async fn journey_passengers(
     let origin = cities.iter().find(|c| c.id == journey.origin_city_id);
     let dest = cities.iter().find(|c| c.id == journey.destination_city_id);
 
-    // Get all bookings for this journey
+    // Get the confirmed bookings for this journey; waitlisted passengers don't ride
     let bookings = booking::Entity::find()
         .filter(booking::Column::JourneyId.eq(journey_id))
+        .filter(booking::Column::Status.eq(BookingStatus::Confirmed))
         .all(&state.db)
         .await?;
 
     // Get user info for each booking
-    let users = user::Entity::find().all(&state.db).await?;
+    let passenger_ids: Vec<Uuid> = bookings.iter().map(|b| b.user_id).collect();
+    let users = user::Entity::find()
+        .filter(user::Column::Id.is_in(passenger_ids))
+        .all(&state.db)
+        .await?;
 
     let passengers: Vec<PassengerPickupInfo> = bookings
         .into_iter()
@@ -550,6 +2574,7 @@ pub async fn journey_passengers(
             PassengerPickupInfo {
                 booking_id: b.id,
                 passenger_name: user.map(|u| u.name.clone()).unwrap_or_default(),
+                passenger_email: user.map(|u| u.email.clone()).unwrap_or_default(),
                 seats: b.seats,
                 pickup_lat: b.pickup_lat,
                 pickup_lng: b.pickup_lng,
@@ -557,11 +2582,393 @@ pub async fn journey_passengers(
         })
         .collect();
 
+    if wants_csv(query.format.as_deref(), &headers) {
+        let rows = passengers.into_iter().map(|p| {
+            vec![
+                p.booking_id.to_string(),
+                p.passenger_name,
+                p.passenger_email,
+                p.seats.to_string(),
+                p.pickup_lat.to_string(),
+                p.pickup_lng.to_string(),
+            ]
+        });
+        let filename = format!("manifest-{}.csv", journey.id);
+        return Ok(CsvResponse::new(filename, &MANIFEST_CSV_COLUMNS, rows).into_response());
+    }
+
     Ok(Json(JourneyPassengersResponse {
         journey_id: journey.id,
         origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
         destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
         departure_time: journey.departure_time.with_timezone(&Utc),
         passengers,
+    })
+    .into_response())
+}
+
+// ============ Stats ============
+
+/// Accounts by role, not counting soft-deleted users
+#[derive(Debug, Default, Serialize)]
+pub struct UserCounts {
+    pub admin: u64,
+    pub driver: u64,
+    pub traveller: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JourneyCounts {
+    /// Departing now or later, whatever their status
+    pub upcoming: u64,
+    pub past: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatsResponse {
+    pub users: UserCounts,
+    pub journeys: JourneyCounts,
+    pub total_bookings: u64,
+    /// Seats held by confirmed bookings
+    pub seats_booked: i64,
+    /// Mean share of seats taken on upcoming, non-cancelled journeys, as a percentage
+    /// rounded to one decimal; `None` when there are no such journeys
+    pub average_utilization_percent: Option<f64>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct RoleCount {
+    role: UserRole,
+    count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct Utilization {
+    average: Option<f64>,
+}
+
+/// Turn a 0..1 ratio into a percentage with one decimal, e.g. 2/3 -> 66.7
+pub fn utilization_percent(ratio: f64) -> f64 {
+    (ratio * 1000.0).round() / 10.0
+}
+
+/// Aggregate counts across users, journeys and bookings (admin).
+/// Everything is computed in the database; no table is loaded into memory.
+pub async fn stats(State(state): State<AppState>) -> AppResult<Json<StatsResponse>> {
+    let now = Utc::now();
+
+    let role_counts = user::Entity::find_active()
+        .select_only()
+        .column(user::Column::Role)
+        .column_as(user::Column::Id.count(), "count")
+        .group_by(user::Column::Role)
+        .into_model::<RoleCount>()
+        .all(&state.db)
+        .await?;
+
+    let mut users = UserCounts::default();
+    for RoleCount { role, count } in role_counts {
+        let count = count as u64;
+        match role {
+            UserRole::Admin => users.admin = count,
+            UserRole::Driver => users.driver = count,
+            UserRole::Traveller => users.traveller = count,
+        }
+    }
+
+    let upcoming = journey::Entity::find()
+        .filter(journey::Column::DepartureTime.gte(now))
+        .count(&state.db)
+        .await?;
+    let past = journey::Entity::find()
+        .filter(journey::Column::DepartureTime.lt(now))
+        .count(&state.db)
+        .await?;
+
+    let total_bookings = booking::Entity::find()
+        .filter(booking::Column::Status.ne(BookingStatus::Cancelled))
+        .count(&state.db)
+        .await?;
+    let seats_booked: Option<i64> = booking::Entity::find()
+        .select_only()
+        .column_as(booking::Column::Seats.sum(), "seats")
+        .filter(booking::Column::Status.eq(BookingStatus::Confirmed))
+        .into_tuple()
+        .one(&state.db)
+        .await?
+        .flatten();
+
+    // Average of per-journey ratios (not total booked / total seats), so a nearly empty
+    // big bus and a full small one weigh the same. Journeys without bookings count as 0%.
+    let utilization = Utilization::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"SELECT AVG(COALESCE(b.booked, 0)::float8 / NULLIF(j.total_seats, 0)) AS average
+           FROM journey j
+           LEFT JOIN (
+               SELECT journey_id, SUM(seats) AS booked
+               FROM booking
+               WHERE status = 'confirmed'
+               GROUP BY journey_id
+           ) b ON b.journey_id = j.id
+           WHERE j.departure_time >= $1 AND j.status <> 'cancelled'"#,
+        [now.into()],
+    ))
+    .one(&state.db)
+    .await?
+    .and_then(|u| u.average);
+
+    Ok(Json(StatsResponse {
+        users,
+        journeys: JourneyCounts { upcoming, past },
+        total_bookings,
+        seats_booked: seats_booked.unwrap_or(0),
+        average_utilization_percent: utilization.map(utilization_percent),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn journey_at(departure: DateTime<Utc>) -> journey::Model {
+        journey::Model {
+            id: Uuid::new_v4(),
+            origin_city_id: 1,
+            destination_city_id: 2,
+            departure_time: departure.into(),
+            total_seats: 40,
+            driver_id: None,
+            status: JourneyStatus::Scheduled,
+            base_fare: 0,
+            booking_close_minutes: 0,
+            created_by: None,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        }
+    }
+
+    fn booking_for(journey_id: Uuid, seats: i32) -> booking::Model {
+        booking::Model {
+            id: Uuid::new_v4(),
+            reference_code: "A1B2C3".to_string(),
+            journey_id,
+            user_id: Uuid::new_v4(),
+            seats,
+            pickup_lat: -10.18,
+            pickup_lng: 123.62,
+            total_fare: 0,
+            status: BookingStatus::Confirmed,
+            cancelled_at: None,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_journey_schedule_validation() {
+        let now = Utc::now();
+        assert!(validate_journey_schedule(Some(now + Duration::hours(1)), Some(1), now).is_ok());
+        assert!(validate_journey_schedule(None, None, now).is_ok());
+        assert!(validate_journey_schedule(Some(now), None, now).is_err());
+        assert!(validate_journey_schedule(Some(now - Duration::hours(1)), None, now).is_err());
+        assert!(validate_journey_schedule(None, Some(0), now).is_err());
+        assert!(validate_journey_schedule(None, Some(-3), now).is_err());
+    }
+
+    #[test]
+    fn test_stop_validation() {
+        let departure = Utc::now();
+        let stop = |city_id, hours| JourneyStopRequest {
+            city_id,
+            scheduled_time: departure + Duration::hours(hours),
+        };
+
+        assert!(validate_stops(&[], 1, 2, departure).is_ok());
+        assert!(validate_stops(&[stop(3, 1), stop(4, 2)], 1, 2, departure).is_ok());
+        // Repeats a city, or one of the route's ends
+        assert!(validate_stops(&[stop(3, 1), stop(3, 2)], 1, 2, departure).is_err());
+        assert!(validate_stops(&[stop(1, 1)], 1, 2, departure).is_err());
+        assert!(validate_stops(&[stop(2, 1)], 1, 2, departure).is_err());
+        // Out of order, simultaneous, or before departure
+        assert!(validate_stops(&[stop(3, 2), stop(4, 1)], 1, 2, departure).is_err());
+        assert!(validate_stops(&[stop(3, 1), stop(4, 1)], 1, 2, departure).is_err());
+        assert!(validate_stops(&[stop(3, 0)], 1, 2, departure).is_err());
+    }
+
+    #[test]
+    fn test_journey_distance_within_limit() {
+        let jakarta = city::Model::test_at("Jakarta", -6.2088, 106.8456);
+        let bandung = city::Model::test_at("Bandung", -6.9175, 107.6191);
+
+        let distance = validate_journey_distance(&jakarta, &bandung, 1500.0).unwrap();
+        assert!(distance > 100.0 && distance < 150.0);
+    }
+
+    #[test]
+    fn test_journey_distance_over_limit() {
+        // Roughly 10,000 km apart
+        let jakarta = city::Model::test_at("Jakarta", -6.2088, 106.8456);
+        let faraway = city::Model::test_at("Faraway", 48.8566, 2.3522);
+
+        assert!(matches!(
+            validate_journey_distance(&jakarta, &faraway, 1500.0),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(validate_journey_distance(&jakarta, &faraway, 20_000.0).is_ok());
+    }
+
+    #[test]
+    fn test_city_geometry_validation() {
+        assert!(validate_city_geometry(-10.18, 123.62, 15.0).is_ok());
+        assert!(validate_city_geometry(90.0, -180.0, 0.5).is_ok());
+        assert!(validate_city_geometry(90.1, 0.0, 10.0).is_err());
+        assert!(validate_city_geometry(-91.0, 0.0, 10.0).is_err());
+        assert!(validate_city_geometry(0.0, 180.5, 10.0).is_err());
+        assert!(validate_city_geometry(0.0, 0.0, 0.0).is_err());
+        assert!(validate_city_geometry(0.0, 0.0, -5.0).is_err());
+        assert!(validate_city_geometry(f64::NAN, 0.0, 10.0).is_err());
+    }
+
+    #[test]
+    fn test_recurring_departures_pick_matching_weekdays() {
+        // 2024-01-01 is a Monday; 08:00 in Jakarta (UTC+7) is 01:00 UTC
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 1, 14).unwrap();
+        let departures = recurring_departures(
+            start,
+            end,
+            NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            &[Weekday::Mon, Weekday::Fri],
+            parse_timezone("Asia/Jakarta"),
+        )
+        .unwrap();
+
+        let days: Vec<_> = departures.iter().map(|d| d.to_rfc3339()).collect();
+        assert_eq!(
+            days,
+            [
+                "2024-01-01T01:00:00+00:00",
+                "2024-01-05T01:00:00+00:00",
+                "2024-01-08T01:00:00+00:00",
+                "2024-01-12T01:00:00+00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recurring_departures_reject_bad_ranges() {
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let eight = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+        let tz = parse_timezone("Asia/Jakarta");
+
+        assert!(recurring_departures(day(10), day(1), eight, &[Weekday::Mon], tz).is_err());
+        assert!(recurring_departures(day(1), day(10), eight, &[], tz).is_err());
+
+        // Daily for two years is well past the cap
+        let far = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let every_day = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ];
+        assert!(recurring_departures(day(1), far, eight, &every_day, tz).is_err());
+    }
+
+    #[test]
+    fn test_utilization_percent_rounds_to_one_decimal() {
+        assert_eq!(utilization_percent(2.0 / 3.0), 66.7);
+        assert_eq!(utilization_percent(1.0 / 3.0), 33.3);
+        assert_eq!(utilization_percent(0.125), 12.5);
+        assert_eq!(utilization_percent(1.0), 100.0);
+        assert_eq!(utilization_percent(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_timezone_validation() {
+        assert_eq!(validate_timezone("Asia/Makassar").unwrap(), "Asia/Makassar");
+        assert!(validate_timezone("Asia/Atlantis").is_err());
+        assert!(validate_timezone("").is_err());
+    }
+
+    #[test]
+    fn test_reschedule_preview_counts_affected_bookings() {
+        let now = Utc::now();
+        let journey = journey_at(now + Duration::days(2));
+        let bookings = vec![booking_for(journey.id, 2), booking_for(journey.id, 3)];
+
+        let preview = build_reschedule_preview(
+            &journey,
+            &bookings,
+            now + Duration::days(3),
+            now,
+            Duration::zero(),
+        );
+
+        assert_eq!(preview.affected_bookings, 2);
+        assert_eq!(preview.affected_seats, 5);
+        assert!(preview.cancellation_window_violations.is_empty());
+    }
+
+    #[test]
+    fn test_reschedule_preview_flags_closed_cancellation_window() {
+        let now = Utc::now();
+        let journey = journey_at(now + Duration::days(2));
+        let bookings = vec![booking_for(journey.id, 1)];
+
+        let preview = build_reschedule_preview(
+            &journey,
+            &bookings,
+            now - Duration::hours(1),
+            now,
+            Duration::zero(),
+        );
+
+        assert_eq!(preview.cancellation_window_violations, vec![bookings[0].id]);
+    }
+
+    #[test]
+    fn test_reschedule_preview_respects_cancellation_cutoff() {
+        let now = Utc::now();
+        let journey = journey_at(now + Duration::days(2));
+        let bookings = vec![booking_for(journey.id, 1)];
+
+        // Still in the future, but inside a 2-hour cutoff
+        let preview = build_reschedule_preview(
+            &journey,
+            &bookings,
+            now + Duration::hours(1),
+            now,
+            Duration::hours(2),
+        );
+
+        assert_eq!(preview.cancellation_window_violations, vec![bookings[0].id]);
+    }
+
+    #[test]
+    fn test_parse_role() {
+        assert_eq!(parse_role("Driver").unwrap(), UserRole::Driver);
+        assert_eq!(parse_role("admin").unwrap(), UserRole::Admin);
+        assert!(matches!(
+            parse_role("superuser"),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_last_admin_cannot_be_demoted() {
+        let (admin, driver) = (UserRole::Admin, UserRole::Driver);
+
+        assert!(matches!(
+            ensure_admin_remains(&admin, &driver, 0),
+            Err(AppError::Conflict(_))
+        ));
+        assert!(ensure_admin_remains(&admin, &driver, 1).is_ok());
+        // Not a demotion
+        assert!(ensure_admin_remains(&admin, &admin, 0).is_ok());
+        assert!(ensure_admin_remains(&driver, &UserRole::Traveller, 0).is_ok());
+    }
+}
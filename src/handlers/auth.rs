@@ -1,42 +1,75 @@
-use argon2::{
-    Argon2,
-    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+use axum::{Extension, Json, extract::State};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, Set,
+    SqlErr, TransactionTrait,
 };
-use axum::{Json, extract::State};
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::AppState;
+use crate::db::revocation::{is_token_revoked, revoke_token};
+use crate::entities::booking::BookingStatus;
+use crate::entities::journey::JourneyStatus;
 use crate::entities::user::{self, UserRole};
-use crate::error::{AppError, AppResult};
-use crate::utils::jwt::create_token;
+use crate::entities::{booking, journey};
+use crate::error::{AppError, AppResult, ErrorResponse};
+use crate::utils::google::{GoogleIdentity, fetch_google_certs, verify_google_id_token};
+use crate::utils::jwt::{Claims, create_refresh_token, create_token, verify_refresh_token};
+use crate::utils::password::{hash_password, needs_rehash, password_matches};
+use crate::utils::validation::{
+    normalize_email, normalize_name, validate_email, validate_password,
+};
+use crate::{AppState, Config};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct RegisterRequest {
     pub email: String,
     pub password: String,
     pub name: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct GoogleLoginRequest {
     pub credential: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct AuthResponse {
     pub token: String,
+    pub refresh_token: String,
     pub user: UserInfo,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LogoutRequest {
+    /// Also revoke this refresh token so it can't mint new access tokens
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RefreshResponse {
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserInfo {
     pub id: Uuid,
     pub email: String,
@@ -44,71 +77,151 @@ pub struct UserInfo {
     pub role: UserRole,
 }
 
+/// Issue an access/refresh token pair for a user
+fn issue_tokens(user: user::Model, config: &Config) -> AppResult<AuthResponse> {
+    let token = create_token(
+        user.id,
+        &user.email,
+        &user.name,
+        user.role.clone(),
+        config.into(),
+        config.jwt_expiration_hours_for(&user.role),
+    )?;
+    let refresh_token = create_refresh_token(
+        user.id,
+        &user.email,
+        &user.name,
+        user.role.clone(),
+        config.into(),
+        config.refresh_token_expiration_days,
+    )?;
+
+    Ok(AuthResponse {
+        token,
+        refresh_token,
+        user: UserInfo {
+            id: user.id,
+            email: user.email,
+            name: user.name,
+            role: user.role,
+        },
+    })
+}
+
 /// Register a new traveller account
+#[utoipa::path(
+    post,
+    path = "/api/auth/register",
+    tag = "auth",
+    request_body = RegisterRequest,
+    responses(
+        (status = 200, body = AuthResponse),
+        (status = 400, description = "Invalid email or name", body = ErrorResponse),
+        (status = 409, description = "Email already registered", body = ErrorResponse),
+    )
+)]
 pub async fn register(
     State(state): State<AppState>,
     Json(payload): Json<RegisterRequest>,
 ) -> AppResult<Json<AuthResponse>> {
+    create_account(&state, payload, false).await.map(Json)
+}
+
+/// Register as a traveller with a pending driver application; an admin promotes the
+/// account to driver via `approve_driver`
+#[utoipa::path(
+    post,
+    path = "/api/auth/register-driver",
+    tag = "auth",
+    request_body = RegisterRequest,
+    responses(
+        (
+            status = 200,
+            description = "Traveller account with a pending driver application",
+            body = AuthResponse,
+        ),
+        (status = 400, description = "Invalid email or name", body = ErrorResponse),
+        (status = 409, description = "Email already registered", body = ErrorResponse),
+    )
+)]
+pub async fn register_driver(
+    State(state): State<AppState>,
+    Json(payload): Json<RegisterRequest>,
+) -> AppResult<Json<AuthResponse>> {
+    create_account(&state, payload, true).await.map(Json)
+}
+
+/// Insert a new account. The existence check before it can race with another
+/// registration; the unique email index settles it, and that's a conflict, not a 500.
+pub async fn insert_account<C: ConnectionTrait>(
+    db: &C,
+    account: user::ActiveModel,
+) -> AppResult<user::Model> {
+    account.insert(db).await.map_err(|e| match e.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            AppError::Conflict("Email already registered".to_string())
+        }
+        _ => e.into(),
+    })
+}
+
+/// Create a password-based traveller account and issue tokens for it
+async fn create_account(
+    state: &AppState,
+    payload: RegisterRequest,
+    driver_application: bool,
+) -> AppResult<AuthResponse> {
+    let email = normalize_email(&payload.email);
+    validate_email(&email)?;
+    let name = normalize_name(&payload.name)?;
+
     // Check if email already exists
-    let existing = user::Entity::find()
-        .filter(user::Column::Email.eq(&payload.email))
-        .one(&state.db)
-        .await?;
+    let existing = user::Entity::find_by_email(&email).one(&state.db).await?;
 
     if existing.is_some() {
         return Err(AppError::Conflict("Email already registered".to_string()));
     }
 
     // Hash password
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    let password_hash = argon2
-        .hash_password(payload.password.as_bytes(), &salt)
-        .map_err(|e| AppError::Internal(format!("Failed to hash password: {}", e)))?
-        .to_string();
+    let password_hash = hash_password(&payload.password, &state.config.password_hash_cost())?;
 
     // Create user
     let user_id = Uuid::new_v4();
     let new_user = user::ActiveModel {
         id: Set(user_id),
-        email: Set(payload.email.clone()),
+        email: Set(email),
         password_hash: Set(Some(password_hash)),
         google_id: Set(None),
-        name: Set(payload.name.clone()),
+        name: Set(name),
         role: Set(UserRole::Traveller),
+        driver_application_pending: Set(driver_application),
         ..Default::default()
     };
 
-    let user = new_user.insert(&state.db).await?;
-
-    // Generate token
-    let token = create_token(
-        user.id,
-        &user.email,
-        user.role.clone(),
-        &state.config.jwt_secret,
-        state.config.jwt_expiration_hours,
-    )?;
+    let user = insert_account(&state.db, new_user).await?;
 
-    Ok(Json(AuthResponse {
-        token,
-        user: UserInfo {
-            id: user.id,
-            email: user.email,
-            name: user.name,
-            role: user.role,
-        },
-    }))
+    // Generate tokens
+    issue_tokens(user, &state.config)
 }
 
 /// Login with email and password
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, body = AuthResponse),
+        (status = 401, description = "Invalid email or password", body = ErrorResponse),
+    )
+)]
 pub async fn login(
     State(state): State<AppState>,
     Json(payload): Json<LoginRequest>,
 ) -> AppResult<Json<AuthResponse>> {
-    // Find user by email
-    let user = user::Entity::find()
-        .filter(user::Column::Email.eq(&payload.email))
+    // Find user by email; soft-deleted accounts can't sign in
+    let user = user::Entity::find_by_email(&normalize_email(&payload.email))
+        .filter(user::Column::DeletedAt.is_null())
         .one(&state.db)
         .await?
         .ok_or_else(|| AppError::Unauthorized("Invalid email or password".to_string()))?;
@@ -120,85 +233,77 @@ pub async fn login(
         )
     })?;
 
-    let parsed_hash = PasswordHash::new(password_hash)
-        .map_err(|e| AppError::Internal(format!("Failed to parse password hash: {}", e)))?;
-
-    Argon2::default()
-        .verify_password(payload.password.as_bytes(), &parsed_hash)
-        .map_err(|_| AppError::Unauthorized("Invalid email or password".to_string()))?;
-
-    // Generate token
-    let token = create_token(
-        user.id,
-        &user.email,
-        user.role.clone(),
-        &state.config.jwt_secret,
-        state.config.jwt_expiration_hours,
-    )?;
+    if !password_matches(&payload.password, password_hash)? {
+        return Err(AppError::Unauthorized(
+            "Invalid email or password".to_string(),
+        ));
+    }
 
-    Ok(Json(AuthResponse {
-        token,
-        user: UserInfo {
-            id: user.id,
-            email: user.email,
-            name: user.name,
-            role: user.role,
-        },
-    }))
-}
+    // Upgrade hashes made under older, cheaper settings now that the password is known
+    let cost = state.config.password_hash_cost();
+    let user = if needs_rehash(password_hash, &cost)? {
+        let mut active: user::ActiveModel = user.into();
+        active.password_hash = Set(Some(hash_password(&payload.password, &cost)?));
+        active.update(&state.db).await?
+    } else {
+        user
+    };
 
-#[derive(Debug, Deserialize)]
-struct GoogleTokenInfo {
-    sub: String,
-    email: String,
-    name: Option<String>,
-    aud: String,
+    // Generate tokens
+    Ok(Json(issue_tokens(user, &state.config)?))
 }
 
 /// Login/Register with Google
+#[utoipa::path(
+    post,
+    path = "/api/auth/google",
+    tag = "auth",
+    request_body = GoogleLoginRequest,
+    responses(
+        (status = 200, body = AuthResponse),
+        (status = 401, description = "Invalid Google ID token", body = ErrorResponse),
+    )
+)]
 pub async fn google_login(
     State(state): State<AppState>,
     Json(payload): Json<GoogleLoginRequest>,
 ) -> AppResult<Json<AuthResponse>> {
-    // Verify token with Google
-    let client = reqwest::Client::new();
-    let resp = client
-        .get("https://oauth2.googleapis.com/tokeninfo")
-        .query(&[("id_token", &payload.credential)])
-        .send()
-        .await
-        .map_err(|e| AppError::Internal(format!("Failed to verify Google token: {}", e)))?;
-
-    if !resp.status().is_success() {
-        return Err(AppError::Unauthorized("Invalid Google token".to_string()));
-    }
+    // Verify the ID token's signature against Google's published keys
+    let certs = fetch_google_certs().await?;
+    let identity =
+        verify_google_id_token(&payload.credential, &certs, &state.config.oauth_client_id)?;
 
-    let token_info: GoogleTokenInfo = resp
-        .json()
-        .await
-        .map_err(|e| AppError::Internal(format!("Failed to parse Google response: {}", e)))?;
+    Ok(Json(sign_in_with_google(&state, identity).await?))
+}
 
-    // Verify audience matches our client ID
-    if token_info.aud != state.config.oauth_client_id {
-        return Err(AppError::Unauthorized("Invalid token audience".to_string()));
-    }
+/// Find or create the user for a verified Google identity and issue tokens
+pub async fn sign_in_with_google(
+    state: &AppState,
+    identity: GoogleIdentity,
+) -> AppResult<AuthResponse> {
+    let email = normalize_email(&identity.email);
 
     // Find existing user by google_id or email
     let existing_user = user::Entity::find()
         .filter(
             user::Column::GoogleId
-                .eq(&token_info.sub)
-                .or(user::Column::Email.eq(&token_info.email)),
+                .eq(&identity.sub)
+                .or(user::email_eq(&email)),
         )
         .one(&state.db)
         .await?;
 
     let user = match existing_user {
+        Some(u) if u.deleted_at.is_some() => {
+            return Err(AppError::Unauthorized(
+                "This account has been deleted".to_string(),
+            ));
+        }
         Some(u) => {
             // Link Google account if user exists by email but not google_id
             if u.google_id.is_none() {
                 let mut am: user::ActiveModel = u.into();
-                am.google_id = Set(Some(token_info.sub));
+                am.google_id = Set(Some(identity.sub));
                 am.update(&state.db).await?
             } else {
                 u
@@ -208,10 +313,10 @@ pub async fn google_login(
             // Create new user
             let new_user = user::ActiveModel {
                 id: Set(Uuid::new_v4()),
-                email: Set(token_info.email),
+                email: Set(email),
                 password_hash: Set(None),
-                google_id: Set(Some(token_info.sub)),
-                name: Set(token_info.name.unwrap_or_else(|| "Google User".to_string())),
+                google_id: Set(Some(identity.sub)),
+                name: Set(identity.name.unwrap_or_else(|| "Google User".to_string())),
                 role: Set(UserRole::Traveller),
                 ..Default::default()
             };
@@ -219,22 +324,185 @@ pub async fn google_login(
         }
     };
 
-    // Generate JWT token
+    // Generate tokens
+    issue_tokens(user, &state.config)
+}
+
+/// Exchange a refresh token for a new access token
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    tag = "auth",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, body = RefreshResponse),
+        (
+            status = 401,
+            description = "Invalid, expired or revoked refresh token",
+            body = ErrorResponse,
+        ),
+    )
+)]
+pub async fn refresh(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequest>,
+) -> AppResult<Json<RefreshResponse>> {
+    let claims = verify_refresh_token(&payload.refresh_token, (&state.config).into())?;
+
+    if is_token_revoked(&state.db, claims.jti).await? {
+        return Err(AppError::Unauthorized("Token has been revoked".to_string()));
+    }
+
+    // Re-read the user so role changes and deletions take effect
+    let user = user::Entity::find_active_by_id(claims.sub)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("User no longer exists".to_string()))?;
+
     let token = create_token(
         user.id,
         &user.email,
+        &user.name,
         user.role.clone(),
-        &state.config.jwt_secret,
-        state.config.jwt_expiration_hours,
+        (&state.config).into(),
+        state.config.jwt_expiration_hours_for(&user.role),
     )?;
 
-    Ok(Json(AuthResponse {
-        token,
-        user: UserInfo {
-            id: user.id,
-            email: user.email,
-            name: user.name,
-            role: user.role,
-        },
-    }))
+    Ok(Json(RefreshResponse { token }))
+}
+
+/// Log out by revoking the current access token (and optionally a refresh token)
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "auth",
+    request_body = LogoutRequest,
+    security(("bearer_auth" = [])),
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    payload: Option<Json<LogoutRequest>>,
+) -> AppResult<Json<serde_json::Value>> {
+    revoke_token(&state.db, &claims).await?;
+
+    if let Some(refresh_token) = payload.and_then(|Json(p)| p.refresh_token) {
+        let refresh_claims = verify_refresh_token(&refresh_token, (&state.config).into())?;
+        if refresh_claims.sub != claims.sub {
+            return Err(AppError::Forbidden(
+                "Refresh token belongs to another user".to_string(),
+            ));
+        }
+        revoke_token(&state.db, &refresh_claims).await?;
+    }
+
+    Ok(Json(serde_json::json!({ "message": "Logged out" })))
+}
+
+/// Change the logged-in user's password after checking the current one
+#[utoipa::path(
+    put,
+    path = "/api/auth/password",
+    tag = "auth",
+    request_body = ChangePasswordRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = serde_json::Value),
+        (
+            status = 400,
+            description = "Weak new password or Google-only account",
+            body = ErrorResponse,
+        ),
+        (status = 401, description = "Current password is incorrect", body = ErrorResponse),
+    )
+)]
+pub async fn change_password(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<ChangePasswordRequest>,
+) -> AppResult<Json<serde_json::Value>> {
+    let user = user::Entity::find_active_by_id(claims.sub)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    let password_hash = user.password_hash.as_ref().ok_or_else(|| {
+        AppError::BadRequest(
+            "This account signs in with Google and has no password to change".to_string(),
+        )
+    })?;
+
+    if !password_matches(&payload.current_password, password_hash)? {
+        return Err(AppError::Unauthorized(
+            "Current password is incorrect".to_string(),
+        ));
+    }
+
+    validate_password(&payload.new_password)?;
+
+    let mut active: user::ActiveModel = user.into();
+    active.password_hash = Set(Some(hash_password(
+        &payload.new_password,
+        &state.config.password_hash_cost(),
+    )?));
+    active.update(&state.db).await?;
+
+    Ok(Json(serde_json::json!({ "message": "Password changed" })))
+}
+
+/// Delete the logged-in traveller's own account, once they have no upcoming trips.
+/// Routed behind `require_traveller`, so admins and drivers can't delete themselves here.
+#[utoipa::path(
+    delete,
+    path = "/api/auth/account",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = serde_json::Value),
+        (
+            status = 409,
+            description = "Upcoming bookings must be cancelled first",
+            body = ErrorResponse,
+        ),
+    )
+)]
+pub async fn delete_account(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<serde_json::Value>> {
+    let txn = state.db.begin().await?;
+
+    let upcoming = booking::Entity::find()
+        .filter(booking::Column::UserId.eq(claims.sub))
+        .filter(booking::Column::Status.ne(BookingStatus::Cancelled))
+        .inner_join(journey::Entity)
+        .filter(journey::Column::DepartureTime.gte(Utc::now()))
+        .filter(journey::Column::Status.ne(JourneyStatus::Cancelled))
+        .count(&txn)
+        .await?;
+
+    if upcoming > 0 {
+        return Err(AppError::Conflict(format!(
+            "You have {} upcoming booking(s); cancel them before deleting your account",
+            upcoming
+        )));
+    }
+
+    booking::Entity::delete_many()
+        .filter(booking::Column::UserId.eq(claims.sub))
+        .exec(&txn)
+        .await?;
+
+    let result = user::Entity::delete_by_id(claims.sub).exec(&txn).await?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("User not found".to_string()));
+    }
+
+    txn.commit().await?;
+
+    // The account is gone, so the token used to delete it shouldn't keep working
+    revoke_token(&state.db, &claims).await?;
+
+    Ok(Json(serde_json::json!({ "message": "Account deleted" })))
 }
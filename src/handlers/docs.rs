@@ -0,0 +1,67 @@
+use axum::Json;
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+};
+
+use crate::handlers::{auth, traveller};
+
+/// OpenAPI description of the auth and traveller endpoints
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        auth::register,
+        auth::register_driver,
+        auth::login,
+        auth::google_login,
+        auth::refresh,
+        auth::logout,
+        auth::change_password,
+        auth::delete_account,
+        traveller::list_journeys,
+        traveller::get_journey,
+        traveller::journey_availability,
+        traveller::list_cities,
+        traveller::nearest_city,
+        traveller::create_booking,
+        traveller::hold_seats,
+        traveller::confirm_hold,
+        traveller::my_bookings,
+        traveller::get_booking,
+        traveller::get_booking_by_reference,
+        traveller::driver_contact,
+        traveller::update_booking,
+        traveller::cancel_booking,
+        traveller::trip_summary,
+    ),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "auth", description = "Accounts, sign-in and tokens"),
+        (name = "journeys", description = "Public journey and city lookup"),
+        (name = "bookings", description = "A traveller's own bookings"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the `Authorization: Bearer <JWT>` scheme referenced by protected paths
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// Serve the generated OpenAPI spec
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
@@ -1,14 +1,22 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     Extension, Json,
 };
-use chrono::{DateTime, Utc};
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
-use serde::Serialize;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, FromQueryResult, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, QueryTrait, Set,
+};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::db::booked_seats_by_journey;
+use crate::entities::booking::BookingStatus;
+use crate::entities::journey::JourneyStatus;
 use crate::entities::{booking, city, journey};
 use crate::error::{AppError, AppResult};
+use crate::handlers::traveller::local_departure;
+use crate::utils::geo::{centroid, cluster_points};
 use crate::utils::jwt::Claims;
 use crate::AppState;
 
@@ -18,49 +26,191 @@ pub struct DriverJourneyResponse {
     pub origin_city: String,
     pub destination_city: String,
     pub departure_time: DateTime<Utc>,
+    /// `departure_time` in the origin city's timezone
+    pub departure_time_local: DateTime<FixedOffset>,
     pub total_seats: i32,
     pub booked_seats: i32,
+    pub status: JourneyStatus,
 }
 
-/// List journeys assigned to the logged-in driver
+#[derive(Debug, Serialize)]
+pub struct DriverJourneysResponse {
+    /// Departing now or later, soonest first
+    pub upcoming: Vec<DriverJourneyResponse>,
+    /// Already departed, most recent first
+    pub past: Vec<DriverJourneyResponse>,
+}
+
+/// List journeys assigned to the logged-in driver, split into upcoming and past
 pub async fn my_journeys(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
-) -> AppResult<Json<Vec<DriverJourneyResponse>>> {
+) -> AppResult<Json<DriverJourneysResponse>> {
     let journeys = journey::Entity::find()
         .filter(journey::Column::DriverId.eq(claims.sub))
+        .order_by_asc(journey::Column::DepartureTime)
+        .order_by_asc(journey::Column::Id)
         .all(&state.db)
         .await?;
+    let ids: Vec<Uuid> = journeys.iter().map(|j| j.id).collect();
+    let booked_by_journey = booked_seats_by_journey(&state.db, &ids).await?;
 
     let cities = city::Entity::find().all(&state.db).await?;
 
-    let mut responses = Vec::new();
+    let now = Utc::now();
+    let mut upcoming = Vec::new();
+    let mut past = Vec::new();
     for j in journeys {
         let origin = cities.iter().find(|c| c.id == j.origin_city_id);
         let dest = cities.iter().find(|c| c.id == j.destination_city_id);
 
-        let booked: i32 = booking::Entity::find()
-            .filter(booking::Column::JourneyId.eq(j.id))
-            .all(&state.db)
-            .await?
-            .iter()
-            .map(|b| b.seats)
-            .sum();
+        let booked = booked_by_journey.get(&j.id).copied().unwrap_or(0);
 
-        responses.push(DriverJourneyResponse {
+        let bucket = if j.departure_time >= now {
+            &mut upcoming
+        } else {
+            &mut past
+        };
+        bucket.push(DriverJourneyResponse {
             id: j.id,
             origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
             destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
             departure_time: j.departure_time.with_timezone(&Utc),
+            departure_time_local: local_departure(&j, origin),
             total_seats: j.total_seats,
             booked_seats: booked,
+            status: j.status,
         });
     }
+    past.reverse();
 
-    Ok(Json(responses))
+    Ok(Json(DriverJourneysResponse { upcoming, past }))
 }
 
 #[derive(Debug, Serialize)]
+pub struct NextJourneyResponse {
+    #[serde(flatten)]
+    pub journey: DriverJourneyResponse,
+    /// Confirmed bookings; `booked_seats` is their seat total
+    pub passenger_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DriverDashboardResponse {
+    /// Soonest journey departing now or later, if any
+    pub next_journey: Option<NextJourneyResponse>,
+    /// Journeys departing today (in each origin city's timezone), including ones already gone
+    pub journeys_today: u64,
+    /// Journeys departing now or later
+    pub upcoming_journeys: u64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct PassengerTotals {
+    passengers: i64,
+    seats: Option<i64>,
+}
+
+/// Summary for the driver app's home screen. Cancelled journeys are left out.
+pub async fn dashboard(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<DriverDashboardResponse>> {
+    let now = Utc::now();
+    let assigned = || {
+        journey::Entity::find()
+            .filter(journey::Column::DriverId.eq(claims.sub))
+            .filter(journey::Column::Status.ne(JourneyStatus::Cancelled))
+    };
+
+    let upcoming_journeys = assigned()
+        .filter(journey::Column::DepartureTime.gte(now))
+        .count(&state.db)
+        .await?;
+
+    // Every city's local "today" contains now and lasts at most 25 hours (on a DST
+    // change), so it lies within 25 hours either side of now
+    let around_today = assigned()
+        .filter(journey::Column::DepartureTime.gte(now - Duration::hours(25)))
+        .filter(journey::Column::DepartureTime.lte(now + Duration::hours(25)))
+        .order_by_asc(journey::Column::DepartureTime)
+        .order_by_asc(journey::Column::Id)
+        .all(&state.db)
+        .await?;
+
+    let next = match around_today.iter().find(|j| j.departure_time >= now) {
+        Some(j) => Some(j.clone()),
+        None => {
+            assigned()
+                .filter(journey::Column::DepartureTime.gte(now))
+                .order_by_asc(journey::Column::DepartureTime)
+                .order_by_asc(journey::Column::Id)
+                .one(&state.db)
+                .await?
+        }
+    };
+
+    let city_ids: Vec<i32> = around_today
+        .iter()
+        .chain(next.iter())
+        .flat_map(|j| [j.origin_city_id, j.destination_city_id])
+        .collect();
+    let cities = city::Entity::find()
+        .filter(city::Column::Id.is_in(city_ids))
+        .all(&state.db)
+        .await?;
+    let origin_of = |j: &journey::Model| cities.iter().find(|c| c.id == j.origin_city_id);
+
+    let journeys_today = around_today
+        .iter()
+        .filter(|j| {
+            let departure = local_departure(j, origin_of(j));
+            departure.date_naive() == now.with_timezone(departure.offset()).date_naive()
+        })
+        .count() as u64;
+
+    let next_journey = match next {
+        Some(j) => {
+            let totals = booking::Entity::find()
+                .select_only()
+                .column_as(booking::Column::Id.count(), "passengers")
+                .column_as(booking::Column::Seats.sum(), "seats")
+                .filter(booking::Column::JourneyId.eq(j.id))
+                .filter(booking::Column::Status.eq(BookingStatus::Confirmed))
+                .into_model::<PassengerTotals>()
+                .one(&state.db)
+                .await?;
+            let (passengers, seats) = totals
+                .map(|t| (t.passengers, t.seats.unwrap_or(0)))
+                .unwrap_or((0, 0));
+
+            let origin = origin_of(&j);
+            let dest = cities.iter().find(|c| c.id == j.destination_city_id);
+            Some(NextJourneyResponse {
+                journey: DriverJourneyResponse {
+                    id: j.id,
+                    origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
+                    destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
+                    departure_time: j.departure_time.with_timezone(&Utc),
+                    departure_time_local: local_departure(&j, origin),
+                    total_seats: j.total_seats,
+                    booked_seats: seats as i32,
+                    status: j.status,
+                },
+                passenger_count: passengers,
+            })
+        }
+        None => None,
+    };
+
+    Ok(Json(DriverDashboardResponse {
+        next_journey,
+        journeys_today,
+        upcoming_journeys,
+    }))
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct PassengerPickupInfo {
     pub booking_id: Uuid,
     pub passenger_name: String,
@@ -75,17 +225,81 @@ pub struct JourneyPassengersResponse {
     pub origin_city: String,
     pub destination_city: String,
     pub departure_time: DateTime<Utc>,
+    /// Whole minutes until departure, rounded down so it turns negative as soon as the
+    /// journey has left
+    pub departure_in_minutes: i64,
+    pub passengers: Vec<PassengerPickupInfo>,
+    /// Nearby pickups grouped together, only when `cluster_radius_km` is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clusters: Option<Vec<PickupCluster>>,
+}
+
+/// Passengers whose pickup points are all within the requested radius of each other
+#[derive(Debug, Serialize)]
+pub struct PickupCluster {
+    pub centroid_lat: f64,
+    pub centroid_lng: f64,
+    pub seats: i32,
     pub passengers: Vec<PassengerPickupInfo>,
 }
 
-/// Get passenger pickup points for a specific journey
+#[derive(Debug, Default, Deserialize)]
+pub struct PassengersQuery {
+    pub cluster_radius_km: Option<f64>,
+    /// Treat journeys that have already departed as not found
+    #[serde(default)]
+    pub upcoming_only: bool,
+}
+
+/// Group passengers into pickup clusters, keeping their original order within each
+pub fn cluster_passengers(
+    passengers: &[PassengerPickupInfo],
+    radius_km: f64,
+) -> Vec<PickupCluster> {
+    let points: Vec<(f64, f64)> = passengers
+        .iter()
+        .map(|p| (p.pickup_lat, p.pickup_lng))
+        .collect();
+
+    cluster_points(&points, radius_km)
+        .into_iter()
+        .map(|members| {
+            let member_points: Vec<(f64, f64)> = members.iter().map(|&i| points[i]).collect();
+            let (centroid_lat, centroid_lng) = centroid(&member_points);
+            let passengers: Vec<PassengerPickupInfo> =
+                members.iter().map(|&i| passengers[i].clone()).collect();
+
+            PickupCluster {
+                centroid_lat,
+                centroid_lng,
+                seats: passengers.iter().map(|p| p.seats).sum(),
+                passengers,
+            }
+        })
+        .collect()
+}
+
+/// Get passenger pickup points for a specific journey, optionally clustered
 pub async fn journey_passengers(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
     Path(journey_id): Path<Uuid>,
+    Query(query): Query<PassengersQuery>,
 ) -> AppResult<Json<JourneyPassengersResponse>> {
+    if let Some(radius) = query.cluster_radius_km
+        && !(radius.is_finite() && radius > 0.0)
+    {
+        return Err(AppError::BadRequest(
+            "cluster_radius_km must be a positive number".to_string(),
+        ));
+    }
+
     // Verify the journey is assigned to this driver
+    let now = Utc::now();
     let journey = journey::Entity::find_by_id(journey_id)
+        .apply_if(query.upcoming_only.then_some(now), |q, now| {
+            q.filter(journey::Column::DepartureTime.gte(now))
+        })
         .one(&state.db)
         .await?
         .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
@@ -100,15 +314,20 @@ pub async fn journey_passengers(
     let origin = cities.iter().find(|c| c.id == journey.origin_city_id);
     let dest = cities.iter().find(|c| c.id == journey.destination_city_id);
 
-    // Get all bookings for this journey
+    // Get the confirmed bookings for this journey; waitlisted passengers don't ride
     let bookings = booking::Entity::find()
         .filter(booking::Column::JourneyId.eq(journey_id))
+        .filter(booking::Column::Status.eq(BookingStatus::Confirmed))
         .all(&state.db)
         .await?;
 
     // Get user info for each booking
     use crate::entities::user;
-    let users = user::Entity::find().all(&state.db).await?;
+    let passenger_ids: Vec<Uuid> = bookings.iter().map(|b| b.user_id).collect();
+    let users = user::Entity::find()
+        .filter(user::Column::Id.is_in(passenger_ids))
+        .all(&state.db)
+        .await?;
 
     let passengers: Vec<PassengerPickupInfo> = bookings
         .into_iter()
@@ -129,6 +348,50 @@ pub async fn journey_passengers(
         origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
         destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
         departure_time: journey.departure_time.with_timezone(&Utc),
+        departure_in_minutes: (journey.departure_time.with_timezone(&Utc) - now)
+            .num_seconds()
+            .div_euclid(60),
+        clusters: query
+            .cluster_radius_km
+            .map(|radius| cluster_passengers(&passengers, radius)),
         passengers,
     }))
 }
+
+/// Mark an assigned journey as completed
+pub async fn complete_journey(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(journey_id): Path<Uuid>,
+) -> AppResult<Json<journey::Model>> {
+    let journey = journey::Entity::find_by_id(journey_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    if journey.driver_id != Some(claims.sub) {
+        return Err(AppError::Forbidden(
+            "You are not assigned to this journey".to_string(),
+        ));
+    }
+
+    match journey.status {
+        JourneyStatus::Scheduled | JourneyStatus::InProgress => {}
+        JourneyStatus::Completed => {
+            return Err(AppError::Conflict(
+                "Journey is already completed".to_string(),
+            ));
+        }
+        JourneyStatus::Cancelled => {
+            return Err(AppError::Conflict(
+                "Cannot complete a cancelled journey".to_string(),
+            ));
+        }
+    }
+
+    let mut active: journey::ActiveModel = journey.into();
+    active.status = Set(JourneyStatus::Completed);
+
+    let result = active.update(&state.db).await?;
+    Ok(Json(result))
+}
@@ -0,0 +1,24 @@
+use axum::{Json, extract::State, http::StatusCode};
+use sea_orm::ConnectionTrait;
+use serde_json::{Value, json};
+
+use crate::AppState;
+
+/// Liveness probe: the process is up and serving requests
+pub async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+/// Readiness probe: the database answers a trivial query
+pub async fn ready(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    match state.db.execute_unprepared("SELECT 1").await {
+        Ok(_) => (StatusCode::OK, Json(json!({ "status": "ok" }))),
+        Err(e) => {
+            tracing::warn!("Readiness check failed: {:?}", e);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({ "status": "db_unavailable" })),
+            )
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use axum::{http::header, response::IntoResponse};
+
+use crate::middleware::metrics::prometheus_handle;
+
+/// Request counters and latency histograms in the Prometheus text format
+pub async fn metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        prometheus_handle().render(),
+    )
+}
@@ -1,4 +1,7 @@
 pub mod admin;
 pub mod auth;
+pub mod docs;
 pub mod driver;
+pub mod health;
+pub mod metrics;
 pub mod traveller;
@@ -1,52 +1,296 @@
+use std::collections::BTreeMap;
+
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderName, StatusCode, header},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
-use chrono::{DateTime, Utc};
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, EntityTrait, FromQueryResult,
+    JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait,
+    Select, Set, TransactionTrait,
+    prelude::DateTimeWithTimeZone,
+    sea_query::{Alias, Expr, extension::postgres::PgExpr},
+};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use crate::entities::{booking, city, journey};
-use crate::error::{AppError, AppResult};
-use crate::utils::geo::is_within_radius;
+use crate::db::idempotency::{
+    find_idempotent_booking, forget_idempotency_keys, remember_idempotency_key,
+};
+use crate::db::holds::{SEAT_HOLD_MINUTES, held_seats, held_seats_by_journey};
+use crate::db::{
+    booked_seats_by_journey, has_free_seats, insert_booking, promote_waitlisted, seat_counts,
+    stops_by_journey,
+};
+use crate::entities::booking::BookingStatus;
+use crate::entities::journey::JourneyStatus;
+use crate::entities::{booking, city, journey, journey_stop, seat_hold, user};
+use crate::error::{AppError, AppResult, ErrorResponse};
+use crate::utils::etag::{booking_etag, check_if_match, if_none_match, weak_etag};
+use crate::utils::geo::{self, is_within_radius, point_in_polygon};
 use crate::utils::jwt::Claims;
+use crate::utils::pagination::{Paginated, Pagination};
+use crate::utils::reference::{generate_reference_code, normalize_reference_code};
+use crate::utils::time::to_local_time;
+use crate::utils::validation::validate_coordinates;
 use crate::AppState;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct AvailableJourneyResponse {
     pub id: Uuid,
     pub origin_city: CityInfo,
     pub destination_city: CityInfo,
     pub departure_time: DateTime<Utc>,
+    /// `departure_time` in the origin city's timezone
+    pub departure_time_local: DateTime<FixedOffset>,
+    pub total_seats: i32,
     pub available_seats: i32,
+    /// No seats left; new bookings go on the waitlist
+    pub is_sold_out: bool,
+    pub base_fare: i64,
     pub has_driver: bool,
+    /// Name of the assigned driver; contact details are only shown to passengers
+    pub driver_name: Option<String>,
+    /// Intermediate stops in route order
+    pub stops: Vec<JourneyStopInfo>,
+}
+
+/// An intermediate city on a journey's route
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JourneyStopInfo {
+    /// Position along the route, from 1
+    pub stop_order: i32,
+    pub city_id: i32,
+    pub city_name: String,
+    pub scheduled_time: DateTime<Utc>,
+    /// `scheduled_time` in the stop city's timezone
+    pub scheduled_time_local: DateTime<FixedOffset>,
+}
+
+/// Stops as shown to clients, resolving each stop's city from `cities`
+pub fn stop_infos(stops: &[journey_stop::Model], cities: &[city::Model]) -> Vec<JourneyStopInfo> {
+    stops
+        .iter()
+        .map(|stop| {
+            let city = cities.iter().find(|c| c.id == stop.city_id);
+            let scheduled_time = stop.scheduled_time.with_timezone(&Utc);
+            JourneyStopInfo {
+                stop_order: stop.stop_order,
+                city_id: stop.city_id,
+                city_name: city.map(|c| c.name.clone()).unwrap_or_default(),
+                scheduled_time,
+                scheduled_time_local: to_local_time(
+                    scheduled_time,
+                    city.map_or("UTC", |c| c.timezone.as_str()),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Live seat counts for a journey, without the journey details
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JourneyAvailability {
+    pub journey_id: Uuid,
+    pub total_seats: i32,
+    /// Seats in confirmed bookings
+    pub booked_seats: i32,
+    /// Seats neither booked nor held at checkout
+    pub available_seats: i32,
+    pub is_sold_out: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CityInfo {
     pub id: i32,
     pub name: String,
     pub center_lat: f64,
     pub center_lng: f64,
     pub pickup_radius_km: f64,
+    pub timezone: String,
+    /// `[lat, lng]` vertices of the pickup zone, which replaces the radius when present
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Vec<Vec<f64>>>)]
+    pub pickup_polygon: Option<Vec<[f64; 2]>>,
+}
+
+/// Optional filters for the public journey list
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JourneyFilter {
+    pub origin_city_id: Option<i32>,
+    pub destination_city_id: Option<i32>,
+    /// Calendar day (UTC) of departure
+    pub departure_date: Option<NaiveDate>,
+    /// Earliest departure (RFC 3339), inclusive
+    pub departure_from: Option<DateTime<Utc>>,
+    /// Latest departure (RFC 3339), inclusive
+    pub departure_to: Option<DateTime<Utc>>,
+    /// Also list journeys with no seats left (hidden by default)
+    #[serde(default)]
+    pub include_sold_out: bool,
+    /// Text the origin or destination city name must contain, ignoring case
+    pub q: Option<String>,
+}
+
+/// `ILIKE` pattern matching names that contain `q`, with `%`, `_` and `\` taken literally.
+/// `None` for a blank query.
+fn contains_pattern(q: &str) -> Option<String> {
+    let q = q.trim();
+    if q.is_empty() {
+        return None;
+    }
+
+    let mut pattern = String::with_capacity(q.len() + 2);
+    pattern.push('%');
+    for c in q.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    Some(pattern)
 }
 
 /// List available journeys for booking
+///
+/// Carries a weak ETag of the page; a matching `If-None-Match` gets an empty 304.
+#[utoipa::path(
+    get,
+    path = "/api/journeys",
+    tag = "journeys",
+    params(
+        Pagination,
+        JourneyFilter,
+        (
+            "If-None-Match" = Option<String>,
+            Header,
+            description = "ETag from an earlier listing; answered with 304 if unchanged",
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            body = Paginated<AvailableJourneyResponse>,
+            headers(("ETag" = String))
+        ),
+        (status = 304, description = "Listing unchanged since the given ETag"),
+        (
+            status = 400,
+            description = "Departure window ends before it starts",
+            body = ErrorResponse
+        ),
+    )
+)]
 pub async fn list_journeys(
     State(state): State<AppState>,
-) -> AppResult<Json<Vec<AvailableJourneyResponse>>> {
-    let now = Utc::now();
-    let journeys = journey::Entity::find().all(&state.db).await?;
+    Query(pagination): Query<Pagination>,
+    Query(filter): Query<JourneyFilter>,
+    headers: HeaderMap,
+) -> AppResult<Response> {
+    let page = available_journeys(&state, &pagination, &filter).await?;
+
+    // Serialize once so the ETag covers exactly the bytes sent
+    let body = serde_json::to_vec(&page).map_err(|e| AppError::Internal(e.to_string()))?;
+    let etag = weak_etag(&body);
+    if if_none_match(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (header::ETAG, etag),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// One page of bookable journeys matching `filter`, as served by `list_journeys`
+pub async fn available_journeys(
+    state: &AppState,
+    pagination: &Pagination,
+    filter: &JourneyFilter,
+) -> AppResult<Paginated<AvailableJourneyResponse>> {
+    if let (Some(from), Some(to)) = (filter.departure_from, filter.departure_to)
+        && from > to
+    {
+        return Err(AppError::BadRequest(
+            "departure_from must not be after departure_to".to_string(),
+        ));
+    }
+
+    // Only future journeys still running to schedule, soonest first
+    let paginator = journey::Entity::find()
+        .filter(journey::Column::DepartureTime.gte(Utc::now()))
+        .filter(journey::Column::Status.eq(JourneyStatus::Scheduled))
+        .apply_if(filter.origin_city_id, |q, id| {
+            q.filter(journey::Column::OriginCityId.eq(id))
+        })
+        .apply_if(filter.destination_city_id, |q, id| {
+            q.filter(journey::Column::DestinationCityId.eq(id))
+        })
+        .apply_if(filter.departure_date, |q, date| {
+            let day_start = date.and_time(NaiveTime::MIN).and_utc();
+            q.filter(journey::Column::DepartureTime.gte(day_start))
+                .filter(journey::Column::DepartureTime.lt(day_start + Duration::days(1)))
+        })
+        .apply_if(filter.departure_from, |q, from| {
+            q.filter(journey::Column::DepartureTime.gte(from))
+        })
+        .apply_if(filter.departure_to, |q, to| {
+            q.filter(journey::Column::DepartureTime.lte(to))
+        })
+        .apply_if(
+            (!filter.include_sold_out).then(has_free_seats),
+            |q, free| q.filter(free),
+        )
+        .apply_if(
+            filter.q.as_deref().and_then(contains_pattern),
+            |q, pattern| {
+                // The city table appears twice, once per end of the journey
+                let origin = Alias::new("origin_city");
+                let destination = Alias::new("destination_city");
+                q.join_as(
+                    JoinType::InnerJoin,
+                    journey::Relation::OriginCity.def(),
+                    origin.clone(),
+                )
+                .join_as(
+                    JoinType::InnerJoin,
+                    journey::Relation::DestinationCity.def(),
+                    destination.clone(),
+                )
+                .filter(
+                    Condition::any()
+                        .add(Expr::col((origin, city::Column::Name)).ilike(pattern.clone()))
+                        .add(Expr::col((destination, city::Column::Name)).ilike(pattern)),
+                )
+            },
+        )
+        .order_by_asc(journey::Column::DepartureTime)
+        .paginate(&state.db, pagination.per_page());
+    let total = paginator.num_items().await?;
+    let journeys = paginator.fetch_page(pagination.page() - 1).await?;
+    let ids: Vec<Uuid> = journeys.iter().map(|j| j.id).collect();
+    let booked_by_journey = booked_seats_by_journey(&state.db, &ids).await?;
+    let held_by_journey = held_seats_by_journey(&state.db, &ids).await?;
+    let stops = stops_by_journey(&state.db, &ids).await?;
     let cities = city::Entity::find().all(&state.db).await?;
+    let driver_ids: Vec<Uuid> = journeys.iter().filter_map(|j| j.driver_id).collect();
+    let drivers = user::Entity::find_active()
+        .filter(user::Column::Id.is_in(driver_ids))
+        .all(&state.db)
+        .await?;
 
     let mut responses = Vec::new();
     for j in journeys {
-        // Skip past journeys
-        if j.departure_time.with_timezone(&Utc) < now {
-            continue;
-        }
-
         let origin = cities.iter().find(|c| c.id == j.origin_city_id);
         let dest = cities.iter().find(|c| c.id == j.destination_city_id);
 
@@ -57,15 +301,10 @@ pub async fn list_journeys(
         let origin = origin.unwrap();
         let dest = dest.unwrap();
 
-        let booked: i32 = booking::Entity::find()
-            .filter(booking::Column::JourneyId.eq(j.id))
-            .all(&state.db)
-            .await?
-            .iter()
-            .map(|b| b.seats)
-            .sum();
+        let booked = booked_by_journey.get(&j.id).copied().unwrap_or(0);
+        let held = held_by_journey.get(&j.id).copied().unwrap_or(0);
 
-        let available = j.total_seats - booked;
+        let available = (j.total_seats - booked - held).max(0);
 
         responses.push(AvailableJourneyResponse {
             id: j.id,
@@ -75,6 +314,8 @@ pub async fn list_journeys(
                 center_lat: origin.center_lat,
                 center_lng: origin.center_lng,
                 pickup_radius_km: origin.pickup_radius_km,
+                timezone: origin.timezone.clone(),
+                pickup_polygon: origin.pickup_polygon.clone().map(|p| p.0),
             },
             destination_city: CityInfo {
                 id: dest.id,
@@ -82,17 +323,43 @@ pub async fn list_journeys(
                 center_lat: dest.center_lat,
                 center_lng: dest.center_lng,
                 pickup_radius_km: dest.pickup_radius_km,
+                timezone: dest.timezone.clone(),
+                pickup_polygon: dest.pickup_polygon.clone().map(|p| p.0),
             },
             departure_time: j.departure_time.with_timezone(&Utc),
+            departure_time_local: local_departure(&j, Some(origin)),
+            total_seats: j.total_seats,
             available_seats: available,
+            is_sold_out: available <= 0,
+            base_fare: j.base_fare,
             has_driver: j.driver_id.is_some(),
+            driver_name: j
+                .driver_id
+                .and_then(|id| drivers.iter().find(|d| d.id == id))
+                .map(|d| d.name.clone()),
+            stops: stop_infos(stops.get(&j.id).map_or(&[], Vec::as_slice), &cities),
         });
     }
 
-    Ok(Json(responses))
+    Ok(Paginated {
+        items: responses,
+        total,
+        page: pagination.page(),
+        per_page: pagination.per_page(),
+    })
 }
 
 /// Get journey details
+#[utoipa::path(
+    get,
+    path = "/api/journeys/{id}",
+    tag = "journeys",
+    params(("id" = Uuid, Path, description = "Journey ID")),
+    responses(
+        (status = 200, body = AvailableJourneyResponse),
+        (status = 404, description = "Journey not found", body = ErrorResponse),
+    )
+)]
 pub async fn get_journey(
     State(state): State<AppState>,
     Path(journey_id): Path<Uuid>,
@@ -114,11 +381,24 @@ pub async fn get_journey(
 
     let booked: i32 = booking::Entity::find()
         .filter(booking::Column::JourneyId.eq(journey.id))
+        .filter(booking::Column::Status.eq(BookingStatus::Confirmed))
         .all(&state.db)
         .await?
         .iter()
         .map(|b| b.seats)
         .sum();
+    let held = held_seats(&state.db, journey.id, None).await?;
+    let available = (journey.total_seats - booked - held).max(0);
+
+    let driver = match journey.driver_id {
+        Some(id) => user::Entity::find_active_by_id(id).one(&state.db).await?,
+        None => None,
+    };
+    let stops = journey_stop::Entity::find()
+        .filter(journey_stop::Column::JourneyId.eq(journey.id))
+        .order_by_asc(journey_stop::Column::StopOrder)
+        .all(&state.db)
+        .await?;
 
     Ok(Json(AvailableJourneyResponse {
         id: journey.id,
@@ -128,6 +408,8 @@ pub async fn get_journey(
             center_lat: origin.center_lat,
             center_lng: origin.center_lng,
             pickup_radius_km: origin.pickup_radius_km,
+            timezone: origin.timezone.clone(),
+            pickup_polygon: origin.pickup_polygon.clone().map(|p| p.0),
         },
         destination_city: CityInfo {
             id: dest.id,
@@ -135,177 +417,1031 @@ pub async fn get_journey(
             center_lat: dest.center_lat,
             center_lng: dest.center_lng,
             pickup_radius_km: dest.pickup_radius_km,
+            timezone: dest.timezone.clone(),
+            pickup_polygon: dest.pickup_polygon.clone().map(|p| p.0),
         },
         departure_time: journey.departure_time.with_timezone(&Utc),
-        available_seats: journey.total_seats - booked,
+        departure_time_local: local_departure(&journey, Some(origin)),
+        total_seats: journey.total_seats,
+        available_seats: available,
+        is_sold_out: available <= 0,
+        base_fare: journey.base_fare,
         has_driver: journey.driver_id.is_some(),
+        driver_name: driver.map(|d| d.name),
+        stops: stop_infos(&stops, &cities),
+    }))
+}
+
+/// Seat counts for a journey, for clients polling availability
+#[utoipa::path(
+    get,
+    path = "/api/journeys/{id}/availability",
+    tag = "journeys",
+    params(("id" = Uuid, Path, description = "Journey ID")),
+    responses(
+        (status = 200, body = JourneyAvailability),
+        (status = 404, description = "Journey not found", body = ErrorResponse),
+    )
+)]
+pub async fn journey_availability(
+    State(state): State<AppState>,
+    Path(journey_id): Path<Uuid>,
+) -> AppResult<Json<JourneyAvailability>> {
+    let counts = seat_counts(&state.db, journey_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+    let available = counts.available();
+
+    Ok(Json(JourneyAvailability {
+        journey_id,
+        total_seats: counts.total_seats,
+        booked_seats: counts.booked as i32,
+        available_seats: available,
+        is_sold_out: available == 0,
+    }))
+}
+
+// ============ City Lookup ============
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CityListQuery {
+    /// Include how many upcoming journeys start and end in each city
+    #[serde(default)]
+    pub with_journey_counts: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CityListItem {
+    #[serde(flatten)]
+    pub city: CityInfo,
+    /// Scheduled, not yet departed journeys leaving this city (with `with_journey_counts`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upcoming_origin_count: Option<i64>,
+    /// Scheduled, not yet departed journeys arriving in this city (with `with_journey_counts`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upcoming_destination_count: Option<i64>,
+}
+
+/// Number of upcoming scheduled journeys per city, grouped on `column`
+async fn upcoming_journeys_per_city(
+    db: &sea_orm::DatabaseConnection,
+    column: journey::Column,
+) -> AppResult<BTreeMap<i32, i64>> {
+    let rows: Vec<(i32, i64)> = journey::Entity::find()
+        .select_only()
+        .column(column)
+        .column_as(journey::Column::Id.count(), "count")
+        .filter(journey::Column::Status.eq(JourneyStatus::Scheduled))
+        .filter(journey::Column::DepartureTime.gt(Utc::now()))
+        .group_by(column)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok(rows.into_iter().collect())
+}
+
+/// List all cities, e.g. to pick an origin and destination
+#[utoipa::path(
+    get,
+    path = "/api/cities",
+    tag = "journeys",
+    params(CityListQuery),
+    responses((status = 200, body = [CityListItem]))
+)]
+pub async fn list_cities(
+    State(state): State<AppState>,
+    Query(query): Query<CityListQuery>,
+) -> AppResult<Json<Vec<CityListItem>>> {
+    let cities = city::Entity::find()
+        .order_by_asc(city::Column::Name)
+        .all(&state.db)
+        .await?;
+
+    let counts = if query.with_journey_counts {
+        Some((
+            upcoming_journeys_per_city(&state.db, journey::Column::OriginCityId).await?,
+            upcoming_journeys_per_city(&state.db, journey::Column::DestinationCityId).await?,
+        ))
+    } else {
+        None
+    };
+
+    let items = cities
+        .into_iter()
+        .map(|c| CityListItem {
+            upcoming_origin_count: counts
+                .as_ref()
+                .map(|(origin, _)| origin.get(&c.id).copied().unwrap_or(0)),
+            upcoming_destination_count: counts
+                .as_ref()
+                .map(|(_, destination)| destination.get(&c.id).copied().unwrap_or(0)),
+            city: CityInfo {
+                id: c.id,
+                name: c.name,
+                center_lat: c.center_lat,
+                center_lng: c.center_lng,
+                pickup_radius_km: c.pickup_radius_km,
+                timezone: c.timezone,
+                pickup_polygon: c.pickup_polygon.map(|p| p.0),
+            },
+        })
+        .collect();
+
+    Ok(Json(items))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NearestCityQuery {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NearestCityResponse {
+    pub city: CityInfo,
+    pub distance_km: f64,
+    /// Whether the point is in this city's pickup zone (its polygon if set, otherwise its radius)
+    pub within_pickup_radius: bool,
+}
+
+/// Find the city closest to a coordinate, e.g. to pre-select the pickup city
+#[utoipa::path(
+    get,
+    path = "/api/cities/nearest",
+    tag = "journeys",
+    params(NearestCityQuery),
+    responses(
+        (status = 200, body = NearestCityResponse),
+        (status = 400, description = "Invalid coordinates", body = ErrorResponse),
+        (status = 404, description = "No cities configured", body = ErrorResponse),
+    )
+)]
+pub async fn nearest_city(
+    State(state): State<AppState>,
+    Query(query): Query<NearestCityQuery>,
+) -> AppResult<Json<NearestCityResponse>> {
+    validate_coordinates(query.lat, query.lng)?;
+
+    let cities = city::Entity::find().all(&state.db).await?;
+    let (city, distance_km) = geo::nearest_city(query.lat, query.lng, &cities)
+        .ok_or_else(|| AppError::NotFound("No cities configured".to_string()))?;
+
+    Ok(Json(NearestCityResponse {
+        city: CityInfo {
+            id: city.id,
+            name: city.name.clone(),
+            center_lat: city.center_lat,
+            center_lng: city.center_lng,
+            pickup_radius_km: city.pickup_radius_km,
+            timezone: city.timezone.clone(),
+            pickup_polygon: city.pickup_polygon.clone().map(|p| p.0),
+        },
+        distance_km,
+        within_pickup_radius: in_pickup_zone(query.lat, query.lng, city),
     }))
 }
 
 // ============ Booking Management ============
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateBookingRequest {
     pub journey_id: Uuid,
     pub seats: i32,
     pub pickup_lat: f64,
     pub pickup_lng: f64,
+    /// Join the waitlist instead of failing when the journey is full
+    #[serde(default)]
+    pub waitlist: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BookingResponse {
     pub id: Uuid,
+    /// Short code to quote instead of the id, e.g. `7K3QZ9`
+    pub reference_code: String,
     pub journey_id: Uuid,
     pub origin_city: String,
     pub destination_city: String,
     pub departure_time: DateTime<Utc>,
+    /// `departure_time` in the origin city's timezone
+    pub departure_time_local: DateTime<FixedOffset>,
     pub seats: i32,
     pub pickup_lat: f64,
     pub pickup_lng: f64,
+    pub total_fare: i64,
+    pub status: BookingStatus,
+    /// Whether the booking was cancelled; cancelled bookings hold no seats
+    pub cancelled: bool,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub journey_status: JourneyStatus,
     pub created_at: DateTime<Utc>,
 }
 
-/// Create a booking
+/// A booking as listed in `my_bookings`, with how full its journey is
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MyBookingResponse {
+    #[serde(flatten)]
+    pub booking: BookingResponse,
+    pub journey_total_seats: i32,
+    /// Seats nobody has booked or is holding, across all travellers
+    pub journey_available_seats: i32,
+}
+
+/// A journey's departure in its origin city's timezone (UTC if the city is missing)
+pub fn local_departure(
+    journey: &journey::Model,
+    origin: Option<&city::Model>,
+) -> DateTime<FixedOffset> {
+    let timezone = origin.map_or("UTC", |c| c.timezone.as_str());
+    to_local_time(journey.departure_time.with_timezone(&Utc), timezone)
+}
+
+/// Total price in cents for `seats` seats at `base_fare` each
+pub fn calculate_fare(base_fare: i64, seats: i32) -> AppResult<i64> {
+    base_fare
+        .checked_mul(i64::from(seats))
+        .ok_or_else(|| AppError::BadRequest("Fare is too large".to_string()))
+}
+
+/// Whether a point lies in a city's pickup zone: its polygon if it has one, otherwise its radius
+pub fn in_pickup_zone(lat: f64, lng: f64, city: &city::Model) -> bool {
+    match &city.pickup_polygon {
+        Some(polygon) => point_in_polygon(lat, lng, &polygon.points()),
+        None => is_within_radius(
+            lat,
+            lng,
+            city.center_lat,
+            city.center_lng,
+            city.pickup_radius_km,
+        ),
+    }
+}
+
+/// A pickup must lie within the origin city's pickup zone (its polygon if it has one,
+/// otherwise its radius) and, where the two cities' areas overlap, on the origin side:
+/// no closer to the destination center than to the origin's
+pub fn validate_pickup_point(
+    lat: f64,
+    lng: f64,
+    origin: &city::Model,
+    dest: &city::Model,
+) -> AppResult<()> {
+    if !in_pickup_zone(lat, lng, origin) {
+        return Err(AppError::BadRequest(match origin.pickup_polygon {
+            Some(_) => format!(
+                "Pickup point must be within the {} pickup zone",
+                origin.name
+            ),
+            None => format!(
+                "Pickup point must be within {} km of {} city center",
+                origin.pickup_radius_km, origin.name
+            ),
+        }));
+    }
+
+    let to_origin = geo::haversine_distance(lat, lng, origin.center_lat, origin.center_lng);
+    let to_dest = geo::haversine_distance(lat, lng, dest.center_lat, dest.center_lng);
+    if to_dest < to_origin {
+        return Err(AppError::BadRequest(format!(
+            "Pickup point is closer to {} than to {}",
+            dest.name, origin.name
+        )));
+    }
+
+    Ok(())
+}
+
+/// A traveller may have at most `max` bookings (confirmed or waitlisted) on one journey
+async fn ensure_booking_allowance<C: ConnectionTrait>(
+    db: &C,
+    journey_id: Uuid,
+    user_id: Uuid,
+    max: u32,
+) -> AppResult<()> {
+    let existing = booking::Entity::find()
+        .filter(booking::Column::JourneyId.eq(journey_id))
+        .filter(booking::Column::UserId.eq(user_id))
+        .filter(booking::Column::Status.ne(BookingStatus::Cancelled))
+        .count(db)
+        .await?;
+
+    if existing >= u64::from(max) {
+        return Err(AppError::Conflict(if max == 1 {
+            "You already have a booking for this journey".to_string()
+        } else {
+            format!("You already have {} bookings for this journey", existing)
+        }));
+    }
+
+    Ok(())
+}
+
+/// A booking (or hold) takes between 1 and `max` seats
+fn validate_seat_count(seats: i32, max: i32) -> AppResult<()> {
+    if seats <= 0 {
+        return Err(AppError::BadRequest(
+            "Must book at least 1 seat".to_string(),
+        ));
+    }
+
+    if seats > max {
+        return Err(AppError::BadRequest(format!(
+            "Cannot book more than {} seats at once",
+            max
+        )));
+    }
+
+    Ok(())
+}
+
+/// The journey is still scheduled, in the future and before its booking close time
+fn ensure_open_for_booking(journey: &journey::Model) -> AppResult<()> {
+    if journey.departure_time.with_timezone(&Utc) < Utc::now() {
+        return Err(AppError::BadRequest("Cannot book past journeys".to_string()));
+    }
+
+    if journey.status != JourneyStatus::Scheduled {
+        return Err(AppError::BadRequest(
+            "Journey is no longer open for booking".to_string(),
+        ));
+    }
+
+    let closes_at = journey.departure_time.with_timezone(&Utc)
+        - Duration::minutes(journey.booking_close_minutes.into());
+    if Utc::now() > closes_at {
+        return Err(AppError::BadRequest(format!(
+            "Booking closes {} minute(s) before departure",
+            journey.booking_close_minutes
+        )));
+    }
+
+    Ok(())
+}
+
+/// Seats held by confirmed bookings on a journey
+async fn confirmed_seats<C: ConnectionTrait>(db: &C, journey_id: Uuid) -> AppResult<i32> {
+    Ok(booking::Entity::find()
+        .filter(booking::Column::JourneyId.eq(journey_id))
+        .filter(booking::Column::Status.eq(BookingStatus::Confirmed))
+        .all(db)
+        .await?
+        .iter()
+        .map(|b| b.seats)
+        .sum())
+}
+
+/// Whether a booking on a journey departing at `departure` may still be cancelled at `now`
+pub fn cancellation_allowed(
+    departure: DateTime<Utc>,
+    now: DateTime<Utc>,
+    cutoff: Duration,
+) -> bool {
+    departure - now >= cutoff
+}
+
+/// Longest `Idempotency-Key` accepted
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// The optional `Idempotency-Key` header: 1 to 255 printable ASCII characters
+fn idempotency_key(headers: &HeaderMap) -> AppResult<Option<String>> {
+    let Some(value) = headers.get("idempotency-key") else {
+        return Ok(None);
+    };
+
+    match value.to_str().map(str::trim) {
+        Ok(key)
+            if !key.is_empty()
+                && key.len() <= MAX_IDEMPOTENCY_KEY_LEN
+                && key.chars().all(|c| c.is_ascii_graphic()) =>
+        {
+            Ok(Some(key.to_string()))
+        }
+        _ => Err(AppError::BadRequest(format!(
+            "Idempotency-Key must be 1 to {} printable characters without spaces",
+            MAX_IDEMPOTENCY_KEY_LEN
+        ))),
+    }
+}
+
+/// Response body for a booking on `journey`
+fn booking_response(
+    booking: booking::Model,
+    journey: &journey::Model,
+    cities: &[city::Model],
+) -> BookingResponse {
+    let origin = cities.iter().find(|c| c.id == journey.origin_city_id);
+    let dest = cities.iter().find(|c| c.id == journey.destination_city_id);
+
+    BookingResponse {
+        id: booking.id,
+        reference_code: booking.reference_code,
+        journey_id: journey.id,
+        origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
+        destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
+        departure_time: journey.departure_time.with_timezone(&Utc),
+        departure_time_local: local_departure(journey, origin),
+        seats: booking.seats,
+        pickup_lat: booking.pickup_lat,
+        pickup_lng: booking.pickup_lng,
+        total_fare: booking.total_fare,
+        status: booking.status,
+        cancelled: booking.status == BookingStatus::Cancelled,
+        cancelled_at: booking.cancelled_at.map(|t| t.with_timezone(&Utc)),
+        journey_status: journey.status,
+        created_at: booking.created_at.with_timezone(&Utc),
+    }
+}
+
+/// Create a booking. With an `Idempotency-Key` header, retries within 24 hours return
+/// the booking the first request created instead of booking again.
+#[utoipa::path(
+    post,
+    path = "/api/bookings",
+    tag = "bookings",
+    request_body = CreateBookingRequest,
+    params(
+        (
+            "Idempotency-Key" = Option<String>,
+            Header,
+            description = "Replays the original booking when retried within 24 hours",
+        ),
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = BookingResponse, headers(("ETag" = String))),
+        (
+            status = 400,
+            description = "Invalid seats or pickup point, or journey not bookable",
+            body = ErrorResponse,
+        ),
+        (status = 404, description = "Journey not found", body = ErrorResponse),
+        (
+            status = 409,
+            description = "Not enough seats, or the idempotency key is in use",
+            body = ErrorResponse,
+        ),
+    )
+)]
 pub async fn create_booking(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
+    headers: HeaderMap,
     Json(payload): Json<CreateBookingRequest>,
-) -> AppResult<Json<BookingResponse>> {
+) -> AppResult<([(HeaderName, String); 1], Json<BookingResponse>)> {
+    let key = idempotency_key(&headers)?;
+    if let Some(key) = &key
+        && let Some(booking_id) = find_idempotent_booking(&state.db, claims.sub, key).await?
+    {
+        return replay_booking(&state, booking_id).await;
+    }
+
+    validate_seat_count(payload.seats, state.config.max_seats_per_booking)?;
+    validate_coordinates(payload.pickup_lat, payload.pickup_lng)?;
+
+    // Seat check and insert run in one transaction. Locking the journey row
+    // serializes concurrent bookings so the last seats can't be sold twice.
+    let txn = state.db.begin().await?;
+
     // Validate journey
     let journey = journey::Entity::find_by_id(payload.journey_id)
-        .one(&state.db)
+        .lock_exclusive()
+        .one(&txn)
         .await?
         .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
 
-    // Check journey is in the future
-    if journey.departure_time.with_timezone(&Utc) < Utc::now() {
-        return Err(AppError::BadRequest("Cannot book past journeys".to_string()));
-    }
+    ensure_open_for_booking(&journey)?;
 
-    // Check seat availability
-    let booked: i32 = booking::Entity::find()
-        .filter(booking::Column::JourneyId.eq(journey.id))
-        .all(&state.db)
-        .await?
-        .iter()
-        .map(|b| b.seats)
-        .sum();
+    // Booking directly replaces the caller's own checkout hold, as holding again does
+    seat_hold::Entity::delete_many()
+        .filter(seat_hold::Column::JourneyId.eq(journey.id))
+        .filter(seat_hold::Column::UserId.eq(claims.sub))
+        .exec(&txn)
+        .await?;
 
-    let available = journey.total_seats - booked;
-    if payload.seats > available {
-        return Err(AppError::BadRequest(format!(
+    // Check seat availability; other travellers' checkout holds are not for sale
+    let booked = confirmed_seats(&txn, journey.id).await?;
+    let held = held_seats(&txn, journey.id, None).await?;
+
+    let available = journey.total_seats - booked - held;
+    let status = if payload.seats <= available {
+        BookingStatus::Confirmed
+    } else if payload.waitlist {
+        BookingStatus::Waitlisted
+    } else {
+        return Err(AppError::Conflict(format!(
             "Only {} seats available",
-            available
+            available.max(0)
         )));
-    }
-
-    if payload.seats <= 0 {
-        return Err(AppError::BadRequest(
-            "Must book at least 1 seat".to_string(),
-        ));
-    }
+    };
 
     // Validate pickup point is within origin city radius
     let origin_city = city::Entity::find_by_id(journey.origin_city_id)
-        .one(&state.db)
+        .one(&txn)
         .await?
         .ok_or_else(|| AppError::Internal("Origin city not found".to_string()))?;
+    let dest_city = city::Entity::find_by_id(journey.destination_city_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::Internal("Destination city not found".to_string()))?;
 
-    if !is_within_radius(
+    validate_pickup_point(
         payload.pickup_lat,
         payload.pickup_lng,
-        origin_city.center_lat,
-        origin_city.center_lng,
-        origin_city.pickup_radius_km,
-    ) {
-        return Err(AppError::BadRequest(format!(
-            "Pickup point must be within {} km of {} city center",
-            origin_city.pickup_radius_km, origin_city.name
-        )));
+        &origin_city,
+        &dest_city,
+    )?;
+
+    // Check the user hasn't used up their bookings for this journey
+    ensure_booking_allowance(
+        &txn,
+        journey.id,
+        claims.sub,
+        state.config.max_bookings_per_user_per_journey,
+    )
+    .await?;
+
+    // Create booking
+    let booking_id = Uuid::new_v4();
+    let new_booking = booking::ActiveModel {
+        id: Set(booking_id),
+        journey_id: Set(journey.id),
+        user_id: Set(claims.sub),
+        seats: Set(payload.seats),
+        pickup_lat: Set(payload.pickup_lat),
+        pickup_lng: Set(payload.pickup_lng),
+        total_fare: Set(calculate_fare(journey.base_fare, payload.seats)?),
+        status: Set(status),
+        ..Default::default()
+    };
+
+    let booking = insert_booking(&txn, new_booking, generate_reference_code).await?;
+    if let Some(key) = &key {
+        remember_idempotency_key(&txn, claims.sub, key, booking.id).await?;
     }
+    txn.commit().await?;
 
-    // Check if user already has a booking for this journey
-    let existing = booking::Entity::find()
-        .filter(booking::Column::JourneyId.eq(journey.id))
-        .filter(booking::Column::UserId.eq(claims.sub))
+    let cities = city::Entity::find().all(&state.db).await?;
+    let etag = booking_etag(&booking);
+    let response = booking_response(booking, &journey, &cities);
+
+    state
+        .notifier
+        .booking_confirmed(&claims.email, &response)
+        .await;
+
+    Ok(([(header::ETAG, etag)], Json(response)))
+}
+
+/// The current state of a booking created by an earlier request with the same key
+async fn replay_booking(
+    state: &AppState,
+    booking_id: Uuid,
+) -> AppResult<([(HeaderName, String); 1], Json<BookingResponse>)> {
+    let booking = booking::Entity::find_by_id(booking_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Booking not found".to_string()))?;
+
+    booking_with_etag(state, booking).await
+}
+
+/// A stored booking as a response, with the ETag conditional updates check against
+async fn booking_with_etag(
+    state: &AppState,
+    booking: booking::Model,
+) -> AppResult<([(HeaderName, String); 1], Json<BookingResponse>)> {
+    let journey = journey::Entity::find_by_id(booking.journey_id)
         .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+    let cities = city::Entity::find().all(&state.db).await?;
+
+    let etag = booking_etag(&booking);
+    Ok((
+        [(header::ETAG, etag)],
+        Json(booking_response(booking, &journey, &cities)),
+    ))
+}
+
+// ============ Seat Holds ============
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct HoldSeatsRequest {
+    pub journey_id: Uuid,
+    pub seats: i32,
+    pub pickup_lat: f64,
+    pub pickup_lng: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SeatHoldResponse {
+    pub id: Uuid,
+    pub journey_id: Uuid,
+    pub seats: i32,
+    pub pickup_lat: f64,
+    pub pickup_lng: f64,
+    /// Confirm before this time or the seats go back on sale
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ConfirmHoldRequest {
+    pub hold_id: Uuid,
+}
+
+/// Reserve seats for a few minutes while the traveller completes checkout.
+/// Holding again on the same journey replaces the earlier hold.
+#[utoipa::path(
+    post,
+    path = "/api/bookings/hold",
+    tag = "bookings",
+    request_body = HoldSeatsRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = SeatHoldResponse),
+        (
+            status = 400,
+            description = "Invalid seats or pickup point, or journey not bookable",
+            body = ErrorResponse,
+        ),
+        (status = 404, description = "Journey not found", body = ErrorResponse),
+        (
+            status = 409,
+            description = "Not enough seats, or already booked on this journey",
+            body = ErrorResponse,
+        ),
+    )
+)]
+pub async fn hold_seats(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<HoldSeatsRequest>,
+) -> AppResult<Json<SeatHoldResponse>> {
+    validate_seat_count(payload.seats, state.config.max_seats_per_booking)?;
+    validate_coordinates(payload.pickup_lat, payload.pickup_lng)?;
+
+    // Same journey lock as create_booking, so holds and bookings can't oversell together
+    let txn = state.db.begin().await?;
+
+    let journey = journey::Entity::find_by_id(payload.journey_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    ensure_open_for_booking(&journey)?;
+
+    let origin_city = city::Entity::find_by_id(journey.origin_city_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::Internal("Origin city not found".to_string()))?;
+    let dest_city = city::Entity::find_by_id(journey.destination_city_id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::Internal("Destination city not found".to_string()))?;
+
+    validate_pickup_point(
+        payload.pickup_lat,
+        payload.pickup_lng,
+        &origin_city,
+        &dest_city,
+    )?;
+
+    ensure_booking_allowance(
+        &txn,
+        journey.id,
+        claims.sub,
+        state.config.max_bookings_per_user_per_journey,
+    )
+    .await?;
+
+    seat_hold::Entity::delete_many()
+        .filter(seat_hold::Column::JourneyId.eq(journey.id))
+        .filter(seat_hold::Column::UserId.eq(claims.sub))
+        .exec(&txn)
         .await?;
 
-    if existing.is_some() {
-        return Err(AppError::Conflict(
-            "You already have a booking for this journey".to_string(),
-        ));
+    let booked = confirmed_seats(&txn, journey.id).await?;
+    let held = held_seats(&txn, journey.id, None).await?;
+    let available = journey.total_seats - booked - held;
+    if payload.seats > available {
+        return Err(AppError::Conflict(format!(
+            "Only {} seats available",
+            available.max(0)
+        )));
     }
 
-    // Create booking
-    let booking_id = Uuid::new_v4();
-    let new_booking = booking::ActiveModel {
-        id: Set(booking_id),
+    let hold = seat_hold::ActiveModel {
+        id: Set(Uuid::new_v4()),
         journey_id: Set(journey.id),
         user_id: Set(claims.sub),
         seats: Set(payload.seats),
         pickup_lat: Set(payload.pickup_lat),
         pickup_lng: Set(payload.pickup_lng),
+        expires_at: Set((Utc::now() + Duration::minutes(SEAT_HOLD_MINUTES)).into()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+
+    Ok(Json(SeatHoldResponse {
+        id: hold.id,
+        journey_id: hold.journey_id,
+        seats: hold.seats,
+        pickup_lat: hold.pickup_lat,
+        pickup_lng: hold.pickup_lng,
+        expires_at: hold.expires_at.with_timezone(&Utc),
+    }))
+}
+
+/// Turn an unexpired seat hold into a confirmed booking
+#[utoipa::path(
+    post,
+    path = "/api/bookings/confirm",
+    tag = "bookings",
+    request_body = ConfirmHoldRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = BookingResponse, headers(("ETag" = String))),
+        (status = 400, description = "Journey no longer bookable", body = ErrorResponse),
+        (status = 404, description = "Seat hold not found", body = ErrorResponse),
+        (
+            status = 409,
+            description = "Hold expired, seats no longer available, or already booked",
+            body = ErrorResponse,
+        ),
+    )
+)]
+pub async fn confirm_hold(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<ConfirmHoldRequest>,
+) -> AppResult<([(HeaderName, String); 1], Json<BookingResponse>)> {
+    let not_found = || AppError::NotFound("Seat hold not found".to_string());
+    let hold = seat_hold::Entity::find_by_id(payload.hold_id)
+        .filter(seat_hold::Column::UserId.eq(claims.sub))
+        .one(&state.db)
+        .await?
+        .ok_or_else(not_found)?;
+
+    let txn = state.db.begin().await?;
+
+    let journey = journey::Entity::find_by_id(hold.journey_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    // Re-read under the journey lock so two confirms can't both use the hold
+    let hold = seat_hold::Entity::find_by_id(hold.id)
+        .one(&txn)
+        .await?
+        .ok_or_else(not_found)?;
+
+    if hold.expires_at.with_timezone(&Utc) <= Utc::now() {
+        return Err(AppError::Conflict("Seat hold has expired".to_string()));
+    }
+
+    ensure_open_for_booking(&journey)?;
+
+    ensure_booking_allowance(
+        &txn,
+        journey.id,
+        claims.sub,
+        state.config.max_bookings_per_user_per_journey,
+    )
+    .await?;
+
+    // The seats were set aside, but an admin may have shrunk the journey since
+    let booked = confirmed_seats(&txn, journey.id).await?;
+    let held_by_others = held_seats(&txn, journey.id, Some(hold.id)).await?;
+    let available = journey.total_seats - booked - held_by_others;
+    if hold.seats > available {
+        return Err(AppError::Conflict(format!(
+            "Only {} seats available",
+            available.max(0)
+        )));
+    }
+
+    seat_hold::Entity::delete_by_id(hold.id).exec(&txn).await?;
+
+    let new_booking = booking::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        journey_id: Set(journey.id),
+        user_id: Set(claims.sub),
+        seats: Set(hold.seats),
+        pickup_lat: Set(hold.pickup_lat),
+        pickup_lng: Set(hold.pickup_lng),
+        total_fare: Set(calculate_fare(journey.base_fare, hold.seats)?),
+        status: Set(BookingStatus::Confirmed),
         ..Default::default()
     };
 
-    let booking = new_booking.insert(&state.db).await?;
+    let booking = insert_booking(&txn, new_booking, generate_reference_code).await?;
+    txn.commit().await?;
 
     let cities = city::Entity::find().all(&state.db).await?;
-    let origin = cities.iter().find(|c| c.id == journey.origin_city_id);
-    let dest = cities.iter().find(|c| c.id == journey.destination_city_id);
+    let etag = booking_etag(&booking);
+    let response = booking_response(booking, &journey, &cities);
 
-    Ok(Json(BookingResponse {
-        id: booking.id,
-        journey_id: journey.id,
-        origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
-        destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
-        departure_time: journey.departure_time.with_timezone(&Utc),
-        seats: booking.seats,
-        pickup_lat: booking.pickup_lat,
-        pickup_lng: booking.pickup_lng,
-        created_at: booking.created_at.with_timezone(&Utc),
+    state
+        .notifier
+        .booking_confirmed(&claims.email, &response)
+        .await;
+
+    Ok(([(header::ETAG, etag)], Json(response)))
+}
+
+/// Fetch one of the caller's bookings
+#[utoipa::path(
+    get,
+    path = "/api/bookings/{id}",
+    tag = "bookings",
+    params(("id" = Uuid, Path, description = "Booking ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = BookingResponse, headers(("ETag" = String))),
+        (status = 404, description = "Booking not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_booking(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(booking_id): Path<Uuid>,
+) -> AppResult<([(HeaderName, String); 1], Json<BookingResponse>)> {
+    // Someone else's booking looks exactly like a missing one, so ids can't be probed
+    let booking = booking::Entity::find_by_id(booking_id)
+        .filter(booking::Column::UserId.eq(claims.sub))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Booking not found".to_string()))?;
+
+    booking_with_etag(&state, booking).await
+}
+
+/// Fetch one of the caller's bookings by its reference code
+#[utoipa::path(
+    get,
+    path = "/api/bookings/by-ref/{code}",
+    tag = "bookings",
+    params(("code" = String, Path, description = "Booking reference code, e.g. 7K3QZ9")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = BookingResponse, headers(("ETag" = String))),
+        (status = 404, description = "Booking not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_booking_by_reference(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(code): Path<String>,
+) -> AppResult<([(HeaderName, String); 1], Json<BookingResponse>)> {
+    let not_found = || AppError::NotFound("Booking not found".to_string());
+    let code = normalize_reference_code(&code).ok_or_else(not_found)?;
+
+    let booking = booking::Entity::find()
+        .filter(booking::Column::ReferenceCode.eq(code))
+        .filter(booking::Column::UserId.eq(claims.sub))
+        .one(&state.db)
+        .await?
+        .ok_or_else(not_found)?;
+
+    booking_with_etag(&state, booking).await
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DriverContactResponse {
+    pub name: String,
+    pub phone: Option<String>,
+}
+
+/// The assigned driver's contact details, for a passenger with a confirmed booking
+#[utoipa::path(
+    get,
+    path = "/api/bookings/{id}/driver-contact",
+    tag = "bookings",
+    params(("id" = Uuid, Path, description = "Booking ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = DriverContactResponse),
+        (status = 403, description = "Booking is not confirmed", body = ErrorResponse),
+        (
+            status = 404,
+            description = "Booking not found, or no driver assigned",
+            body = ErrorResponse,
+        ),
+    )
+)]
+pub async fn driver_contact(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(booking_id): Path<Uuid>,
+) -> AppResult<Json<DriverContactResponse>> {
+    let booking = booking::Entity::find_by_id(booking_id)
+        .filter(booking::Column::UserId.eq(claims.sub))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Booking not found".to_string()))?;
+
+    // Waitlisted travellers aren't riding (yet), so they don't get the number
+    if booking.status != BookingStatus::Confirmed {
+        return Err(AppError::Forbidden(
+            "Driver contact is only available for confirmed bookings".to_string(),
+        ));
+    }
+
+    let journey = journey::Entity::find_by_id(booking.journey_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    let no_driver = || AppError::NotFound("No driver assigned to this journey yet".to_string());
+    let driver_id = journey.driver_id.ok_or_else(no_driver)?;
+    let driver = user::Entity::find_active_by_id(driver_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(no_driver)?;
+
+    Ok(Json(DriverContactResponse {
+        name: driver.name,
+        phone: driver.phone,
     }))
 }
 
-/// List user's bookings
+/// List user's bookings, including cancelled ones
+#[utoipa::path(
+    get,
+    path = "/api/bookings",
+    tag = "bookings",
+    params(Pagination),
+    security(("bearer_auth" = [])),
+    responses((status = 200, body = Paginated<MyBookingResponse>))
+)]
 pub async fn my_bookings(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
-) -> AppResult<Json<Vec<BookingResponse>>> {
-    let bookings = booking::Entity::find()
+    Query(pagination): Query<Pagination>,
+) -> AppResult<Json<Paginated<MyBookingResponse>>> {
+    // Newest bookings first
+    let paginator = booking::Entity::find()
         .filter(booking::Column::UserId.eq(claims.sub))
+        .order_by_desc(booking::Column::CreatedAt)
+        .paginate(&state.db, pagination.per_page());
+    let total = paginator.num_items().await?;
+    let bookings = paginator.fetch_page(pagination.page() - 1).await?;
+
+    let mut journey_ids: Vec<Uuid> = bookings.iter().map(|b| b.journey_id).collect();
+    journey_ids.sort();
+    journey_ids.dedup();
+    let journeys = journey::Entity::find()
+        .filter(journey::Column::Id.is_in(journey_ids.clone()))
         .all(&state.db)
         .await?;
-
-    let journeys = journey::Entity::find().all(&state.db).await?;
     let cities = city::Entity::find().all(&state.db).await?;
+    let booked_by_journey = booked_seats_by_journey(&state.db, &journey_ids).await?;
+    let held_by_journey = held_seats_by_journey(&state.db, &journey_ids).await?;
 
-    let responses: Vec<BookingResponse> = bookings
+    let responses: Vec<MyBookingResponse> = bookings
         .into_iter()
         .filter_map(|b| {
             let journey = journeys.iter().find(|j| j.id == b.journey_id)?;
-            let origin = cities.iter().find(|c| c.id == journey.origin_city_id);
-            let dest = cities.iter().find(|c| c.id == journey.destination_city_id);
-
-            Some(BookingResponse {
-                id: b.id,
-                journey_id: journey.id,
-                origin_city: origin.map(|c| c.name.clone()).unwrap_or_default(),
-                destination_city: dest.map(|c| c.name.clone()).unwrap_or_default(),
-                departure_time: journey.departure_time.with_timezone(&Utc),
-                seats: b.seats,
-                pickup_lat: b.pickup_lat,
-                pickup_lng: b.pickup_lng,
-                created_at: b.created_at.with_timezone(&Utc),
+            let booked = booked_by_journey.get(&journey.id).copied().unwrap_or(0);
+            let held = held_by_journey.get(&journey.id).copied().unwrap_or(0);
+
+            Some(MyBookingResponse {
+                booking: booking_response(b, journey, &cities),
+                journey_total_seats: journey.total_seats,
+                journey_available_seats: (journey.total_seats - booked - held).max(0),
             })
         })
         .collect();
 
-    Ok(Json(responses))
+    Ok(Json(Paginated {
+        items: responses,
+        total,
+        page: pagination.page(),
+        per_page: pagination.per_page(),
+    }))
 }
 
 /// Cancel a booking
+#[utoipa::path(
+    delete,
+    path = "/api/bookings/{id}",
+    tag = "bookings",
+    params(("id" = Uuid, Path, description = "Booking ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = serde_json::Value),
+        (status = 400, description = "Booking can no longer be cancelled", body = ErrorResponse),
+        (status = 404, description = "Booking not found", body = ErrorResponse),
+        (status = 409, description = "Booking is already cancelled", body = ErrorResponse),
+    )
+)]
 pub async fn cancel_booking(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
@@ -316,29 +1452,483 @@ pub async fn cancel_booking(
         .await?
         .ok_or_else(|| AppError::NotFound("Booking not found".to_string()))?;
 
-    // Verify ownership
+    // Someone else's booking looks exactly like a missing one, so ids can't be probed
     if booking.user_id != claims.sub {
-        return Err(AppError::Forbidden(
-            "You can only cancel your own bookings".to_string(),
+        tracing::debug!(
+            "User {} tried to cancel booking {} owned by {}",
+            claims.sub,
+            booking.id,
+            booking.user_id
+        );
+        return Err(AppError::NotFound("Booking not found".to_string()));
+    }
+
+    if booking.status == BookingStatus::Cancelled {
+        return Err(AppError::Conflict(
+            "Booking is already cancelled".to_string(),
         ));
     }
 
+    // Lock the journey so freed seats go to the waitlist without racing new bookings
+    let txn = state.db.begin().await?;
+
     // Check if journey is still in the future
     let journey = journey::Entity::find_by_id(booking.journey_id)
-        .one(&state.db)
+        .lock_exclusive()
+        .one(&txn)
         .await?;
 
-    if let Some(j) = journey {
-        if j.departure_time.with_timezone(&Utc) < Utc::now() {
+    if let Some(j) = &journey {
+        let departure = j.departure_time.with_timezone(&Utc);
+        let now = Utc::now();
+        if departure < now {
             return Err(AppError::BadRequest(
                 "Cannot cancel bookings for past journeys".to_string(),
             ));
         }
+
+        let cutoff_hours = state.config.cancellation_cutoff_hours;
+        if !cancellation_allowed(departure, now, Duration::hours(cutoff_hours.into())) {
+            return Err(AppError::BadRequest(format!(
+                "Bookings can't be cancelled within {} hour(s) of departure",
+                cutoff_hours
+            )));
+        }
+
+        // A driver may start or finish a trip ahead of its scheduled departure
+        if matches!(
+            j.status,
+            JourneyStatus::InProgress | JourneyStatus::Completed
+        ) {
+            return Err(AppError::BadRequest(
+                "Cannot cancel bookings for journeys that have started or completed".to_string(),
+            ));
+        }
     }
 
-    booking::Entity::delete_by_id(booking_id)
-        .exec(&state.db)
-        .await?;
+    // Keep the row so the trip stays in the traveller's history
+    let previous_status = booking.status;
+    let mut active: booking::ActiveModel = booking.into();
+    active.status = Set(BookingStatus::Cancelled);
+    active.cancelled_at = Set(Some(Utc::now().into()));
+    active.update(&txn).await?;
+    forget_idempotency_keys(&txn, booking_id).await?;
+
+    if let Some(j) = &journey
+        && previous_status == BookingStatus::Confirmed
+        && j.status == JourneyStatus::Scheduled
+    {
+        for promoted in promote_waitlisted(&txn, j).await? {
+            tracing::info!(
+                booking_id = %promoted.id,
+                journey_id = %j.id,
+                "Promoted waitlisted booking"
+            );
+        }
+    }
+
+    txn.commit().await?;
 
     Ok(Json(serde_json::json!({ "message": "Booking cancelled" })))
 }
+
+/// Change seats and/or pickup point (traveller) - all fields optional
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateBookingRequest {
+    pub seats: Option<i32>,
+    pub pickup_lat: Option<f64>,
+    pub pickup_lng: Option<f64>,
+}
+
+/// Modify one of the caller's bookings, with the same checks as booking afresh
+#[utoipa::path(
+    put,
+    path = "/api/bookings/{id}",
+    tag = "bookings",
+    request_body = UpdateBookingRequest,
+    params(
+        ("id" = Uuid, Path, description = "Booking ID"),
+        (
+            "If-Match" = String,
+            Header,
+            description = "ETag from an earlier read; fails with 412 if the booking changed",
+        ),
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = BookingResponse, headers(("ETag" = String))),
+        (
+            status = 400,
+            description = "Invalid seats or pickup point, or booking cancelled",
+            body = ErrorResponse,
+        ),
+        (status = 404, description = "Booking not found", body = ErrorResponse),
+        (status = 409, description = "Not enough seats", body = ErrorResponse),
+        (
+            status = 412,
+            description = "If-Match does not match the current ETag",
+            body = ErrorResponse,
+        ),
+        (status = 428, description = "If-Match header missing", body = ErrorResponse),
+    )
+)]
+pub async fn update_booking(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+    Path(booking_id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(payload): Json<UpdateBookingRequest>,
+) -> AppResult<([(HeaderName, String); 1], Json<BookingResponse>)> {
+    let booking_record = booking::Entity::find_by_id(booking_id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Booking not found".to_string()))?;
+
+    // Verify ownership
+    if booking_record.user_id != claims.sub {
+        return Err(AppError::Forbidden(
+            "You can only modify your own bookings".to_string(),
+        ));
+    }
+
+    // Lock the journey so a seat increase can't race other bookings
+    let txn = state.db.begin().await?;
+
+    let journey = journey::Entity::find_by_id(booking_record.journey_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Journey not found".to_string()))?;
+
+    // Compare against the locked row, so a concurrent edit can't slip in between
+    let booking_record = booking::Entity::find_by_id(booking_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Booking not found".to_string()))?;
+
+    check_if_match(&headers, &booking_etag(&booking_record))?;
+
+    if booking_record.status == BookingStatus::Cancelled {
+        return Err(AppError::BadRequest(
+            "Cancelled bookings can't be modified".to_string(),
+        ));
+    }
+
+    if journey.departure_time.with_timezone(&Utc) < Utc::now() {
+        return Err(AppError::BadRequest(
+            "Cannot modify bookings for past journeys".to_string(),
+        ));
+    }
+
+    if journey.status != JourneyStatus::Scheduled {
+        return Err(AppError::BadRequest(
+            "Journey is no longer open for booking".to_string(),
+        ));
+    }
+
+    let mut active: booking::ActiveModel = booking_record.clone().into();
+
+    if payload.pickup_lat.is_some() || payload.pickup_lng.is_some() {
+        let new_lat = payload.pickup_lat.unwrap_or(booking_record.pickup_lat);
+        let new_lng = payload.pickup_lng.unwrap_or(booking_record.pickup_lng);
+        validate_coordinates(new_lat, new_lng)?;
+
+        let origin_city = city::Entity::find_by_id(journey.origin_city_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::Internal("Origin city not found".to_string()))?;
+        let dest_city = city::Entity::find_by_id(journey.destination_city_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::Internal("Destination city not found".to_string()))?;
+
+        validate_pickup_point(new_lat, new_lng, &origin_city, &dest_city)?;
+
+        active.pickup_lat = Set(new_lat);
+        active.pickup_lng = Set(new_lng);
+    }
+
+    if let Some(new_seats) = payload.seats {
+        validate_seat_count(new_seats, state.config.max_seats_per_booking)?;
+
+        // A waitlisted booking holds no seats, so only confirmed ones need room
+        if booking_record.status == BookingStatus::Confirmed {
+            let booked_by_others: i32 = booking::Entity::find()
+                .filter(booking::Column::JourneyId.eq(journey.id))
+                .filter(booking::Column::Status.eq(BookingStatus::Confirmed))
+                .filter(booking::Column::Id.ne(booking_record.id))
+                .all(&txn)
+                .await?
+                .iter()
+                .map(|b| b.seats)
+                .sum();
+
+            let held = held_seats(&txn, journey.id, None).await?;
+
+            let available = journey.total_seats - booked_by_others - held;
+            if new_seats > available {
+                return Err(AppError::Conflict(format!(
+                    "Only {} seats available",
+                    available.max(0)
+                )));
+            }
+        }
+
+        // Keep the per-seat price the booking was made at
+        let per_seat = booking_record.total_fare / i64::from(booking_record.seats);
+        active.seats = Set(new_seats);
+        active.total_fare = Set(calculate_fare(per_seat, new_seats)?);
+    }
+
+    let updated = active.update(&txn).await?;
+
+    // Seats given up by a confirmed booking go to the waitlist
+    if updated.status == BookingStatus::Confirmed && updated.seats < booking_record.seats {
+        for promoted in promote_waitlisted(&txn, &journey).await? {
+            tracing::info!(
+                booking_id = %promoted.id,
+                journey_id = %journey.id,
+                "Promoted waitlisted booking"
+            );
+        }
+    }
+
+    txn.commit().await?;
+
+    let cities = city::Entity::find().all(&state.db).await?;
+    let etag = booking_etag(&updated);
+
+    Ok((
+        [(header::ETAG, etag)],
+        Json(booking_response(updated, &journey, &cities)),
+    ))
+}
+
+// ============ Trip Summary ============
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RouteSummary {
+    pub origin_city: String,
+    pub destination_city: String,
+    pub trips: usize,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TripSummaryResponse {
+    pub total_trips: usize,
+    pub total_seats: i32,
+    pub most_traveled_route: Option<RouteSummary>,
+    pub first_trip: Option<DateTime<Utc>>,
+    pub last_trip: Option<DateTime<Utc>>,
+}
+
+/// Totals over a traveller's completed trips, as aggregated by the database
+#[derive(Debug, Default, FromQueryResult)]
+struct TripTotals {
+    trips: i64,
+    seats: Option<i64>,
+    first_trip: Option<DateTimeWithTimeZone>,
+    last_trip: Option<DateTimeWithTimeZone>,
+}
+
+/// A traveller's confirmed bookings on journeys that departed before `now` and weren't cancelled
+fn completed_trips(user_id: Uuid, now: DateTime<Utc>) -> Select<booking::Entity> {
+    booking::Entity::find()
+        .inner_join(journey::Entity)
+        .filter(booking::Column::UserId.eq(user_id))
+        .filter(booking::Column::Status.eq(BookingStatus::Confirmed))
+        .filter(journey::Column::DepartureTime.lt(now))
+        .filter(journey::Column::Status.ne(JourneyStatus::Cancelled))
+}
+
+/// Put the aggregates into response form, naming the top route's cities
+fn trip_summary_response(
+    totals: TripTotals,
+    top_route: Option<(i32, i32, i64)>,
+    cities: &[city::Model],
+) -> TripSummaryResponse {
+    let city_name = |id: i32| {
+        cities
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| c.name.clone())
+            .unwrap_or_default()
+    };
+
+    TripSummaryResponse {
+        total_trips: totals.trips as usize,
+        total_seats: totals.seats.unwrap_or(0) as i32,
+        most_traveled_route: top_route.map(|(origin, dest, trips)| RouteSummary {
+            origin_city: city_name(origin),
+            destination_city: city_name(dest),
+            trips: trips as usize,
+        }),
+        first_trip: totals.first_trip.map(|t| t.with_timezone(&Utc)),
+        last_trip: totals.last_trip.map(|t| t.with_timezone(&Utc)),
+    }
+}
+
+/// Get the caller's loyalty/trip summary
+#[utoipa::path(
+    get,
+    path = "/api/auth/summary",
+    tag = "bookings",
+    security(("bearer_auth" = [])),
+    responses((status = 200, body = TripSummaryResponse))
+)]
+pub async fn trip_summary(
+    State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
+) -> AppResult<Json<TripSummaryResponse>> {
+    let now = Utc::now();
+
+    let totals = completed_trips(claims.sub, now)
+        .select_only()
+        .column_as(booking::Column::Id.count(), "trips")
+        .column_as(booking::Column::Seats.sum(), "seats")
+        .column_as(journey::Column::DepartureTime.min(), "first_trip")
+        .column_as(journey::Column::DepartureTime.max(), "last_trip")
+        .into_model::<TripTotals>()
+        .one(&state.db)
+        .await?
+        .unwrap_or_default();
+
+    // Ties go to the route with the lowest city IDs so the result is stable
+    let top_route: Option<(i32, i32, i64)> = completed_trips(claims.sub, now)
+        .select_only()
+        .column(journey::Column::OriginCityId)
+        .column(journey::Column::DestinationCityId)
+        .column_as(booking::Column::Id.count(), "trips")
+        .group_by(journey::Column::OriginCityId)
+        .group_by(journey::Column::DestinationCityId)
+        .order_by_desc(booking::Column::Id.count())
+        .order_by_asc(journey::Column::OriginCityId)
+        .order_by_asc(journey::Column::DestinationCityId)
+        .limit(1)
+        .into_tuple()
+        .one(&state.db)
+        .await?;
+
+    let cities = city::Entity::find().all(&state.db).await?;
+
+    Ok(Json(trip_summary_response(totals, top_route, &cities)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::city::PickupPolygon;
+    use chrono::Duration;
+
+    #[test]
+    fn test_contains_pattern_escapes_wildcards() {
+        assert_eq!(contains_pattern(" Soe "), Some("%Soe%".to_string()));
+        assert_eq!(
+            contains_pattern("50%_off"),
+            Some("%50\\%\\_off%".to_string())
+        );
+        assert_eq!(contains_pattern("a\\b"), Some("%a\\\\b%".to_string()));
+        assert_eq!(contains_pattern("  "), None);
+    }
+
+    #[test]
+    fn test_pickup_point_must_be_on_origin_side() {
+        // Neighbouring towns about 22 km apart whose pickup areas overlap
+        let kupang = city::Model {
+            pickup_radius_km: 30.0,
+            ..city::Model::test_at("Kupang", -10.1772, 123.6070)
+        };
+        let oelamasi = city::Model::test_at("Oelamasi", -10.1750, 123.8100);
+
+        // West of Kupang's center, heading east
+        assert!(validate_pickup_point(-10.1800, 123.5800, &kupang, &oelamasi).is_ok());
+        // Inside Kupang's radius, but most of the way to Oelamasi
+        let err = validate_pickup_point(-10.1760, 123.7800, &kupang, &oelamasi).unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(msg) if msg.contains("closer to Oelamasi")));
+        // Outside the origin radius altogether
+        assert!(validate_pickup_point(-9.0, 123.6, &kupang, &oelamasi).is_err());
+    }
+
+    #[test]
+    fn test_pickup_polygon_replaces_radius() {
+        let mut kupang = city::Model {
+            pickup_radius_km: 30.0,
+            ..city::Model::test_at("Kupang", -10.1772, 123.6070)
+        };
+        let soe = city::Model::test_at("Soe", -9.8600, 124.2800);
+        // A narrow strip along the coast, well inside the 30 km radius
+        kupang.pickup_polygon = Some(PickupPolygon(vec![
+            [-10.20, 123.55],
+            [-10.20, 123.65],
+            [-10.15, 123.65],
+            [-10.15, 123.55],
+        ]));
+
+        assert!(validate_pickup_point(-10.1800, 123.6000, &kupang, &soe).is_ok());
+        // Within the radius, but outside the polygon
+        let err = validate_pickup_point(-10.3000, 123.6000, &kupang, &soe).unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(msg) if msg.contains("Kupang pickup zone")));
+    }
+
+    #[test]
+    fn test_cancellation_cutoff() {
+        let now = Utc::now();
+        let departure = now + Duration::hours(3);
+
+        assert!(cancellation_allowed(departure, now, Duration::zero()));
+        assert!(cancellation_allowed(departure, now, Duration::hours(3)));
+        assert!(!cancellation_allowed(departure, now, Duration::hours(4)));
+        // No cutoff still means no cancelling after departure
+        assert!(!cancellation_allowed(
+            now - Duration::minutes(1),
+            now,
+            Duration::zero()
+        ));
+    }
+
+    #[test]
+    fn test_calculate_fare() {
+        assert_eq!(calculate_fare(15_000, 3).unwrap(), 45_000);
+        assert_eq!(calculate_fare(0, 4).unwrap(), 0);
+        assert!(calculate_fare(i64::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn test_trip_summary_names_top_route() {
+        let first = Utc::now() - Duration::days(30);
+        let totals = TripTotals {
+            trips: 3,
+            seats: Some(6),
+            first_trip: Some(first.into()),
+            last_trip: Some(first.into()),
+        };
+        let cities = [
+            city::Model::test_at("Kupang", -10.1836, 123.6257),
+            city::Model {
+                id: 1,
+                ..city::Model::test_at("Soe", -9.8684, 124.2861)
+            },
+        ];
+
+        let summary = trip_summary_response(totals, Some((0, 1, 2)), &cities);
+
+        assert_eq!(summary.total_trips, 3);
+        assert_eq!(summary.total_seats, 6);
+        let route = summary.most_traveled_route.unwrap();
+        assert_eq!(route.origin_city, "Kupang");
+        assert_eq!(route.destination_city, "Soe");
+        assert_eq!(route.trips, 2);
+        assert_eq!(summary.first_trip, Some(first));
+    }
+
+    #[test]
+    fn test_trip_summary_without_trips() {
+        let summary = trip_summary_response(TripTotals::default(), None, &[]);
+
+        assert_eq!(summary.total_trips, 0);
+        assert_eq!(summary.total_seats, 0);
+        assert!(summary.most_traveled_route.is_none());
+        assert_eq!(summary.first_trip, None);
+    }
+}
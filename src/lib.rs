@@ -5,15 +5,22 @@ pub mod error;
 pub mod handlers;
 pub mod middleware;
 pub mod routes;
+pub mod shutdown;
 pub mod utils;
 
+use std::sync::Arc;
+
 use sea_orm::DatabaseConnection;
 
 pub use config::Config;
 pub use error::{AppError, AppResult};
+use utils::geocode::Geocoder;
+use utils::notify::Notifier;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: DatabaseConnection,
     pub config: Config,
+    pub notifier: Arc<dyn Notifier>,
+    pub geocoder: Arc<dyn Geocoder>,
 }
@@ -1,39 +1,37 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 
-use argon2::{
-    Argon2,
-    password_hash::{PasswordHasher, SaltString, rand_core::OsRng},
-};
-use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
 use sea_orm_migration::MigratorTrait;
 use tokio::net::TcpListener;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use uuid::Uuid;
 
 use bus_travel_backend::{
     AppState,
-    config::Config,
-    db,
-    entities::user::{self, UserRole},
+    config::{Config, LogFormat},
+    db::{self, seed::WELL_KNOWN_ADMIN_PASSWORD},
+    middleware::compression::create_compression_layer,
+    middleware::https::{HttpsPolicy, enforce_https},
+    middleware::metrics::prometheus_handle,
     middleware::rate_limit::create_global_governor,
     routes,
+    shutdown::shutdown_signal,
+    utils::geocode::NoGeocoder,
+    utils::notify::LogNotifier,
 };
 
+const EXPIRY_CLEANUP_SECS: u64 = 60 * 60;
+const METRICS_UPKEEP_SECS: u64 = 5;
+
 #[tokio::main]
 async fn main() {
-    // Initialize tracing
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "bus_travel_backend=debug,tower_http=debug".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
-
-    // Load configuration
+    // Load configuration (first, since it picks the log format)
     let config = Config::from_env();
+
+    // Initialize tracing
+    init_tracing(config.log_format);
     tracing::info!("Starting server at {}", config.server_addr());
 
     // Connect to database
@@ -49,24 +47,52 @@ async fn main() {
     tracing::info!("Migrations complete");
 
     // Seed admin account if not exists
-    seed_admin(&db).await;
+    seed_admin(&db, &config).await;
+
+    // Periodically drop token revocations and idempotency keys that have expired
+    spawn_expiry_cleanup(db.clone());
 
     // Create app state
     let state = AppState {
-        db,
+        db: db.clone(),
         config: config.clone(),
+        notifier: Arc::new(LogNotifier),
+        geocoder: Arc::new(NoGeocoder),
+    };
+
+    // Restrict CORS to the configured origins when set
+    let cors = CorsLayer::new().allow_methods(Any).allow_headers(Any);
+    let cors = match &config.allowed_origins {
+        Some(origins) => cors.allow_origin(AllowOrigin::list(origins.clone())),
+        None => {
+            tracing::warn!("ALLOWED_ORIGINS is not set; allowing requests from any origin");
+            cors.allow_origin(Any)
+        }
     };
 
     // Create router with middleware
-    let app = routes::create_router(state)
+    let mut app = routes::create_router(state)
         .layer(TraceLayer::new_for_http())
-        .layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any),
-        )
-        .layer(create_global_governor());
+        // Inside CORS so preflights and CORS headers are untouched by encoding
+        .layer(create_compression_layer())
+        .layer(cors)
+        .layer(create_global_governor(config.rate_limit_global_burst));
+
+    // HSTS (and optional HTTP -> HTTPS redirect) for production deployments
+    if config.force_https {
+        app = app.layer(axum::middleware::from_fn_with_state(
+            HttpsPolicy {
+                redirect: config.https_redirect,
+            },
+            enforce_https,
+        ));
+    }
+
+    // Expose /metrics on its own listener when configured, e.g. to keep it off the public port
+    spawn_metrics_upkeep();
+    if let Some(metrics_addr) = &config.metrics_addr {
+        spawn_metrics_server(metrics_addr).await;
+    }
 
     // Start server with socket address for rate limiting
     let addr: SocketAddr = config.server_addr().parse().expect("Invalid address");
@@ -76,43 +102,103 @@ async fn main() {
 
     tracing::info!("Server listening on {}", addr);
 
+    // Stop accepting connections on Ctrl+C/SIGTERM and let in-flight requests finish
     axum::serve(
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
+    .with_graceful_shutdown(shutdown_signal())
     .await
     .expect("Failed to start server");
+
+    if let Err(e) = db.close().await {
+        tracing::warn!("Failed to close database connection: {:?}", e);
+    }
+    tracing::info!("Server stopped");
 }
 
-/// Seed the admin account if it doesn't exist
-async fn seed_admin(db: &sea_orm::DatabaseConnection) {
-    let admin_email = "admin@bustravel.com";
+/// Purge expired token revocations, booking idempotency keys and seat holds once an hour
+fn spawn_expiry_cleanup(db: sea_orm::DatabaseConnection) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(EXPIRY_CLEANUP_SECS));
+        loop {
+            interval.tick().await;
+            match db::revocation::purge_expired_revocations(&db).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("Purged {} expired token revocations", n),
+                Err(e) => tracing::warn!("Failed to purge expired token revocations: {:?}", e),
+            }
+            match db::idempotency::purge_expired_idempotency_keys(&db).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("Purged {} expired idempotency keys", n),
+                Err(e) => tracing::warn!("Failed to purge expired idempotency keys: {:?}", e),
+            }
+            match db::holds::purge_expired_holds(&db).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("Purged {} expired seat holds", n),
+                Err(e) => tracing::warn!("Failed to purge expired seat holds: {:?}", e),
+            }
+        }
+    });
+}
+
+/// Drain recorded histogram samples into their buckets every few seconds
+fn spawn_metrics_upkeep() {
+    let handle = prometheus_handle();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(METRICS_UPKEEP_SECS));
+        loop {
+            interval.tick().await;
+            handle.run_upkeep();
+        }
+    });
+}
+
+/// Serve `/metrics` on `addr`, separate from the API listener
+async fn spawn_metrics_server(addr: &str) {
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind metrics address");
+    tracing::info!("Metrics listening on {}", addr);
+
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, routes::metrics_router()).await {
+            tracing::warn!("Metrics server stopped: {:?}", e);
+        }
+    });
+}
+
+/// Install the global subscriber, writing either pretty or JSON lines
+fn init_tracing(format: LogFormat) {
+    let registry = tracing_subscriber::registry().with(
+        tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| "bus_travel_backend=debug,tower_http=debug".into()),
+    );
+
+    match format {
+        LogFormat::Pretty => registry.with(tracing_subscriber::fmt::layer()).init(),
+        LogFormat::Json => registry
+            .with(tracing_subscriber::fmt::layer().json())
+            .init(),
+    }
+}
+
+/// Seed the admin account from `ADMIN_EMAIL`/`ADMIN_PASSWORD` if it doesn't exist
+async fn seed_admin(db: &sea_orm::DatabaseConnection, config: &Config) {
+    let (Some(email), Some(password)) = (&config.admin_email, &config.admin_password) else {
+        tracing::warn!("ADMIN_EMAIL or ADMIN_PASSWORD is not set; skipping admin seeding");
+        return;
+    };
+
+    if password == WELL_KNOWN_ADMIN_PASSWORD && !cfg!(debug_assertions) {
+        tracing::warn!("Refusing to seed the admin with the default password; set ADMIN_PASSWORD");
+        return;
+    }
 
-    let existing = user::Entity::find()
-        .filter(user::Column::Email.eq(admin_email))
-        .one(db)
+    let created = db::seed::seed_admin(db, email, password, &config.password_hash_cost())
         .await
-        .expect("Failed to check for admin");
-
-    if existing.is_none() {
-        let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
-        let password_hash = argon2
-            .hash_password(b"admin123", &salt)
-            .expect("Failed to hash admin password")
-            .to_string();
-
-        let admin = user::ActiveModel {
-            id: Set(Uuid::new_v4()),
-            email: Set(admin_email.to_string()),
-            password_hash: Set(Some(password_hash)),
-            google_id: Set(None),
-            name: Set("Admin".to_string()),
-            role: Set(UserRole::Admin),
-            ..Default::default()
-        };
-
-        admin.insert(db).await.expect("Failed to create admin");
-        tracing::info!("Admin account created: {}", admin_email);
+        .expect("Failed to seed admin");
+    if let Some(admin) = created {
+        tracing::info!("Admin account created: {}", admin.email);
     }
 }
@@ -8,19 +8,36 @@ use axum_extra::{
     TypedHeader,
 };
 
-use crate::entities::user::UserRole;
+use crate::db::revocation::is_token_revoked;
+use crate::entities::user::{self, UserRole};
 use crate::error::{AppError, AppResult};
-use crate::utils::jwt::{verify_token, Claims};
+use crate::utils::jwt::{verify_access_token, Claims};
 use crate::AppState;
 
-/// Extract and validate JWT token from Authorization header
+/// Extract and validate JWT token from Authorization header, rejecting deleted accounts
 pub async fn auth_middleware(
     State(state): State<AppState>,
     TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
     mut request: Request,
     next: Next,
 ) -> AppResult<Response> {
-    let claims = verify_token(auth.token(), &state.config.jwt_secret)?;
+    let claims = verify_access_token(auth.token(), (&state.config).into())?;
+
+    if is_token_revoked(&state.db, claims.jti).await? {
+        return Err(AppError::Unauthorized("Token has been revoked".to_string()));
+    }
+
+    // Deleting an account doesn't revoke its tokens, so check the user still exists
+    if user::Entity::find_active_by_id(claims.sub)
+        .one(&state.db)
+        .await?
+        .is_none()
+    {
+        return Err(AppError::Unauthorized(
+            "Account no longer exists".to_string(),
+        ));
+    }
+
     request.extensions_mut().insert(claims);
     Ok(next.run(request).await)
 }
@@ -0,0 +1,19 @@
+use tower_http::compression::{
+    CompressionLayer,
+    predicate::{And, DefaultPredicate, Predicate, SizeAbove},
+};
+
+/// Responses smaller than this are sent as is; compressing them saves little and costs CPU
+pub const COMPRESSION_MIN_BYTES: u16 = 1024;
+
+/// Type alias for the response compression layer
+pub type ResponseCompressionLayer = CompressionLayer<And<DefaultPredicate, SizeAbove>>;
+
+/// Gzip/brotli response compression, negotiated from the request's `Accept-Encoding`.
+/// Keeps tower-http's defaults (no images, gRPC or event streams) and skips small bodies.
+pub fn create_compression_layer() -> ResponseCompressionLayer {
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(DefaultPredicate::new().and(SizeAbove::new(COMPRESSION_MIN_BYTES)))
+}
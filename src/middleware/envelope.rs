@@ -0,0 +1,158 @@
+use axum::{
+    body::Body,
+    extract::{Query, Request},
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::error::AppError;
+use crate::middleware::request_id::RequestId;
+
+#[derive(Debug, Default, Deserialize)]
+struct EnvelopeQuery {
+    #[serde(default)]
+    envelope: bool,
+}
+
+/// Whether the caller asked for `?envelope=true`
+fn wants_envelope(request: &Request) -> bool {
+    Query::<EnvelopeQuery>::try_from_uri(request.uri())
+        .map(|Query(q)| q.envelope)
+        .unwrap_or(false)
+}
+
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"))
+}
+
+/// With `?envelope=true`, wrap successful JSON bodies as `{"data": ..., "meta": {...}}`
+/// to match the error shape, dropping any `ETag`. Other responses, and every response
+/// without the parameter, pass through unchanged.
+pub async fn envelope(request: Request, next: Next) -> Response {
+    if !wants_envelope(&request) {
+        return next.run(request).await;
+    }
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.clone());
+
+    let response = next.run(request).await;
+    if !response.status().is_success() || !is_json(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return AppError::Internal(format!("Failed to read response body for envelope: {}", e))
+                .into_response();
+        }
+    };
+    let Ok(data) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let wrapped = json!({
+        "data": data,
+        "meta": { "request_id": request_id },
+    });
+    let body = serde_json::to_vec(&wrapped).unwrap_or_default();
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    // Tags describe the unwrapped body; If-Match and If-None-Match are checked against those
+    parts.headers.remove(header::ETAG);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Json, Router, http::StatusCode, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use crate::error::AppResult;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/items", get(|| async { Json(json!([1, 2, 3])) }))
+            .route("/text", get(|| async { "plain" }))
+            .route(
+                "/tagged",
+                get(|| async { ([(header::ETAG, "\"1\"")], Json(json!({ "id": 1 }))) }),
+            )
+            .route(
+                "/missing",
+                get(|| async { AppResult::<()>::Err(AppError::NotFound("Gone".to_string())) }),
+            )
+            .layer(middleware::from_fn(envelope))
+    }
+
+    async fn get_body(uri: &str) -> (StatusCode, Vec<u8>) {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, bytes.to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_json_wrapped_only_when_asked() {
+        let (_, raw) = get_body("/items").await;
+        assert_eq!(
+            serde_json::from_slice::<Value>(&raw).unwrap(),
+            json!([1, 2, 3])
+        );
+
+        let (_, wrapped) = get_body("/items?envelope=true").await;
+        let wrapped: Value = serde_json::from_slice(&wrapped).unwrap();
+        assert_eq!(wrapped["data"], json!([1, 2, 3]));
+        assert!(wrapped["meta"].is_object());
+
+        let (_, unwrapped) = get_body("/items?envelope=false").await;
+        assert_eq!(raw, unwrapped);
+    }
+
+    #[tokio::test]
+    async fn test_errors_and_non_json_pass_through() {
+        let (status, body) = get_body("/missing?envelope=true").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "NOT_FOUND");
+        assert!(body.get("data").is_none());
+
+        let (_, text) = get_body("/text?envelope=true").await;
+        assert_eq!(text, b"plain");
+    }
+
+    #[tokio::test]
+    async fn test_wrapped_body_drops_etag() {
+        let request = Request::builder()
+            .uri("/tagged?envelope=true")
+            .body(Body::empty())
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert!(response.headers().get(header::ETAG).is_none());
+
+        let request = Request::builder()
+            .uri("/tagged")
+            .body(Body::empty())
+            .unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[header::ETAG], "\"1\"");
+    }
+}
@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+/// HSTS policy: remember HTTPS-only for one year, including subdomains
+const HSTS_VALUE: &str = "max-age=31536000; includeSubDomains";
+
+/// HTTPS enforcement settings for `enforce_https`
+#[derive(Debug, Clone, Copy)]
+pub struct HttpsPolicy {
+    /// Redirect plain-HTTP requests (as reported by the proxy) to HTTPS
+    pub redirect: bool,
+}
+
+/// Whether the TLS-terminating proxy reports the original request as plain HTTP
+fn is_plain_http(request: &Request) -> bool {
+    request
+        .headers()
+        .get("x-forwarded-proto")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|proto| proto.eq_ignore_ascii_case("http"))
+}
+
+/// Build the HTTPS URL for a request, if its host is known
+fn https_location(request: &Request) -> Option<String> {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())?;
+    let path = request
+        .uri()
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+
+    Some(format!("https://{}{}", host, path))
+}
+
+/// Add HSTS to every response and optionally redirect plain-HTTP requests
+pub async fn enforce_https(
+    State(policy): State<HttpsPolicy>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if policy.redirect
+        && is_plain_http(&request)
+        && let Some(location) = https_location(&request)
+    {
+        return (
+            StatusCode::PERMANENT_REDIRECT,
+            [(header::LOCATION, location)],
+        )
+            .into_response();
+    }
+
+    let mut response = next.run(request).await;
+    response.headers_mut().insert(
+        header::STRICT_TRANSPORT_SECURITY,
+        HeaderValue::from_static(HSTS_VALUE),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, middleware, routing::get};
+    use tower::ServiceExt;
+
+    fn app(redirect: bool) -> Router {
+        Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(middleware::from_fn_with_state(
+                HttpsPolicy { redirect },
+                enforce_https,
+            ))
+    }
+
+    #[tokio::test]
+    async fn test_hsts_header_added() {
+        let response = app(false)
+            .oneshot(Request::get("/ping").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get(header::STRICT_TRANSPORT_SECURITY)
+                .unwrap(),
+            HSTS_VALUE
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plain_http_redirected() {
+        let request = Request::get("/ping?x=1")
+            .header(header::HOST, "api.example.com")
+            .header("x-forwarded-proto", "http")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app(true).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers().get(header::LOCATION).unwrap(),
+            "https://api.example.com/ping?x=1"
+        );
+    }
+}
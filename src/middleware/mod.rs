@@ -1,3 +1,9 @@
 pub mod auth;
+pub mod compression;
+pub mod envelope;
+pub mod https;
+pub mod metrics;
 pub mod rate_limit;
+pub mod request_id;
 pub mod role_rate_limit;
+pub mod timeout;
@@ -1,8 +1,13 @@
 use axum::body::Body;
-use axum::http::{Response, StatusCode};
+use axum::http::{HeaderValue, Response, StatusCode, header::RETRY_AFTER};
 use axum::response::IntoResponse;
 use std::sync::Arc;
-use tower_governor::{governor::GovernorConfigBuilder, GovernorError, GovernorLayer};
+use governor::middleware::{NoOpMiddleware, StateInformationMiddleware};
+use tower_governor::{
+    governor::{GovernorConfig, GovernorConfigBuilder},
+    key_extractor::PeerIpKeyExtractor,
+    GovernorError, GovernorLayer,
+};
 
 /// Type alias for the global governor layer (IP-based rate limiting)
 pub type GlobalGovernorLayer = GovernorLayer<
@@ -11,16 +16,30 @@ pub type GlobalGovernorLayer = GovernorLayer<
     Body,
 >;
 
+/// Type alias for the public governor layer (IP-based, with `X-RateLimit-*` response headers)
+pub type PublicGovernorLayer = GovernorLayer<PeerIpKeyExtractor, StateInformationMiddleware, Body>;
+
 /// Error handler for rate limiting - logs the rejection and returns a 429 response.
 /// This function is used by both global and role-based rate limiters.
+/// The governor's headers (`Retry-After`, and `X-RateLimit-*` on layers built with
+/// `use_headers`) are passed through so clients know when to retry.
 pub fn rate_limit_error_handler(err: GovernorError) -> Response<Body> {
     match err {
-        GovernorError::TooManyRequests { .. } => {
+        GovernorError::TooManyRequests { wait_time, headers } => {
             tracing::warn!(
                 status = %StatusCode::TOO_MANY_REQUESTS,
+                wait_time,
                 "Rate limited - request rejected due to too many requests"
             );
-            (StatusCode::TOO_MANY_REQUESTS, "Too Many Requests").into_response()
+            let mut response = (StatusCode::TOO_MANY_REQUESTS, "Too Many Requests").into_response();
+            if let Some(headers) = headers {
+                response.headers_mut().extend(headers);
+            }
+            response
+                .headers_mut()
+                .entry(RETRY_AFTER)
+                .or_insert_with(|| HeaderValue::from(wait_time));
+            response
         }
         _ => {
             tracing::error!(
@@ -32,31 +51,65 @@ pub fn rate_limit_error_handler(err: GovernorError) -> Response<Body> {
     }
 }
 
-/// Create a GovernorLayer for global rate limiting (per IP address)
-/// - Applied before authentication to protect against DDoS
-pub fn create_global_governor() -> GlobalGovernorLayer {
-    let config = Arc::new(
+/// Milliseconds between replenished tokens for a bucket of `burst` tokens,
+/// so the bucket refills at `burst / 2` tokens per minute
+pub fn replenish_interval_ms(burst: u32) -> u64 {
+    (60 * 1000 * 2 / u64::from(burst.max(1))).max(1)
+}
+
+/// Build the per-IP governor config with the given burst size
+fn ip_governor_config(burst: u32) -> Arc<GovernorConfig<PeerIpKeyExtractor, NoOpMiddleware>> {
+    Arc::new(
         GovernorConfigBuilder::default()
-            .per_millisecond(60 * 2) // One token every 60 * 2 ms (1000 / 2 per minute)
-            .burst_size(1000)    // Max capacity of the "window"
+            .per_millisecond(replenish_interval_ms(burst))
+            .burst_size(burst) // Max capacity of the "window"
             .finish()
-            .unwrap(),
-    );
+            .expect("Rate limit burst size must be positive"),
+    )
+}
 
-    GovernorLayer::new(config).error_handler(rate_limit_error_handler)
+/// Create a GovernorLayer for global rate limiting (per IP address)
+/// - Applied before authentication to protect against DDoS
+pub fn create_global_governor(burst: u32) -> GlobalGovernorLayer {
+    GovernorLayer::new(ip_governor_config(burst)).error_handler(rate_limit_error_handler)
 }
 
 /// Create a GovernorLayer for public endpoints (per IP address, with traveller-level limits)
 /// - Applied to public routes where there's no authenticated user
-/// - Uses same restrictive limits as traveller rate limiting
-pub fn create_public_governor() -> GlobalGovernorLayer {
+/// - Callers pass the traveller limit so both stay equally restrictive
+/// - Responses carry `X-RateLimit-Limit` and `X-RateLimit-Remaining`
+pub fn create_public_governor(burst: u32) -> PublicGovernorLayer {
     let config = Arc::new(
         GovernorConfigBuilder::default()
-            .per_millisecond(600 * 2) // One token every 600 * 2 ms (100 / 2 per minute)
-            .burst_size(100)      // Same as traveller limit
+            .per_millisecond(replenish_interval_ms(burst))
+            .burst_size(burst)
+            .use_headers()
             .finish()
-            .unwrap(),
+            .expect("Rate limit burst size must be positive"),
     );
-
     GovernorLayer::new(config).error_handler(rate_limit_error_handler)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn test_replenish_interval_matches_previous_limits() {
+        assert_eq!(replenish_interval_ms(1000), 60 * 2);
+        assert_eq!(replenish_interval_ms(100), 600 * 2);
+        assert_eq!(replenish_interval_ms(0), 60 * 1000 * 2);
+    }
+
+    #[test]
+    fn test_ip_governor_uses_configured_burst() {
+        let config = ip_governor_config(3);
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        for _ in 0..3 {
+            assert!(config.limiter().check_key(&ip).is_ok());
+        }
+        assert!(config.limiter().check_key(&ip).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest inbound request id we pass through; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// Id of the request being handled, available to handlers as an extension
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Id of the request whose handler is currently running, if any
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// An inbound `x-request-id`, if it is short and printable enough to log and echo back
+fn inbound_request_id(request: &Request) -> Option<String> {
+    let id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    (!id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.chars().all(|c| c.is_ascii_graphic()))
+        .then(|| id.to_string())
+}
+
+/// Tag each request with an id (the caller's or a fresh UUID), record it on the tracing
+/// span and echo it in the `x-request-id` response header
+pub async fn request_id(mut request: Request, next: Next) -> Response {
+    let id = inbound_request_id(&request).unwrap_or_else(|| Uuid::new_v4().to_string());
+    request.extensions_mut().insert(RequestId(id.clone()));
+
+    let span = tracing::info_span!("request", request_id = %id);
+    let mut response = CURRENT_REQUEST_ID
+        .scope(id.clone(), next.run(request).instrument(span))
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, http::StatusCode, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use crate::error::{AppError, AppResult};
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/fail",
+                get(|| async { AppResult::<()>::Err(AppError::Internal("boom".to_string())) }),
+            )
+            .layer(middleware::from_fn(request_id))
+    }
+
+    async fn call(request: Request) -> (Response, serde_json::Value) {
+        let response = app().oneshot(request).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap();
+        (Response::from_parts(parts, Body::empty()), json)
+    }
+
+    #[tokio::test]
+    async fn test_inbound_request_id_round_trips() {
+        let request = Request::get("/fail")
+            .header(REQUEST_ID_HEADER, "client-req-42")
+            .body(Body::empty())
+            .unwrap();
+
+        let (response, body) = call(request).await;
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            response.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "client-req-42"
+        );
+        assert_eq!(body["error"]["request_id"], "client-req-42");
+    }
+
+    #[tokio::test]
+    async fn test_request_id_generated_when_missing_or_invalid() {
+        for inbound in [None, Some(""), Some("has spaces")] {
+            let mut request = Request::get("/fail");
+            if let Some(inbound) = inbound {
+                request = request.header(REQUEST_ID_HEADER, inbound);
+            }
+
+            let (response, body) = call(request.body(Body::empty()).unwrap()).await;
+
+            let header = response.headers().get(REQUEST_ID_HEADER).unwrap();
+            assert!(Uuid::parse_str(header.to_str().unwrap()).is_ok());
+            assert_eq!(body["error"]["request_id"], header.to_str().unwrap());
+        }
+    }
+}
@@ -1,13 +1,14 @@
 use axum::http::Request;
 use std::sync::Arc;
 use tower_governor::{
-    governor::GovernorConfigBuilder,
+    governor::{GovernorConfig, GovernorConfigBuilder},
     key_extractor::KeyExtractor,
     GovernorError, GovernorLayer,
 };
 use uuid::Uuid;
 
-use crate::middleware::rate_limit::rate_limit_error_handler;
+use crate::config::Config;
+use crate::middleware::rate_limit::{rate_limit_error_handler, replenish_interval_ms};
 use crate::utils::jwt::Claims;
 
 /// Custom key extractor that extracts user ID from JWT claims in request extensions
@@ -28,16 +29,15 @@ impl KeyExtractor for UserIdExtractor {
     }
 }
 
+/// Role governors report `X-RateLimit-Limit` and `X-RateLimit-Remaining` on every response
 pub type RoleGovernorLayer = GovernorLayer<
     UserIdExtractor,
-    governor::middleware::NoOpMiddleware<governor::clock::QuantaInstant>,
+    governor::middleware::StateInformationMiddleware,
     axum::body::Body,
 >;
 
-/// Create a GovernorLayer for a specific role
-/// - Admin: No rate limiting (10x base in IP-based global rate limiter)
-/// - Driver: 500 requests per minute (5x base)
-/// - Traveller: 100 requests per minute (base)
+type RoleGovernorConfig =
+    GovernorConfig<UserIdExtractor, governor::middleware::StateInformationMiddleware>;
 
 // The dedicated roles enum for rate limiting is meant to
 // prevent the role-based rate limiter from being used in admin routes.
@@ -56,20 +56,46 @@ pub enum RateLimitedRole {
 //     ...
 // }
 
-pub fn create_role_governor(role: RateLimitedRole) -> RoleGovernorLayer {
-    let (per_ms, burst) = match role {
-        RateLimitedRole::Driver => (120 * 2, 500),     // 500 / 2 per minute
-        RateLimitedRole::Traveller => (600 * 2, 100),  // 100 / 2 per minute
+/// Create a GovernorLayer for a specific role
+/// - Admin: No rate limiting (10x base in IP-based global rate limiter)
+/// - Driver: `RATE_LIMIT_DRIVER_PER_MIN` burst (default 500, 5x base)
+/// - Traveller: `RATE_LIMIT_TRAVELLER_PER_MIN` burst (default 100, base)
+pub fn create_role_governor(role: RateLimitedRole, config: &Config) -> RoleGovernorLayer {
+    let burst = match role {
+        RateLimitedRole::Driver => config.rate_limit_driver_per_min,
+        RateLimitedRole::Traveller => config.rate_limit_traveller_per_min,
     };
 
-    let config = Arc::new(
+    GovernorLayer::new(role_governor_config(burst)).error_handler(rate_limit_error_handler)
+}
+
+/// Build the per-user governor config with the given burst size (refilling at burst / 2 per minute)
+fn role_governor_config(burst: u32) -> Arc<RoleGovernorConfig> {
+    Arc::new(
         GovernorConfigBuilder::default()
-            .per_millisecond(per_ms)
+            .per_millisecond(replenish_interval_ms(burst))
             .burst_size(burst)
             .key_extractor(UserIdExtractor)
+            .use_headers()
             .finish()
-            .unwrap(),
-    );
+            .expect("Rate limit burst size must be positive"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    GovernorLayer::new(config).error_handler(rate_limit_error_handler)
+    #[test]
+    fn test_role_governor_uses_configured_burst() {
+        let config = role_governor_config(5);
+        let user = Uuid::new_v4();
+
+        for _ in 0..5 {
+            assert!(config.limiter().check_key(&user).is_ok());
+        }
+        assert!(config.limiter().check_key(&user).is_err());
+        // Other users have their own bucket
+        assert!(config.limiter().check_key(&Uuid::new_v4()).is_ok());
+    }
 }
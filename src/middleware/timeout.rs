@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::error_body;
+
+/// Load balancer probes should fail fast rather than queue behind a stuck database
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Request deadlines for `enforce_timeout`
+#[derive(Debug, Clone, Copy)]
+pub struct TimeoutPolicy {
+    /// Longest any request may run before it is answered with 504
+    pub request: Duration,
+}
+
+impl TimeoutPolicy {
+    pub fn from_secs(secs: u64) -> Self {
+        Self {
+            request: Duration::from_secs(secs),
+        }
+    }
+
+    /// Deadline for a request path; `/health` probes get the shorter of the two
+    fn limit_for(&self, path: &str) -> Duration {
+        if path == "/health" || path.starts_with("/health/") {
+            self.request.min(HEALTH_TIMEOUT)
+        } else {
+            self.request
+        }
+    }
+}
+
+/// Answer with 504 when the inner service doesn't respond within the policy's deadline
+///
+/// The handler future is dropped at the deadline, which also rolls back any open transaction.
+pub async fn enforce_timeout(
+    State(policy): State<TimeoutPolicy>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let limit = policy.limit_for(request.uri().path());
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+
+    match tokio::time::timeout(limit, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!("{} {} timed out after {:?}", method, path, limit);
+            (
+                StatusCode::GATEWAY_TIMEOUT,
+                error_body("GATEWAY_TIMEOUT", "Request timed out"),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Body, middleware, routing::get};
+    use tower::ServiceExt;
+
+    async fn slow() -> &'static str {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        "done"
+    }
+
+    fn app(request: Duration) -> Router {
+        Router::new()
+            .route("/slow", get(slow))
+            .route("/fast", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(
+                TimeoutPolicy { request },
+                enforce_timeout,
+            ))
+    }
+
+    #[tokio::test]
+    async fn test_slow_request_times_out() {
+        let response = app(Duration::from_millis(50))
+            .oneshot(Request::get("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["error"]["code"], "GATEWAY_TIMEOUT");
+    }
+
+    #[tokio::test]
+    async fn test_fast_request_passes_through() {
+        let response = app(Duration::from_millis(50))
+            .oneshot(Request::get("/fast").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_health_gets_shorter_limit() {
+        let policy = TimeoutPolicy::from_secs(30);
+        assert_eq!(policy.limit_for("/api/journeys"), Duration::from_secs(30));
+        assert_eq!(policy.limit_for("/health"), HEALTH_TIMEOUT);
+        assert_eq!(policy.limit_for("/health/ready"), HEALTH_TIMEOUT);
+        assert_eq!(policy.limit_for("/healthy"), Duration::from_secs(30));
+
+        // A deadline already below the health cap applies as is
+        let tight = TimeoutPolicy::from_secs(2);
+        assert_eq!(tight.limit_for("/health"), Duration::from_secs(2));
+    }
+}
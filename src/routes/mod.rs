@@ -1,33 +1,71 @@
 use axum::{
     Router, middleware,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
 };
 
 use crate::AppState;
-use crate::handlers::{admin, auth, driver, traveller};
+use crate::handlers::{admin, auth, docs, driver, health, metrics, traveller};
 use crate::middleware::auth::{auth_middleware, require_admin, require_driver, require_traveller};
+use crate::middleware::envelope::envelope;
+use crate::middleware::metrics::track_metrics;
 use crate::middleware::rate_limit::create_public_governor;
+use crate::middleware::request_id::request_id;
 use crate::middleware::role_rate_limit::RateLimitedRole;
 use crate::middleware::role_rate_limit::create_role_governor;
+use crate::middleware::timeout::{TimeoutPolicy, enforce_timeout};
 
 pub fn create_router(state: AppState) -> Router {
     // Create role-specific governor layers
-    let driver_governor = create_role_governor(RateLimitedRole::Driver);
-    let traveller_governor = create_role_governor(RateLimitedRole::Traveller);
+    let driver_governor = create_role_governor(RateLimitedRole::Driver, &state.config);
+    let traveller_governor = create_role_governor(RateLimitedRole::Traveller, &state.config);
     // Create IP-based governor for public routes (with traveller-level limits)
-    let public_governor = create_public_governor();
+    let public_governor = create_public_governor(state.config.rate_limit_traveller_per_min);
+
+    // Load balancer probes (no auth, no per-IP or per-role rate limiting)
+    let health_routes = Router::new()
+        .route("/", get(health::health))
+        .route("/ready", get(health::ready));
 
     // Public routes (with traveller-level rate limiting per IP)
     let auth_routes = Router::new()
         .route("/register", post(auth::register))
+        .route("/register-driver", post(auth::register_driver))
         .route("/login", post(auth::login))
         .route("/google", post(auth::google_login))
+        .route("/refresh", post(auth::refresh))
         .layer(public_governor.clone());
 
-    // Public journey routes (list available journeys, cities)
+    // Authenticated account routes (requires auth + traveller role)
+    let account_routes = Router::new()
+        .route("/summary", get(traveller::trip_summary))
+        .route("/account", delete(auth::delete_account))
+        .layer(traveller_governor.clone())
+        .layer(middleware::from_fn(require_traveller))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ));
+
+    // Session routes (requires auth, any role)
+    let session_routes = Router::new()
+        .route("/logout", post(auth::logout))
+        .route("/password", put(auth::change_password))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ));
+
+    // Public journey routes (list available journeys, cities) and the API spec
     let public_routes = Router::new()
         .route("/journeys", get(traveller::list_journeys))
         .route("/journeys/{id}", get(traveller::get_journey))
+        .route(
+            "/journeys/{id}/availability",
+            get(traveller::journey_availability),
+        )
+        .route("/cities", get(traveller::list_cities))
+        .route("/cities/nearest", get(traveller::nearest_city))
+        .route("/docs/openapi.json", get(docs::openapi_json))
         .layer(public_governor);
 
     // Admin routes (requires auth + admin role)
@@ -35,20 +73,49 @@ pub fn create_router(state: AppState) -> Router {
     let admin_routes = Router::new()
         // Journey management
         .route("/cities", get(admin::list_cities))
+        .route("/cities", post(admin::create_city))
+        .route("/cities/import", post(admin::import_cities))
+        .route("/cities/{id}", put(admin::update_city))
+        .route("/cities/{id}", delete(admin::delete_city))
+        .route(
+            "/cities/{id}/geocode-refresh",
+            post(admin::refresh_city_geocode),
+        )
         .route("/journeys", get(admin::list_journeys))
         .route("/journeys", post(admin::create_journey))
-        .route("/journeys/{id}", put(admin::update_journey))
+        .route(
+            "/journeys/recurring",
+            post(admin::create_recurring_journeys),
+        )
+        .route("/journeys/{id}", put(admin::replace_journey))
+        .route("/journeys/{id}", patch(admin::update_journey))
         .route("/journeys/{id}", delete(admin::delete_journey))
         .route("/journeys/{id}/assign-driver", post(admin::assign_driver))
+        .route("/journeys/{id}/unassign-driver", post(admin::unassign_driver))
+        .route("/journeys/{id}/reschedule-preview", post(admin::reschedule_preview))
+        .route("/journeys/{id}/reschedule", post(admin::reschedule_journey))
+        .route("/journeys/{id}/cancel", post(admin::cancel_journey))
         .route("/journeys/{id}/passengers", get(admin::journey_passengers))
+        .route("/stats", get(admin::stats))
         // User management
         .route("/users", get(admin::list_all_users))
         .route("/users/{id}", delete(admin::delete_user))
         .route("/users/{id}/role", put(admin::update_user_role))
+        .route("/audit/role-changes", get(admin::list_role_change_audits))
+        .route(
+            "/users/{id}/reset-password",
+            post(admin::reset_user_password),
+        )
+        .route("/users/{id}/bookings", get(admin::list_user_bookings))
         // Drivers
         .route("/drivers", get(admin::list_drivers))
+        .route("/drivers", post(admin::create_driver))
+        .route("/drivers/{id}", delete(admin::delete_driver))
+        .route("/driver-applications", get(admin::list_driver_applications))
+        .route("/users/{id}/approve-driver", post(admin::approve_driver))
         // Booking management
         .route("/bookings", get(admin::list_all_bookings))
+        .route("/bookings/bulk-delete", post(admin::bulk_delete_bookings))
         .route("/bookings/{id}", delete(admin::delete_booking))
         .route("/bookings/{id}", put(admin::update_booking))
         // .layer(admin_governor)  // No need for second rate limiter for admin
@@ -61,8 +128,10 @@ pub fn create_router(state: AppState) -> Router {
     // Driver routes (requires auth + driver role)
     // Rate limit: 500 / 2 requests per minute (5x base)
     let driver_routes = Router::new()
+        .route("/dashboard", get(driver::dashboard))
         .route("/journeys", get(driver::my_journeys))
         .route("/journeys/{id}/passengers", get(driver::journey_passengers))
+        .route("/journeys/{id}/complete", post(driver::complete_journey))
         .layer(driver_governor)
         .layer(middleware::from_fn(require_driver))
         .layer(middleware::from_fn_with_state(
@@ -75,6 +144,12 @@ pub fn create_router(state: AppState) -> Router {
     let traveller_routes = Router::new()
         .route("/", post(traveller::create_booking))
         .route("/", get(traveller::my_bookings))
+        .route("/hold", post(traveller::hold_seats))
+        .route("/confirm", post(traveller::confirm_hold))
+        .route("/{id}", get(traveller::get_booking))
+        .route("/by-ref/{code}", get(traveller::get_booking_by_reference))
+        .route("/{id}/driver-contact", get(traveller::driver_contact))
+        .route("/{id}", put(traveller::update_booking))
         .route("/{id}", delete(traveller::cancel_booking))
         .layer(traveller_governor)
         .layer(middleware::from_fn(require_traveller))
@@ -83,12 +158,39 @@ pub fn create_router(state: AppState) -> Router {
             auth_middleware,
         ));
 
+    // Scraped from the main listener unless METRICS_ADDR moves it elsewhere
+    let timeout_policy = TimeoutPolicy::from_secs(state.config.request_timeout_secs);
+    let metrics_routes = match state.config.metrics_addr {
+        Some(_) => Router::new(),
+        None => metrics_router(),
+    };
+
     // Combine all routes
     Router::new()
-        .nest("/api/auth", auth_routes)
+        .nest("/health", health_routes)
+        .nest(
+            "/api/auth",
+            auth_routes.merge(account_routes).merge(session_routes),
+        )
         .nest("/api", public_routes)
         .nest("/api/admin", admin_routes)
         .nest("/api/driver", driver_routes)
         .nest("/api/bookings", traveller_routes)
+        .merge(metrics_routes)
+        // Inside request_id, so the envelope can carry the id
+        .layer(middleware::from_fn(envelope))
+        // Inside metrics and request_id, so timed-out requests are counted and carry the id
+        .layer(middleware::from_fn_with_state(
+            timeout_policy,
+            enforce_timeout,
+        ))
+        .layer(middleware::from_fn(track_metrics))
+        // Outermost, so auth failures and rate-limit rejections carry the id too
+        .layer(middleware::from_fn(request_id))
         .with_state(state)
 }
+
+/// `GET /metrics` on its own, for serving on a separate address
+pub fn metrics_router<S: Clone + Send + Sync + 'static>() -> Router<S> {
+    Router::new().route("/metrics", get(metrics::metrics))
+}
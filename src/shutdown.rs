@@ -0,0 +1,71 @@
+use std::future::Future;
+
+/// Resolve on Ctrl+C or, on Unix, SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    wait_for_shutdown(ctrl_c, terminate).await;
+}
+
+/// Resolve as soon as either trigger fires
+pub async fn wait_for_shutdown(
+    ctrl_c: impl Future<Output = ()>,
+    terminate: impl Future<Output = ()>,
+) {
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("shutting down");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::{sync::oneshot, time::timeout};
+
+    async fn trigger(rx: oneshot::Receiver<()>) {
+        rx.await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_resolves_on_either_trigger() {
+        for fire_terminate in [false, true] {
+            let (tx, rx) = oneshot::channel();
+            let shutdown = if fire_terminate {
+                tokio::spawn(wait_for_shutdown(std::future::pending(), trigger(rx)))
+            } else {
+                tokio::spawn(wait_for_shutdown(trigger(rx), std::future::pending()))
+            };
+
+            tx.send(()).unwrap();
+            timeout(Duration::from_secs(1), shutdown)
+                .await
+                .expect("shutdown did not resolve")
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_waits_without_trigger() {
+        let shutdown = wait_for_shutdown(std::future::pending(), std::future::pending());
+        assert!(timeout(Duration::from_millis(50), shutdown).await.is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use axum::{
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
+
+/// A CSV document served as a file download
+pub struct CsvResponse {
+    pub filename: String,
+    pub body: String,
+}
+
+impl CsvResponse {
+    /// Build a CSV document from a header row and data rows, escaping every field
+    pub fn new<R, F>(filename: impl Into<String>, columns: &[&str], rows: R) -> Self
+    where
+        R: IntoIterator<Item = Vec<F>>,
+        F: AsRef<str>,
+    {
+        let mut body = csv_line(columns);
+        for row in rows {
+            body.push_str(&csv_line(&row));
+        }
+
+        Self {
+            filename: filename.into(),
+            body,
+        }
+    }
+}
+
+impl IntoResponse for CsvResponse {
+    fn into_response(self) -> Response {
+        (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", self.filename),
+                ),
+            ],
+            self.body,
+        )
+            .into_response()
+    }
+}
+
+/// Whether the client asked for CSV, either via `?format=csv` or an `Accept: text/csv` header
+pub fn wants_csv(format: Option<&str>, headers: &HeaderMap) -> bool {
+    if let Some(format) = format {
+        return format.eq_ignore_ascii_case("csv");
+    }
+
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| {
+            accept
+                .split(',')
+                .any(|media| media.split(';').next().unwrap_or("").trim() == "text/csv")
+        })
+}
+
+/// Quote a field when it contains a delimiter, quote or line break (RFC 4180)
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn csv_line<F: AsRef<str>>(fields: &[F]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|f| escape_field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// Split one line of CSV into fields, undoing the quoting `escape_field` applies.
+/// Quoted fields may not span lines.
+pub fn parse_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.trim_end_matches(['\r', '\n']).chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => {
+                    quoted = false;
+                    if !matches!(chars.peek(), None | Some(',')) {
+                        return Err("Unexpected character after closing quote".to_string());
+                    }
+                }
+                _ => field.push(c),
+            }
+        } else {
+            match c {
+                ',' => fields.push(std::mem::take(&mut field)),
+                '"' if field.is_empty() => quoted = true,
+                '"' => return Err("Unexpected quote inside an unquoted field".to_string()),
+                _ => field.push(c),
+            }
+        }
+    }
+
+    if quoted {
+        return Err("Unterminated quoted field".to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("plain"), "plain");
+        assert_eq!(escape_field("Doe, John"), "\"Doe, John\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_parse_csv_line() {
+        assert_eq!(
+            parse_csv_line("a,b,,c\r\n").unwrap(),
+            vec!["a", "b", "", "c"]
+        );
+        assert_eq!(
+            parse_csv_line("\"Doe, John\",\"say \"\"hi\"\"\"").unwrap(),
+            vec!["Doe, John", "say \"hi\""]
+        );
+        // What escape_field writes reads back unchanged
+        let fields = ["plain", "Doe, John", "say \"hi\""];
+        let line = csv_line(&fields);
+        assert_eq!(parse_csv_line(&line).unwrap(), fields);
+
+        assert!(parse_csv_line("\"open").is_err());
+        assert!(parse_csv_line("\"closed\"x").is_err());
+        assert!(parse_csv_line("in\"side").is_err());
+    }
+
+    #[test]
+    fn test_wants_csv() {
+        let mut headers = HeaderMap::new();
+        assert!(!wants_csv(None, &headers));
+        assert!(wants_csv(Some("csv"), &headers));
+
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("text/csv;q=0.9, */*"),
+        );
+        assert!(wants_csv(None, &headers));
+        // An explicit format wins over the Accept header
+        assert!(!wants_csv(Some("json"), &headers));
+    }
+}
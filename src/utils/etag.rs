@@ -0,0 +1,117 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use axum::http::{HeaderMap, header};
+
+use crate::entities::booking;
+use crate::error::{AppError, AppResult};
+
+/// Strong ETag for a booking, derived from its `updated_at` version marker
+pub fn booking_etag(booking: &booking::Model) -> String {
+    format!("\"{}\"", booking.updated_at.timestamp_micros())
+}
+
+/// Weak ETag for a response body; equal bodies get equal tags within a build
+pub fn weak_etag(body: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    format!("W/\"{:016x}\"", hasher.finish())
+}
+
+/// Whether an `If-None-Match` header lists `current_etag`, compared weakly (ignoring `W/`)
+pub fn if_none_match(headers: &HeaderMap, current_etag: &str) -> bool {
+    let Some(value) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+
+    let current = current_etag.trim_start_matches("W/");
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == current)
+}
+
+/// Check the required `If-Match` header against the current ETag.
+/// A missing header is rejected with 428; one matching none of the listed tags with 412.
+pub fn check_if_match(headers: &HeaderMap, current_etag: &str) -> AppResult<()> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Err(AppError::PreconditionRequired(
+            "If-Match header is required".to_string(),
+        ));
+    };
+
+    let value = value
+        .to_str()
+        .map_err(|_| AppError::BadRequest("Invalid If-Match header".to_string()))?;
+
+    let matches = value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag == current_etag);
+
+    if !matches {
+        return Err(AppError::PreconditionFailed(
+            "Booking was modified by another request".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderValue, StatusCode};
+    use axum::response::IntoResponse;
+
+    #[test]
+    fn test_missing_if_match_yields_428() {
+        let err = check_if_match(&HeaderMap::new(), "\"1\"").unwrap_err();
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::PRECONDITION_REQUIRED
+        );
+    }
+
+    #[test]
+    fn test_matching_if_match_passes() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("\"1\""));
+        assert!(check_if_match(&headers, "\"1\"").is_ok());
+    }
+
+    #[test]
+    fn test_weak_etag_follows_body() {
+        let etag = weak_etag(b"[1,2]");
+        assert!(etag.starts_with("W/\""), "{}", etag);
+        assert_eq!(etag, weak_etag(b"[1,2]"));
+        assert_ne!(etag, weak_etag(b"[1,3]"));
+    }
+
+    #[test]
+    fn test_if_none_match_compares_weakly() {
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match(&headers, "W/\"1\""));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_static("\"0\", \"1\""),
+        );
+        assert!(if_none_match(&headers, "W/\"1\""));
+        assert!(!if_none_match(&headers, "W/\"2\""));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(if_none_match(&headers, "W/\"2\""));
+    }
+
+    #[test]
+    fn test_stale_if_match_yields_412() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("\"1\""));
+
+        let err = check_if_match(&headers, "\"2\"").unwrap_err();
+        assert_eq!(err.into_response().status(), StatusCode::PRECONDITION_FAILED);
+    }
+}
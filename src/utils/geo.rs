@@ -1,3 +1,5 @@
+use crate::entities::city;
+
 /// Calculate distance between two coordinates using Haversine formula
 /// Returns distance in kilometers
 pub fn haversine_distance(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
@@ -26,10 +28,98 @@ pub fn is_within_radius(
     haversine_distance(pickup_lat, pickup_lng, center_lat, center_lng) <= max_radius_km
 }
 
+/// Check if a point lies inside a polygon of `(lat, lng)` vertices, using ray casting.
+/// Points on an edge count as inside. Coordinates are treated as planar, which is
+/// accurate enough for city-sized areas away from the antimeridian.
+pub fn point_in_polygon(lat: f64, lng: f64, polygon: &[(f64, f64)]) -> bool {
+    const EPSILON: f64 = 1e-9;
+
+    if polygon.len() < 3 {
+        return false;
+    }
+
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (lat_i, lng_i) = polygon[i];
+        let (lat_j, lng_j) = polygon[j];
+
+        // On the segment: collinear and within its bounding box
+        let cross = (lng_j - lng_i) * (lat - lat_i) - (lat_j - lat_i) * (lng - lng_i);
+        if cross.abs() < EPSILON
+            && lat >= lat_i.min(lat_j) - EPSILON
+            && lat <= lat_i.max(lat_j) + EPSILON
+            && lng >= lng_i.min(lng_j) - EPSILON
+            && lng <= lng_i.max(lng_j) + EPSILON
+        {
+            return true;
+        }
+
+        // Cast a ray towards increasing longitude and count the edges it crosses
+        if (lat_i > lat) != (lat_j > lat)
+            && lng < (lng_j - lng_i) * (lat - lat_i) / (lat_j - lat_i) + lng_i
+        {
+            inside = !inside;
+        }
+
+        j = i;
+    }
+
+    inside
+}
+
+/// Find the city whose center is closest to a point, with the distance in kilometers.
+/// Equidistant cities resolve to the lowest ID so the answer is stable.
+pub fn nearest_city(lat: f64, lng: f64, cities: &[city::Model]) -> Option<(&city::Model, f64)> {
+    cities
+        .iter()
+        .map(|c| (c, haversine_distance(lat, lng, c.center_lat, c.center_lng)))
+        .min_by(|(a, da), (b, db)| da.total_cmp(db).then(a.id.cmp(&b.id)))
+}
+
+/// Group `(lat, lng)` points so every pair within a group is at most `radius_km` apart.
+/// Greedy: each point, in order, joins the first group it fits, or starts a new one.
+/// Returns groups of indices into `points`, in order of their first member.
+pub fn cluster_points(points: &[(f64, f64)], radius_km: f64) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+
+    for (i, &(lat, lng)) in points.iter().enumerate() {
+        let fits = |cluster: &&mut Vec<usize>| {
+            cluster.iter().all(|&j| {
+                let (other_lat, other_lng) = points[j];
+                haversine_distance(lat, lng, other_lat, other_lng) <= radius_km
+            })
+        };
+
+        match clusters.iter_mut().find(fits) {
+            Some(cluster) => cluster.push(i),
+            None => clusters.push(vec![i]),
+        }
+    }
+
+    clusters
+}
+
+/// Mean latitude and longitude of a set of nearby points
+pub fn centroid(points: &[(f64, f64)]) -> (f64, f64) {
+    let n = points.len() as f64;
+    let (lat, lng) = points
+        .iter()
+        .fold((0.0, 0.0), |(lat, lng), p| (lat + p.0, lng + p.1));
+    (lat / n, lng / n)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn city(id: i32, name: &str, lat: f64, lng: f64) -> city::Model {
+        city::Model {
+            id,
+            ..city::Model::test_at(name, lat, lng)
+        }
+    }
+
     #[test]
     fn test_haversine_jakarta_bandung() {
         // Jakarta center
@@ -52,4 +142,118 @@ mod tests {
         let far = (-6.9175, 107.6191);    // Bandung
         assert!(!is_within_radius(far.0, far.1, center.0, center.1, 10.0));
     }
+
+    #[test]
+    fn test_point_in_polygon() {
+        // Square around Kupang's center
+        let square = [
+            (-10.3, 123.5),
+            (-10.3, 123.7),
+            (-10.1, 123.7),
+            (-10.1, 123.5),
+        ];
+
+        assert!(point_in_polygon(-10.2, 123.6, &square));
+        assert!(!point_in_polygon(-10.2, 123.8, &square));
+        assert!(!point_in_polygon(-10.0, 123.6, &square));
+    }
+
+    #[test]
+    fn test_point_in_polygon_concave() {
+        // L shape: the notch at the top right is outside
+        let shape = [
+            (0.0, 0.0),
+            (0.0, 2.0),
+            (1.0, 2.0),
+            (1.0, 1.0),
+            (2.0, 1.0),
+            (2.0, 0.0),
+        ];
+
+        assert!(point_in_polygon(0.5, 1.5, &shape));
+        assert!(point_in_polygon(1.5, 0.5, &shape));
+        assert!(!point_in_polygon(1.5, 1.5, &shape));
+    }
+
+    #[test]
+    fn test_point_on_polygon_edge() {
+        let square = [
+            (-10.3, 123.5),
+            (-10.3, 123.7),
+            (-10.1, 123.7),
+            (-10.1, 123.5),
+        ];
+
+        // Edges and vertices count as inside
+        assert!(point_in_polygon(-10.3, 123.6, &square));
+        assert!(point_in_polygon(-10.2, 123.7, &square));
+        assert!(point_in_polygon(-10.1, 123.5, &square));
+    }
+
+    #[test]
+    fn test_point_in_degenerate_polygon() {
+        assert!(!point_in_polygon(0.0, 0.0, &[]));
+        assert!(!point_in_polygon(0.0, 0.0, &[(0.0, 0.0), (1.0, 1.0)]));
+    }
+
+    #[test]
+    fn test_nearest_city_jakarta_bandung() {
+        let cities = [
+            city(1, "Jakarta", -6.2088, 106.8456),
+            city(2, "Bandung", -6.9175, 107.6191),
+        ];
+
+        // Bogor sits between the two, closer to Jakarta
+        let (nearest, distance) = nearest_city(-6.5971, 106.8060, &cities).unwrap();
+        assert_eq!(nearest.name, "Jakarta");
+        assert!(distance > 40.0 && distance < 50.0);
+
+        let (nearest, distance) = nearest_city(-6.92, 107.62, &cities).unwrap();
+        assert_eq!(nearest.name, "Bandung");
+        assert!(distance < 1.0);
+    }
+
+    #[test]
+    fn test_nearest_city_tie_goes_to_lowest_id() {
+        // Two cities mirrored around the equator are equidistant from it
+        let cities = [city(7, "North", 1.0, 120.0), city(3, "South", -1.0, 120.0)];
+
+        let (nearest, _) = nearest_city(0.0, 120.0, &cities).unwrap();
+        assert_eq!(nearest.id, 3);
+    }
+
+    #[test]
+    fn test_nearest_city_without_cities() {
+        assert!(nearest_city(0.0, 0.0, &[]).is_none());
+    }
+
+    #[test]
+    fn test_cluster_points() {
+        let points = [
+            (-10.1700, 123.6000), // Kupang centre
+            (-9.8600, 124.2800),  // Soe, ~80 km away
+            (-10.1750, 123.6050), // ~0.8 km from the first
+            (-9.8620, 124.2830),  // ~0.4 km from Soe
+            (-10.1800, 123.6100), // ~0.8 km from the third, ~1.6 km from the first
+        ];
+
+        assert_eq!(
+            cluster_points(&points, 1.0),
+            vec![vec![0, 2], vec![1, 3], vec![4]]
+        );
+        assert_eq!(
+            cluster_points(&points, 2.0),
+            vec![vec![0, 2, 4], vec![1, 3]]
+        );
+        assert_eq!(cluster_points(&points, 0.1).len(), 5);
+        assert_eq!(cluster_points(&points, 200.0), vec![vec![0, 1, 2, 3, 4]]);
+        assert!(cluster_points(&[], 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_centroid() {
+        let (lat, lng) = centroid(&[(-10.0, 123.0), (-10.2, 123.4)]);
+        assert!((lat - -10.1).abs() < 1e-9);
+        assert!((lng - 123.2).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+
+use crate::error::{AppError, AppResult};
+
+/// Looks up coordinates by place name, so admins don't have to enter city centers by hand
+#[async_trait]
+pub trait Geocoder: Send + Sync {
+    /// `(latitude, longitude)` of the place called `name`
+    async fn geocode(&self, name: &str) -> AppResult<(f64, f64)>;
+}
+
+/// Used when no geocoding service is configured; every lookup is refused
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoGeocoder;
+
+#[async_trait]
+impl Geocoder for NoGeocoder {
+    async fn geocode(&self, name: &str) -> AppResult<(f64, f64)> {
+        tracing::debug!(
+            "Geocoding requested for {} but no geocoder is configured",
+            name
+        );
+        Err(AppError::BadRequest(
+            "Geocoding is not configured".to_string(),
+        ))
+    }
+}
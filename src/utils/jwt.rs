@@ -3,50 +3,415 @@ use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation}
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::config::Config;
 use crate::entities::user::UserRole;
 use crate::error::{AppError, AppResult};
 
+/// Signing secret plus the issuer and audience every token must carry
+#[derive(Debug, Clone, Copy)]
+pub struct JwtKeys<'a> {
+    pub secret: &'a str,
+    pub issuer: &'a str,
+    pub audience: &'a str,
+}
+
+impl<'a> From<&'a Config> for JwtKeys<'a> {
+    fn from(config: &'a Config) -> Self {
+        Self {
+            secret: &config.jwt_secret,
+            issuer: &config.jwt_issuer,
+            audience: &config.jwt_audience,
+        }
+    }
+}
+
+/// Distinguishes short-lived access tokens from long-lived refresh tokens
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    #[default]
+    Access,
+    Refresh,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: Uuid,       // user id
     pub email: String,
+    #[serde(default)]
+    pub name: String,    // display name when the token was issued
     pub role: UserRole,
     pub exp: i64,        // expiration timestamp
     pub iat: i64,        // issued at timestamp
+    #[serde(default)]
+    pub nbf: i64,        // not valid before this timestamp
+    pub jti: Uuid,       // unique token id, used for revocation
+    pub iss: String,
+    pub aud: String,
+    #[serde(default)]
+    pub token_type: TokenType,
 }
 
-pub fn create_token(
+fn sign(
     user_id: Uuid,
     email: &str,
+    name: &str,
     role: UserRole,
-    secret: &str,
-    expiration_hours: i64,
+    keys: JwtKeys,
+    token_type: TokenType,
+    lifetime: Duration,
 ) -> AppResult<String> {
     let now = Utc::now();
-    let exp = now + Duration::hours(expiration_hours);
+    let exp = now + lifetime;
 
     let claims = Claims {
         sub: user_id,
         email: email.to_string(),
+        name: name.to_string(),
         role,
         exp: exp.timestamp(),
         iat: now.timestamp(),
+        nbf: now.timestamp(),
+        jti: Uuid::new_v4(),
+        iss: keys.issuer.to_string(),
+        aud: keys.audience.to_string(),
+        token_type,
     };
 
     encode(
         &Header::default(),
         &claims,
-        &EncodingKey::from_secret(secret.as_bytes()),
+        &EncodingKey::from_secret(keys.secret.as_bytes()),
     )
     .map_err(|e| AppError::Internal(format!("Failed to create token: {}", e)))
 }
 
-pub fn verify_token(token: &str, secret: &str) -> AppResult<Claims> {
+pub fn create_token(
+    user_id: Uuid,
+    email: &str,
+    name: &str,
+    role: UserRole,
+    keys: JwtKeys,
+    expiration_hours: i64,
+) -> AppResult<String> {
+    sign(
+        user_id,
+        email,
+        name,
+        role,
+        keys,
+        TokenType::Access,
+        Duration::hours(expiration_hours),
+    )
+}
+
+/// Create a long-lived token that can only be exchanged for new access tokens
+pub fn create_refresh_token(
+    user_id: Uuid,
+    email: &str,
+    name: &str,
+    role: UserRole,
+    keys: JwtKeys,
+    expiration_days: i64,
+) -> AppResult<String> {
+    sign(
+        user_id,
+        email,
+        name,
+        role,
+        keys,
+        TokenType::Refresh,
+        Duration::days(expiration_days),
+    )
+}
+
+/// Verify signature, expiry and not-before, and that the token was issued by and for this service
+pub fn verify_token(token: &str, keys: JwtKeys) -> AppResult<Claims> {
+    let mut validation = Validation::default();
+    validation.validate_nbf = true;
+    validation.set_issuer(&[keys.issuer]);
+    validation.set_audience(&[keys.audience]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+
     decode::<Claims>(
         token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &Validation::default(),
+        &DecodingKey::from_secret(keys.secret.as_bytes()),
+        &validation,
     )
     .map(|data| data.claims)
     .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))
 }
+
+/// Verify a token and require it to be an access token
+pub fn verify_access_token(token: &str, keys: JwtKeys) -> AppResult<Claims> {
+    let claims = verify_token(token, keys)?;
+    if claims.token_type != TokenType::Access {
+        return Err(AppError::Unauthorized(
+            "Refresh tokens cannot be used for API access".to_string(),
+        ));
+    }
+    Ok(claims)
+}
+
+/// Verify a token and require it to be a refresh token
+pub fn verify_refresh_token(token: &str, keys: JwtKeys) -> AppResult<Claims> {
+    let claims = verify_token(token, keys)?;
+    if claims.token_type != TokenType::Refresh {
+        return Err(AppError::Unauthorized("Not a refresh token".to_string()));
+    }
+    Ok(claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYS: JwtKeys = JwtKeys {
+        secret: "test-secret",
+        issuer: "bus-travel-backend",
+        audience: "bus-travel-api",
+    };
+
+    #[test]
+    fn test_valid_refresh_token() {
+        let user_id = Uuid::new_v4();
+        let token =
+            create_refresh_token(user_id, "a@b.com", "Ann", UserRole::Traveller, KEYS, 30).unwrap();
+
+        let claims = verify_refresh_token(&token, KEYS).unwrap();
+        assert_eq!(claims.sub, user_id);
+        assert_eq!(claims.token_type, TokenType::Refresh);
+    }
+
+    #[test]
+    fn test_expired_refresh_token_rejected() {
+        let token = create_refresh_token(
+            Uuid::new_v4(),
+            "a@b.com",
+            "Ann",
+            UserRole::Traveller,
+            KEYS,
+            -1,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            verify_refresh_token(&token, KEYS),
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_access_token_is_not_a_refresh_token() {
+        let token = create_token(
+            Uuid::new_v4(),
+            "a@b.com",
+            "Ann",
+            UserRole::Traveller,
+            KEYS,
+            24,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            verify_refresh_token(&token, KEYS),
+            Err(AppError::Unauthorized(_))
+        ));
+        assert!(verify_access_token(&token, KEYS).is_ok());
+    }
+
+    #[test]
+    fn test_refresh_token_is_not_an_access_token() {
+        let token = create_refresh_token(
+            Uuid::new_v4(),
+            "a@b.com",
+            "Ann",
+            UserRole::Traveller,
+            KEYS,
+            30,
+        )
+        .unwrap();
+
+        assert!(verify_access_token(&token, KEYS).is_err());
+    }
+
+    #[test]
+    fn test_token_for_another_audience_rejected() {
+        let other_service = JwtKeys {
+            audience: "another-api",
+            ..KEYS
+        };
+        let token = create_token(
+            Uuid::new_v4(),
+            "a@b.com",
+            "Ann",
+            UserRole::Traveller,
+            other_service,
+            24,
+        )
+        .unwrap();
+
+        assert!(verify_token(&token, other_service).is_ok());
+        assert!(matches!(
+            verify_token(&token, KEYS),
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_token_from_another_issuer_rejected() {
+        let other_issuer = JwtKeys {
+            issuer: "someone-else",
+            ..KEYS
+        };
+        let token = create_token(
+            Uuid::new_v4(),
+            "a@b.com",
+            "Ann",
+            UserRole::Traveller,
+            other_issuer,
+            24,
+        )
+        .unwrap();
+
+        assert!(verify_access_token(&token, KEYS).is_err());
+    }
+
+    #[test]
+    fn test_token_without_audience_rejected() {
+        #[derive(Serialize)]
+        struct Bare {
+            sub: Uuid,
+            email: String,
+            role: UserRole,
+            exp: i64,
+            iat: i64,
+            jti: Uuid,
+            iss: String,
+        }
+
+        let now = Utc::now();
+        let token = encode(
+            &Header::default(),
+            &Bare {
+                sub: Uuid::new_v4(),
+                email: "a@b.com".to_string(),
+                role: UserRole::Traveller,
+                exp: (now + Duration::hours(1)).timestamp(),
+                iat: now.timestamp(),
+                jti: Uuid::new_v4(),
+                iss: KEYS.issuer.to_string(),
+            },
+            &EncodingKey::from_secret(KEYS.secret.as_bytes()),
+        )
+        .unwrap();
+
+        assert!(verify_token(&token, KEYS).is_err());
+    }
+
+    #[test]
+    fn test_token_before_nbf_rejected() {
+        let now = Utc::now();
+        let sign_with_nbf = |nbf: i64| {
+            let claims = Claims {
+                sub: Uuid::new_v4(),
+                email: "a@b.com".to_string(),
+                name: "Ann".to_string(),
+                role: UserRole::Traveller,
+                exp: (now + Duration::hours(2)).timestamp(),
+                iat: now.timestamp(),
+                nbf,
+                jti: Uuid::new_v4(),
+                iss: KEYS.issuer.to_string(),
+                aud: KEYS.audience.to_string(),
+                token_type: TokenType::Access,
+            };
+            encode(
+                &Header::default(),
+                &claims,
+                &EncodingKey::from_secret(KEYS.secret.as_bytes()),
+            )
+            .unwrap()
+        };
+
+        // Past the default one-minute leeway
+        let future = sign_with_nbf((now + Duration::hours(1)).timestamp());
+        assert!(matches!(
+            verify_token(&future, KEYS),
+            Err(AppError::Unauthorized(_))
+        ));
+
+        let active = sign_with_nbf(now.timestamp());
+        assert!(verify_token(&active, KEYS).is_ok());
+    }
+
+    #[test]
+    fn test_issued_token_has_nbf() {
+        let token = create_token(
+            Uuid::new_v4(),
+            "a@b.com",
+            "Ann",
+            UserRole::Traveller,
+            KEYS,
+            24,
+        )
+        .unwrap();
+
+        let claims = verify_access_token(&token, KEYS).unwrap();
+        assert_eq!(claims.nbf, claims.iat);
+    }
+
+    #[test]
+    fn test_issued_token_carries_name() {
+        let token = create_token(
+            Uuid::new_v4(),
+            "a@b.com",
+            "Ann Lee",
+            UserRole::Driver,
+            KEYS,
+            24,
+        )
+        .unwrap();
+
+        let claims = verify_access_token(&token, KEYS).unwrap();
+        assert_eq!(claims.name, "Ann Lee");
+        assert_eq!(claims.email, "a@b.com");
+        assert_eq!(claims.role, UserRole::Driver);
+    }
+
+    #[test]
+    fn test_token_without_name_still_accepted() {
+        // Shape of tokens issued before the name claim existed
+        #[derive(Serialize)]
+        struct Legacy {
+            sub: Uuid,
+            email: String,
+            role: UserRole,
+            exp: i64,
+            iat: i64,
+            jti: Uuid,
+            iss: String,
+            aud: String,
+        }
+
+        let now = Utc::now();
+        let token = encode(
+            &Header::default(),
+            &Legacy {
+                sub: Uuid::new_v4(),
+                email: "a@b.com".to_string(),
+                role: UserRole::Traveller,
+                exp: (now + Duration::hours(1)).timestamp(),
+                iat: now.timestamp(),
+                jti: Uuid::new_v4(),
+                iss: KEYS.issuer.to_string(),
+                aud: KEYS.audience.to_string(),
+            },
+            &EncodingKey::from_secret(KEYS.secret.as_bytes()),
+        )
+        .unwrap();
+
+        let claims = verify_access_token(&token, KEYS).unwrap();
+        assert_eq!(claims.name, "");
+    }
+}
@@ -1,2 +1,13 @@
+pub mod csv;
+pub mod etag;
 pub mod geo;
+pub mod geocode;
+pub mod google;
 pub mod jwt;
+pub mod notify;
+pub mod pagination;
+pub mod password;
+pub mod reference;
+pub mod sort;
+pub mod time;
+pub mod validation;
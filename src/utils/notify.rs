@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::handlers::traveller::BookingResponse;
+
+/// Outbound messages to users. Implementations handle their own delivery failures;
+/// a notification never fails the request that triggered it.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// A booking was created; `booking.status` tells a confirmed seat from a waitlist place
+    async fn booking_confirmed(&self, to: &str, booking: &BookingResponse);
+
+    /// The journey a booking is on now departs at `new_departure` instead of `old_departure`
+    async fn journey_rescheduled(
+        &self,
+        to: &str,
+        booking_id: Uuid,
+        old_departure: DateTime<Utc>,
+        new_departure: DateTime<Utc>,
+    );
+}
+
+/// Writes notifications to the log instead of sending them
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    async fn booking_confirmed(&self, to: &str, booking: &BookingResponse) {
+        tracing::info!(
+            "Booking {} ({:?}) for {}: {} seat(s), {} -> {} departing {}",
+            booking.id,
+            booking.status,
+            to,
+            booking.seats,
+            booking.origin_city,
+            booking.destination_city,
+            booking.departure_time_local
+        );
+    }
+
+    async fn journey_rescheduled(
+        &self,
+        to: &str,
+        booking_id: Uuid,
+        old_departure: DateTime<Utc>,
+        new_departure: DateTime<Utc>,
+    ) {
+        tracing::info!(
+            "Booking {} for {}: departure moved from {} to {}",
+            booking_id,
+            to,
+            old_departure,
+            new_departure
+        );
+    }
+}
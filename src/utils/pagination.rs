@@ -0,0 +1,151 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+
+const DEFAULT_PER_PAGE: u64 = 20;
+const MAX_PER_PAGE: u64 = 100;
+
+/// `?page=&per_page=` query parameters (1-based pages)
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct Pagination {
+    pub page: Option<u64>,
+    pub per_page: Option<u64>,
+}
+
+impl Pagination {
+    /// Requested page, at least 1
+    pub fn page(&self) -> u64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    /// Requested page size, between 1 and `MAX_PER_PAGE`
+    pub fn per_page(&self) -> u64 {
+        self.per_page
+            .unwrap_or(DEFAULT_PER_PAGE)
+            .clamp(1, MAX_PER_PAGE)
+    }
+}
+
+/// A page of results plus the total number of matching items
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub page: u64,
+    pub per_page: u64,
+}
+
+/// `?after=&limit=` query parameters for keyset pagination
+#[derive(Debug, Default, Deserialize)]
+pub struct CursorQuery {
+    /// `next_cursor` from the previous page; omitted for the first page
+    pub after: Option<String>,
+    pub limit: Option<u64>,
+}
+
+impl CursorQuery {
+    /// Requested page size, between 1 and `MAX_PER_PAGE`
+    pub fn limit(&self) -> u64 {
+        self.limit
+            .unwrap_or(DEFAULT_PER_PAGE)
+            .clamp(1, MAX_PER_PAGE)
+    }
+
+    /// The decoded `after` cursor, if any
+    pub fn cursor(&self) -> AppResult<Option<Cursor>> {
+        self.after.as_deref().map(Cursor::parse).transpose()
+    }
+}
+
+/// Position of the last item on a page: its sort timestamp and ID, as `<timestamp>,<id>`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cursor {
+    pub timestamp: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn parse(raw: &str) -> AppResult<Self> {
+        let invalid = || AppError::BadRequest("Invalid cursor".to_string());
+        let (timestamp, id) = raw.split_once(',').ok_or_else(invalid)?;
+
+        Ok(Self {
+            timestamp: DateTime::parse_from_rfc3339(timestamp.trim())
+                .map_err(|_| invalid())?
+                .with_timezone(&Utc),
+            id: Uuid::parse_str(id.trim()).map_err(|_| invalid())?,
+        })
+    }
+
+    /// Microsecond precision matches Postgres timestamps, so the cursor round-trips exactly
+    pub fn encode(&self) -> String {
+        format!(
+            "{},{}",
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+            self.id
+        )
+    }
+}
+
+/// A page of results plus the cursor for the next one, `None` on the last page
+#[derive(Debug, Serialize)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pagination_defaults() {
+        let p = Pagination::default();
+        assert_eq!(p.page(), 1);
+        assert_eq!(p.per_page(), DEFAULT_PER_PAGE);
+    }
+
+    #[test]
+    fn test_pagination_clamps() {
+        let p = Pagination {
+            page: Some(0),
+            per_page: Some(1000),
+        };
+        assert_eq!(p.page(), 1);
+        assert_eq!(p.per_page(), MAX_PER_PAGE);
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor {
+            timestamp: DateTime::parse_from_rfc3339("2026-10-15T08:30:00.123456Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            id: Uuid::new_v4(),
+        };
+
+        let encoded = cursor.encode();
+        assert!(encoded.starts_with("2026-10-15T08:30:00.123456Z,"));
+        assert_eq!(Cursor::parse(&encoded).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_invalid_cursor_rejected() {
+        for raw in [
+            "",
+            "2026-10-15T08:30:00Z",
+            "yesterday,abc",
+            "2026-10-15T08:30:00Z,abc",
+        ] {
+            assert!(
+                matches!(Cursor::parse(raw), Err(AppError::BadRequest(_))),
+                "{}",
+                raw
+            );
+        }
+    }
+}
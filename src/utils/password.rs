@@ -0,0 +1,137 @@
+use argon2::{
+    Algorithm, Argon2, Params, Version,
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+};
+
+use crate::error::{AppError, AppResult};
+
+/// Argon2id cost parameters new password hashes are made with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashCost {
+    /// Memory per hash in KiB
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for HashCost {
+    /// The argon2 crate's recommended defaults
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl HashCost {
+    /// Argon2 parameters for this cost, or why the combination is invalid
+    pub fn params(&self) -> Result<Params, argon2::Error> {
+        Params::new(self.memory_kib, self.iterations, self.parallelism, None)
+    }
+}
+
+/// Hash a password with Argon2id at `cost` and a random salt
+pub fn hash_password(password: &str, cost: &HashCost) -> AppResult<String> {
+    let params = cost
+        .params()
+        .map_err(|e| AppError::Internal(format!("Invalid password hash cost: {}", e)))?;
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AppError::Internal(format!("Failed to hash password: {}", e)))
+}
+
+/// Check a password against a stored Argon2 hash, using the parameters recorded in the hash
+pub fn password_matches(password: &str, password_hash: &str) -> AppResult<bool> {
+    let parsed_hash = PasswordHash::new(password_hash)
+        .map_err(|e| AppError::Internal(format!("Failed to parse password hash: {}", e)))?;
+
+    Ok(Argon2::default()
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .is_ok())
+}
+
+/// Whether a stored hash was made with a different algorithm, an older version, or any
+/// parameter below `cost`, and should be replaced once the password is known
+pub fn needs_rehash(password_hash: &str, cost: &HashCost) -> AppResult<bool> {
+    let parsed_hash = PasswordHash::new(password_hash)
+        .map_err(|e| AppError::Internal(format!("Failed to parse password hash: {}", e)))?;
+
+    if Algorithm::try_from(parsed_hash.algorithm) != Ok(Algorithm::Argon2id)
+        || parsed_hash.version != Some(Version::V0x13.into())
+    {
+        return Ok(true);
+    }
+
+    let params = Params::try_from(&parsed_hash)
+        .map_err(|e| AppError::Internal(format!("Failed to read password hash cost: {}", e)))?;
+    Ok(params.m_cost() < cost.memory_kib
+        || params.t_cost() < cost.iterations
+        || params.p_cost() < cost.parallelism)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap enough to keep the tests fast
+    const LOW: HashCost = HashCost {
+        memory_kib: 1024,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[test]
+    fn test_hash_round_trip() {
+        let hash = hash_password("correct horse", &LOW).unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(password_matches("correct horse", &hash).unwrap());
+        assert!(!password_matches("wrong horse", &hash).unwrap());
+    }
+
+    #[test]
+    fn test_weaker_hash_needs_rehash() {
+        let hash = hash_password("correct horse", &LOW).unwrap();
+        assert!(!needs_rehash(&hash, &LOW).unwrap());
+
+        let more_memory = HashCost {
+            memory_kib: 2048,
+            ..LOW
+        };
+        let more_iterations = HashCost {
+            iterations: 2,
+            ..LOW
+        };
+        assert!(needs_rehash(&hash, &more_memory).unwrap());
+        assert!(needs_rehash(&hash, &more_iterations).unwrap());
+
+        // A stronger hash is left alone when the configured cost is lowered
+        let strong = hash_password("correct horse", &more_memory).unwrap();
+        assert!(!needs_rehash(&strong, &LOW).unwrap());
+    }
+
+    #[test]
+    fn test_other_algorithms_need_rehash() {
+        let salt = SaltString::generate(&mut OsRng);
+        let argon2i = Argon2::new(Algorithm::Argon2i, Version::V0x13, LOW.params().unwrap())
+            .hash_password(b"correct horse", &salt)
+            .unwrap()
+            .to_string();
+
+        assert!(password_matches("correct horse", &argon2i).unwrap());
+        assert!(needs_rehash(&argon2i, &LOW).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_cost_is_rejected() {
+        let cost = HashCost {
+            iterations: 0,
+            ..LOW
+        };
+        assert!(cost.params().is_err());
+        assert!(hash_password("correct horse", &cost).is_err());
+    }
+}
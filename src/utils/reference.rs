@@ -0,0 +1,62 @@
+use rand::Rng;
+
+/// Crockford base32: digits and capitals without I, L, O and U, so codes survive being
+/// read aloud or copied by hand
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+pub const REFERENCE_CODE_LEN: usize = 6;
+
+/// A random booking reference such as `7K3QZ9`
+pub fn generate_reference_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..REFERENCE_CODE_LEN)
+        .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())] as char)
+        .collect()
+}
+
+/// Canonical form of a code typed by a person: trimmed, upper case, and with the letters
+/// Crockford base32 leaves out read as the digits they resemble. `None` if it can't be a code.
+pub fn normalize_reference_code(code: &str) -> Option<String> {
+    let code: String = code
+        .trim()
+        .chars()
+        .map(|c| match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        })
+        .collect();
+
+    (code.len() == REFERENCE_CODE_LEN && code.bytes().all(|b| ALPHABET.contains(&b)))
+        .then_some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_codes_are_well_formed() {
+        for _ in 0..1000 {
+            let code = generate_reference_code();
+            assert_eq!(code.len(), REFERENCE_CODE_LEN);
+            assert_eq!(normalize_reference_code(&code), Some(code));
+        }
+    }
+
+    #[test]
+    fn test_normalize_reference_code() {
+        assert_eq!(
+            normalize_reference_code(" a1b2c3 "),
+            Some("A1B2C3".to_string())
+        );
+        assert_eq!(
+            normalize_reference_code("OIL234"),
+            Some("011234".to_string())
+        );
+        assert_eq!(normalize_reference_code("A1B2C"), None);
+        assert_eq!(normalize_reference_code("A1B2C3D"), None);
+        assert_eq!(normalize_reference_code("A1B2U3"), None);
+        assert_eq!(normalize_reference_code("A1-2C3"), None);
+    }
+}
@@ -0,0 +1,86 @@
+use sea_orm::Order;
+use serde::Deserialize;
+
+use crate::error::{AppError, AppResult};
+
+/// `?sort=` query parameter, e.g. `created_at_desc`
+#[derive(Debug, Default, Deserialize)]
+pub struct SortQuery {
+    pub sort: Option<String>,
+}
+
+/// A sort key accepted by a list endpoint and the ordering it maps to
+pub struct SortOption<C> {
+    pub key: &'static str,
+    pub column: C,
+    pub descending: bool,
+}
+
+impl SortQuery {
+    /// Resolve the requested key against `options`; the first option is the default.
+    /// Unknown keys are rejected rather than silently ignored.
+    pub fn resolve<C: Copy>(&self, options: &[SortOption<C>]) -> AppResult<(C, Order)> {
+        let option = match self.sort.as_deref() {
+            None => options.first(),
+            Some(key) => options.iter().find(|o| o.key == key),
+        }
+        .ok_or_else(|| {
+            let keys: Vec<_> = options.iter().map(|o| o.key).collect();
+            AppError::BadRequest(format!(
+                "Unknown sort key; expected one of: {}",
+                keys.join(", ")
+            ))
+        })?;
+
+        let order = if option.descending {
+            Order::Desc
+        } else {
+            Order::Asc
+        };
+
+        Ok((option.column, order))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPTIONS: [SortOption<u8>; 2] = [
+        SortOption {
+            key: "created_at_desc",
+            column: 1,
+            descending: true,
+        },
+        SortOption {
+            key: "created_at_asc",
+            column: 1,
+            descending: false,
+        },
+    ];
+
+    fn query(sort: Option<&str>) -> SortQuery {
+        SortQuery {
+            sort: sort.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_default_is_first_option() {
+        let (column, order) = query(None).resolve(&OPTIONS).unwrap();
+        assert_eq!(column, 1);
+        assert!(matches!(order, Order::Desc));
+    }
+
+    #[test]
+    fn test_known_key() {
+        let (_, order) = query(Some("created_at_asc")).resolve(&OPTIONS).unwrap();
+        assert!(matches!(order, Order::Asc));
+    }
+
+    #[test]
+    fn test_unknown_key_rejected() {
+        let err = query(Some("password_desc")).resolve(&OPTIONS).unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(msg) if msg.contains("created_at_asc")));
+    }
+}
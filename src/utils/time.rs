@@ -0,0 +1,88 @@
+use chrono::{DateTime, FixedOffset, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+/// Parse an IANA timezone name (e.g. "Asia/Jakarta"), falling back to UTC if unknown
+pub fn parse_timezone(name: &str) -> Tz {
+    name.parse().unwrap_or_else(|_| {
+        tracing::warn!("Unknown timezone '{}', falling back to UTC", name);
+        Tz::UTC
+    })
+}
+
+/// Convert a UTC instant to the local time of day in the given timezone
+pub fn local_time_of_day(instant: DateTime<Utc>, tz: Tz) -> NaiveTime {
+    instant.with_timezone(&tz).time()
+}
+
+/// Express a UTC instant in an IANA timezone, keeping the offset (e.g. `+07:00`) when serialized
+pub fn to_local_time(instant: DateTime<Utc>, timezone: &str) -> DateTime<FixedOffset> {
+    instant
+        .with_timezone(&parse_timezone(timezone))
+        .fixed_offset()
+}
+
+/// Check if a local time of day falls within an operating window (inclusive).
+/// A window whose end is before its start wraps past midnight (e.g. 22:00-04:00).
+/// If either bound is missing the city operates 24/7.
+pub fn is_within_operating_hours(
+    local: NaiveTime,
+    start: Option<NaiveTime>,
+    end: Option<NaiveTime>,
+) -> bool {
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end => local >= start && local <= end,
+        (Some(start), Some(end)) => local >= start || local <= end,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn hm(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_departure_within_operating_hours() {
+        // 23:30 UTC is 06:30 the next day in Asia/Jakarta (UTC+7)
+        let departure = Utc.with_ymd_and_hms(2024, 1, 14, 23, 30, 0).unwrap();
+        let local = local_time_of_day(departure, parse_timezone("Asia/Jakarta"));
+
+        assert_eq!(local, hm(6, 30));
+        assert!(is_within_operating_hours(local, Some(hm(6, 0)), Some(hm(20, 0))));
+    }
+
+    #[test]
+    fn test_departure_outside_operating_hours() {
+        // 14:00 UTC is 21:00 in Asia/Jakarta
+        let departure = Utc.with_ymd_and_hms(2024, 1, 15, 14, 0, 0).unwrap();
+        let local = local_time_of_day(departure, parse_timezone("Asia/Jakarta"));
+
+        assert!(!is_within_operating_hours(local, Some(hm(6, 0)), Some(hm(20, 0))));
+    }
+
+    #[test]
+    fn test_to_local_time_wib_and_wita() {
+        let departure = Utc.with_ymd_and_hms(2024, 1, 15, 1, 0, 0).unwrap();
+
+        // WIB is UTC+7, WITA is UTC+8
+        let wib = to_local_time(departure, "Asia/Jakarta");
+        assert_eq!(wib.to_rfc3339(), "2024-01-15T08:00:00+07:00");
+        let wita = to_local_time(departure, "Asia/Makassar");
+        assert_eq!(wita.to_rfc3339(), "2024-01-15T09:00:00+08:00");
+
+        // Same instant, different wall clock
+        assert_eq!(wib, wita);
+    }
+
+    #[test]
+    fn test_overnight_and_unbounded_windows() {
+        assert!(is_within_operating_hours(hm(23, 0), Some(hm(22, 0)), Some(hm(4, 0))));
+        assert!(is_within_operating_hours(hm(3, 0), Some(hm(22, 0)), Some(hm(4, 0))));
+        assert!(!is_within_operating_hours(hm(12, 0), Some(hm(22, 0)), Some(hm(4, 0))));
+        assert!(is_within_operating_hours(hm(3, 0), None, None));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::error::{AppError, AppResult};
+
+/// Longest display name the `user.name` column holds, in characters
+pub const MAX_NAME_LEN: usize = 100;
+
+/// Basic email shape check: exactly one `@`, a non-empty local part,
+/// and a domain containing a dot with non-empty labels around it
+pub fn validate_email(email: &str) -> AppResult<()> {
+    let invalid = || AppError::BadRequest(format!("Invalid email address: {}", email));
+
+    let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+
+    if local.is_empty() || domain.contains('@') || email.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+
+    if !domain.contains('.') || domain.split('.').any(str::is_empty) {
+        return Err(invalid());
+    }
+
+    Ok(())
+}
+
+/// Minimum password policy: at least 8 characters with a letter and a digit
+pub fn validate_password(password: &str) -> AppResult<()> {
+    let long_enough = password.chars().count() >= 8;
+    let has_letter = password.chars().any(char::is_alphabetic);
+    let has_digit = password.chars().any(|c| c.is_ascii_digit());
+
+    if !(long_enough && has_letter && has_digit) {
+        return Err(AppError::BadRequest(
+            "Password must be at least 8 characters and contain a letter and a digit".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Reject latitudes outside [-90, 90] and longitudes outside [-180, 180] (NaN included)
+pub fn validate_coordinates(lat: f64, lng: f64) -> AppResult<()> {
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(AppError::BadRequest(
+            "Latitude must be between -90 and 90".to_string(),
+        ));
+    }
+
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(AppError::BadRequest(
+            "Longitude must be between -180 and 180".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Phone numbers: an optional leading `+`, then 6 to 20 digits, optionally grouped
+/// with spaces or dashes
+pub fn validate_phone(phone: &str) -> AppResult<()> {
+    let digits = phone.strip_prefix('+').unwrap_or(phone);
+    let digit_count = digits.chars().filter(char::is_ascii_digit).count();
+
+    if !(6..=20).contains(&digit_count)
+        || !digits.starts_with(|c: char| c.is_ascii_digit())
+        || !digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == ' ' || c == '-')
+    {
+        return Err(AppError::BadRequest(format!(
+            "Invalid phone number: {}",
+            phone
+        )));
+    }
+
+    Ok(())
+}
+
+/// A display name with surrounding whitespace removed; must be 1 to 100 characters
+pub fn normalize_name(name: &str) -> AppResult<String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(AppError::BadRequest("Name must not be empty".to_string()));
+    }
+
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(AppError::BadRequest(format!(
+            "Name must be at most {} characters",
+            MAX_NAME_LEN
+        )));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Canonical form used for storage and lookup, so addresses compare case-insensitively
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_emails() {
+        assert!(validate_email("user@example.com").is_ok());
+        assert!(validate_email("first.last+tag@mail.example.co.id").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_emails() {
+        for email in [
+            "notanemail",
+            "@example.com",
+            "user@",
+            "user@localhost",
+            "user@@example.com",
+            "user@exa@mple.com",
+            "user@example.",
+            "user@.com",
+            "us er@example.com",
+        ] {
+            assert!(
+                validate_email(email).is_err(),
+                "{} should be rejected",
+                email
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_password() {
+        assert!(validate_password("password123").is_ok());
+        for weak in ["short1", "passwordonly", "1234567890", ""] {
+            assert!(
+                validate_password(weak).is_err(),
+                "{} should be rejected",
+                weak
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_coordinates() {
+        assert!(validate_coordinates(-10.18, 123.63).is_ok());
+        assert!(validate_coordinates(90.0, -180.0).is_ok());
+        assert!(validate_coordinates(90.1, 0.0).is_err());
+        assert!(validate_coordinates(0.0, 180.5).is_err());
+        assert!(validate_coordinates(f64::NAN, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_validate_phone() {
+        for valid in ["+62 812-3456-7890", "0380 821234", "123456"] {
+            assert!(
+                validate_phone(valid).is_ok(),
+                "{} should be accepted",
+                valid
+            );
+        }
+        for invalid in [
+            "",
+            "+",
+            "12345",
+            "+62 (812) 3456",
+            "call me",
+            "-123456",
+            "1".repeat(21).as_str(),
+        ] {
+            assert!(
+                validate_phone(invalid).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("  Ann Lee \n").unwrap(), "Ann Lee");
+        // Counted in characters, like the varchar column
+        assert!(normalize_name(&"é".repeat(MAX_NAME_LEN)).is_ok());
+        assert!(normalize_name(&"a".repeat(MAX_NAME_LEN + 1)).is_err());
+        assert!(normalize_name("").is_err());
+        assert!(normalize_name(" \t ").is_err());
+    }
+
+    #[test]
+    fn test_normalize_email() {
+        assert_eq!(normalize_email("  FOO@X.COM "), "foo@x.com");
+    }
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    entities::{
+        booking::{self, BookingStatus},
+        user::UserRole,
+    },
+    utils::etag::booking_etag,
+};
+
+use common::{
+    create_journey, create_user, insert_booking, send, send_raw, test_state, token_for, tomorrow,
+};
+
+#[tokio::test]
+async fn test_admin_delete_booking_returns_passenger() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let booking = insert_booking(&state, &journey, &traveller, 3).await;
+    let token = token_for(&state, &admin);
+    let uri = format!("/api/admin/bookings/{}", booking.id);
+
+    let (status, body) = send(&state, Method::DELETE, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["booking_id"], booking.id.to_string());
+    assert_eq!(body["reference_code"], booking.reference_code);
+    assert_eq!(body["journey_id"], journey.id.to_string());
+    assert_eq!(body["seats"], 3);
+    assert_eq!(body["status"], "confirmed");
+    assert_eq!(body["passenger_name"], traveller.name);
+    assert_eq!(body["passenger_email"], traveller.email);
+
+    let gone = booking::Entity::find_by_id(booking.id)
+        .one(&state.db)
+        .await
+        .unwrap();
+    assert!(gone.is_none());
+
+    let (status, _) = send(&state, Method::DELETE, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_admin_delete_missing_booking() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let uri = format!("/api/admin/bookings/{}", Uuid::new_v4());
+
+    let token = token_for(&state, &admin);
+    let (status, _) = send(&state, Method::DELETE, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_admin_filters_bookings_by_status() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let kept = insert_booking(&state, &journey, &traveller, 1).await;
+    let cancelled = insert_booking(&state, &journey, &traveller, 1).await;
+
+    let traveller_token = token_for(&state, &traveller);
+    let uri = format!("/api/bookings/{}", cancelled.id);
+    let (status, _) = send(&state, Method::DELETE, &uri, Some(&traveller_token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let token = token_for(&state, &admin);
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        "/api/admin/bookings?status=cancelled",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = body["items"].as_array().unwrap();
+    assert!(items.iter().all(|b| b["status"] == "cancelled"));
+    assert!(items.iter().any(|b| b["id"] == cancelled.id.to_string()));
+    assert!(!items.iter().any(|b| b["id"] == kept.id.to_string()));
+}
+
+#[tokio::test]
+async fn test_admin_seat_decrease_promotes_waitlist() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let journey = create_journey(&state, 2, tomorrow()).await;
+    let holder = create_user(&state, UserRole::Traveller).await;
+    let booked = insert_booking(&state, &journey, &holder, 2).await;
+    let waiter = create_user(&state, UserRole::Traveller).await;
+    let waiting = insert_booking(&state, &journey, &waiter, 1).await;
+    let mut active: booking::ActiveModel = waiting.clone().into();
+    active.status = Set(BookingStatus::Waitlisted);
+    active.update(&state.db).await.unwrap();
+
+    let request = Request::builder()
+        .method(Method::PUT)
+        .uri(format!("/api/admin/bookings/{}", booked.id))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::IF_MATCH, booking_etag(&booked))
+        .body(Body::from(json!({ "seats": 1 }).to_string()))
+        .unwrap();
+    let (status, _, _) = send_raw(&state, request).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let promoted = booking::Entity::find_by_id(waiting.id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(promoted.status, BookingStatus::Confirmed);
+}
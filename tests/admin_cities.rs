@@ -0,0 +1,412 @@
+mod common;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppResult, AppState,
+    entities::{city, user::UserRole},
+    utils::geocode::Geocoder,
+};
+
+use common::{
+    city_by_name, create_journey_between, create_user, send, send_raw, test_state, token_for,
+    tomorrow,
+};
+
+fn new_city_body(name: &str) -> serde_json::Value {
+    json!({
+        "name": name,
+        "center_lat": -7.2575,
+        "center_lng": 112.7521,
+        "pickup_radius_km": 20.0
+    })
+}
+
+#[tokio::test]
+async fn test_admin_creates_and_updates_city() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let name = format!("Surabaya-{}", Uuid::new_v4());
+
+    let (status, created) = send(
+        &state,
+        Method::POST,
+        "/api/admin/cities",
+        Some(&token),
+        Some(new_city_body(&name)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(created["name"], name.as_str());
+    assert_eq!(created["pickup_radius_km"], 20.0);
+
+    let (status, updated) = send(
+        &state,
+        Method::PUT,
+        &format!("/api/admin/cities/{}", created["id"]),
+        Some(&token),
+        Some(json!({ "pickup_radius_km": 35.5 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["pickup_radius_km"], 35.5);
+    assert_eq!(updated["name"], name.as_str());
+
+    // Names are unique
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/admin/cities",
+        Some(&token),
+        Some(new_city_body(&name)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = send(
+        &state,
+        Method::DELETE,
+        &format!("/api/admin/cities/{}", created["id"]),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_create_city_rejects_invalid_geometry() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    for (lat, lng, radius) in [(91.0, 0.0, 10.0), (0.0, -181.0, 10.0), (0.0, 0.0, 0.0)] {
+        let mut body = new_city_body(&format!("Invalid-{}", Uuid::new_v4()));
+        body["center_lat"] = json!(lat);
+        body["center_lng"] = json!(lng);
+        body["pickup_radius_km"] = json!(radius);
+
+        let (status, _) = send(
+            &state,
+            Method::POST,
+            "/api/admin/cities",
+            Some(&token),
+            Some(body),
+        )
+        .await;
+        assert_eq!(
+            status,
+            StatusCode::BAD_REQUEST,
+            "{} {} {}",
+            lat,
+            lng,
+            radius
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_delete_city_in_use_is_conflict() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    let (status, created) = send(
+        &state,
+        Method::POST,
+        "/api/admin/cities",
+        Some(&token),
+        Some(new_city_body(&format!("Malang-{}", Uuid::new_v4()))),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let new_city: city::Model = serde_json::from_value(created).unwrap();
+
+    let kupang = city_by_name(&state, "Kupang").await;
+    create_journey_between(&state, &kupang, &new_city, 10, tomorrow()).await;
+
+    let (status, _) = send(
+        &state,
+        Method::DELETE,
+        &format!("/api/admin/cities/{}", new_city.id),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_city_pickup_polygon() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let mut body = new_city_body(&format!("Surabaya-{}", Uuid::new_v4()));
+
+    // Fewer than three vertices is not an area
+    body["pickup_polygon"] = json!([[-7.3, 112.7], [-7.2, 112.8]]);
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/admin/cities",
+        Some(&token),
+        Some(body.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let square = json!([[-7.3, 112.7], [-7.3, 112.8], [-7.2, 112.8], [-7.2, 112.7]]);
+    body["pickup_polygon"] = square.clone();
+    let (status, created) = send(
+        &state,
+        Method::POST,
+        "/api/admin/cities",
+        Some(&token),
+        Some(body),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(created["pickup_polygon"], square);
+
+    // An empty list falls back to the radius
+    let uri = format!("/api/admin/cities/{}", created["id"]);
+    let (status, updated) = send(
+        &state,
+        Method::PUT,
+        &uri,
+        Some(&token),
+        Some(json!({ "pickup_polygon": [] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(updated["pickup_polygon"].is_null());
+
+    send(&state, Method::DELETE, &uri, Some(&token), None).await;
+}
+
+/// Places every city at the same spot
+struct FixedGeocoder(f64, f64);
+
+#[async_trait]
+impl Geocoder for FixedGeocoder {
+    async fn geocode(&self, _name: &str) -> AppResult<(f64, f64)> {
+        Ok((self.0, self.1))
+    }
+}
+
+#[tokio::test]
+async fn test_geocode_refresh_updates_city_center() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    let (status, created) = send(
+        &state,
+        Method::POST,
+        "/api/admin/cities",
+        Some(&token),
+        Some(new_city_body(&format!("Surabaya-{}", Uuid::new_v4()))),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let uri = format!("/api/admin/cities/{}", created["id"]);
+    let refresh_uri = format!("{}/geocode-refresh", uri);
+
+    // Without a geocoder the city is left alone
+    let (status, _) = send(&state, Method::POST, &refresh_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    state.geocoder = Arc::new(FixedGeocoder(-7.2459, 112.7378));
+    let (status, updated) = send(&state, Method::POST, &refresh_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["center_lat"], -7.2459);
+    assert_eq!(updated["center_lng"], 112.7378);
+
+    let id = created["id"].as_i64().unwrap() as i32;
+    let stored = city::Entity::find_by_id(id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((stored.center_lat, stored.center_lng), (-7.2459, 112.7378));
+    assert_eq!(stored.pickup_radius_km, 20.0);
+
+    // Coordinates the geocoder gets wrong are rejected like any other
+    state.geocoder = Arc::new(FixedGeocoder(91.0, 0.0));
+    let (status, _) = send(&state, Method::POST, &refresh_uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/admin/cities/0/geocode-refresh",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    send(&state, Method::DELETE, &uri, Some(&token), None).await;
+}
+
+/// POST a CSV body to the import endpoint
+async fn import_csv(
+    state: &AppState,
+    token: &str,
+    query: &str,
+    csv: &str,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("/api/admin/cities/import{}", query))
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .header(header::CONTENT_TYPE, "text/csv")
+        .body(Body::from(csv.to_string()))
+        .unwrap();
+    let (status, _, bytes) = send_raw(state, request).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+/// Two good rows, a duplicate of the first, an out-of-range latitude and a non-numeric radius
+fn mixed_csv(first: &str, second: &str) -> String {
+    format!(
+        "name,center_lat,center_lng,pickup_radius_km\n\
+         {first},-7.2575,112.7521,20\n\
+         \"{second}\",-7.2459,112.7378,15.5\n\
+         {first},-7.2575,112.7521,20\n\
+         \n\
+         Nowhere-{first},95.0,112.7521,20\n\
+         Elsewhere-{first},-7.2575,112.7521,wide\n"
+    )
+}
+
+#[tokio::test]
+async fn test_import_cities_lenient_reports_each_row() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    let first = format!("Surabaya-{}", Uuid::new_v4());
+    let second = format!("Sidoarjo, {}", Uuid::new_v4());
+    let (status, body) = import_csv(&state, &token, "", &mixed_csv(&first, &second)).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(
+        (&body["created"], &body["skipped"], &body["errors"]),
+        (&json!(2), &json!(1), &json!(2))
+    );
+
+    let rows = body["rows"].as_array().unwrap();
+    let statuses: Vec<_> = rows
+        .iter()
+        .map(|r| (r["line"].as_u64().unwrap(), r["status"].as_str().unwrap()))
+        .collect();
+    // The blank line 5 is not reported
+    assert_eq!(
+        statuses,
+        vec![
+            (2, "created"),
+            (3, "created"),
+            (4, "skipped"),
+            (6, "error"),
+            (7, "error")
+        ]
+    );
+    assert_eq!(rows[1]["name"], second);
+    assert!(
+        rows[4]["message"]
+            .as_str()
+            .unwrap()
+            .contains("pickup_radius_km")
+    );
+
+    let created = city::Entity::find()
+        .filter(city::Column::Name.is_in([first.clone(), second.clone()]))
+        .all(&state.db)
+        .await
+        .unwrap();
+    assert_eq!(created.len(), 2);
+
+    assert_eq!(rows[2]["message"], "Duplicate name earlier in file");
+
+    // Invalid names are errors however often they repeat
+    let csv = "name,center_lat,center_lng,pickup_radius_km\n\
+               \" \",-7.2575,112.7521,20\n\
+               \" \",-7.2575,112.7521,20\n";
+    let (status, blank) = import_csv(&state, &token, "", csv).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        (&blank["skipped"], &blank["errors"]),
+        (&json!(0), &json!(2))
+    );
+    let (status, _) = import_csv(&state, &token, "?strict=true", csv).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Importing the same file again skips the cities it already created
+    let (status, again) = import_csv(&state, &token, "", &mixed_csv(&first, &second)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        (&again["created"], &again["skipped"]),
+        (&json!(0), &json!(3))
+    );
+
+    for city in created {
+        city::Entity::delete_by_id(city.id)
+            .exec(&state.db)
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_import_cities_strict_rejects_whole_file() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    let first = format!("Surabaya-{}", Uuid::new_v4());
+    let second = format!("Sidoarjo-{}", Uuid::new_v4());
+    let csv = mixed_csv(&first, &second);
+    let (status, body) = import_csv(&state, &token, "?strict=true", &csv).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Line 6:")
+    );
+
+    let created = city::Entity::find()
+        .filter(city::Column::Name.is_in([first, second]))
+        .count(&state.db)
+        .await
+        .unwrap();
+    assert_eq!(created, 0);
+
+    // A file with the wrong header is rejected in either mode
+    let (status, _) = import_csv(&state, &token, "", "name,lat,lng\nX,1,2\n").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
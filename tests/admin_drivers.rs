@@ -0,0 +1,299 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::entities::booking::{self, BookingStatus};
+use bus_travel_backend::entities::user::{self, UserRole};
+
+use common::{create_journey, create_user, insert_booking, send, test_state, token_for, tomorrow};
+
+fn new_driver_body(email: &str) -> serde_json::Value {
+    json!({
+        "email": email,
+        "password": "driverpass",
+        "name": "Driver Test"
+    })
+}
+
+#[tokio::test]
+async fn test_admin_creates_and_deletes_driver() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let email = format!("driver-{}@test.example", Uuid::new_v4());
+
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        "/api/admin/drivers",
+        Some(&token),
+        Some(new_driver_body(&email)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["email"], email);
+
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/admin/drivers",
+        Some(&token),
+        Some(new_driver_body(&email)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let uri = format!("/api/admin/drivers/{}", body["id"].as_str().unwrap());
+    let (status, _) = send(&state, Method::DELETE, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(&state, Method::DELETE, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_traveller_cannot_manage_drivers() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let driver = create_user(&state, UserRole::Driver).await;
+    let token = token_for(&state, &traveller);
+
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/admin/drivers",
+        Some(&token),
+        Some(new_driver_body("sneaky@test.example")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let uri = format!("/api/admin/drivers/{}", driver.id);
+    let (status, _) = send(&state, Method::DELETE, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_driver_application_lifecycle() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let admin_token = token_for(&state, &admin);
+    let email = format!("applicant-{}@test.example", Uuid::new_v4());
+
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        "/api/auth/register-driver",
+        None,
+        Some(new_driver_body(&email)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    // Applicants start out as travellers
+    assert_eq!(body["user"]["role"], "Traveller");
+    let applicant_id = body["user"]["id"].as_str().unwrap().to_string();
+    let applicant_token = body["token"].as_str().unwrap().to_string();
+
+    // Not a driver yet
+    let (status, _) = send(
+        &state,
+        Method::GET,
+        "/api/driver/journeys",
+        Some(&applicant_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, pending) = send(
+        &state,
+        Method::GET,
+        "/api/admin/driver-applications",
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        pending
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|u| u["id"] == applicant_id.as_str())
+    );
+
+    let approve = format!("/api/admin/users/{}/approve-driver", applicant_id);
+    let (status, approved) = send(&state, Method::POST, &approve, Some(&admin_token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(approved["role"], "Driver");
+
+    // Off the pending list, and the application can't be approved twice
+    let (_, pending) = send(
+        &state,
+        Method::GET,
+        "/api/admin/driver-applications",
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert!(
+        !pending
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|u| u["id"] == applicant_id.as_str())
+    );
+    let (status, _) = send(&state, Method::POST, &approve, Some(&admin_token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_approval_cancels_only_upcoming_bookings() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let applicant = create_user(&state, UserRole::Traveller).await;
+    let mut pending: user::ActiveModel = applicant.clone().into();
+    pending.driver_application_pending = Set(true);
+    pending.update(&state.db).await.unwrap();
+
+    let past = create_journey(&state, 10, Utc::now() - Duration::days(3)).await;
+    let upcoming = create_journey(&state, 1, tomorrow()).await;
+    let kept = insert_booking(&state, &past, &applicant, 1).await;
+    let released = insert_booking(&state, &upcoming, &applicant, 1).await;
+
+    // Someone is waiting for the applicant's seat on the full journey
+    let other = create_user(&state, UserRole::Traveller).await;
+    let waiting = insert_booking(&state, &upcoming, &other, 1).await;
+    let mut active: booking::ActiveModel = waiting.clone().into();
+    active.status = Set(BookingStatus::Waitlisted);
+    active.update(&state.db).await.unwrap();
+
+    let uri = format!("/api/admin/users/{}/approve-driver", applicant.id);
+    let (status, _) = send(&state, Method::POST, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let status_of = |id: Uuid| {
+        let db = state.db.clone();
+        async move {
+            booking::Entity::find_by_id(id)
+                .one(&db)
+                .await
+                .unwrap()
+                .unwrap()
+                .status
+        }
+    };
+    assert_eq!(status_of(kept.id).await, BookingStatus::Confirmed);
+    assert_eq!(status_of(released.id).await, BookingStatus::Cancelled);
+    assert_eq!(status_of(waiting.id).await, BookingStatus::Confirmed);
+}
+
+#[tokio::test]
+async fn test_approving_non_applicant_rejected() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    let uri = format!("/api/admin/users/{}/approve-driver", traveller.id);
+    let (status, body) = send(&state, Method::POST, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"]["message"],
+        "User has no pending driver application"
+    );
+
+    let uri = format!("/api/admin/users/{}/approve-driver", Uuid::new_v4());
+    let (status, _) = send(&state, Method::POST, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_unassign_driver() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let driver = create_user(&state, UserRole::Driver).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        &format!("/api/admin/journeys/{}/assign-driver", journey.id),
+        Some(&token),
+        Some(json!({ "driver_id": driver.id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["driver_id"], driver.id.to_string());
+
+    let uri = format!("/api/admin/journeys/{}/unassign-driver", journey.id);
+    let (status, body) = send(&state, Method::POST, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["driver_id"].is_null());
+
+    // Nothing left to unassign is still a success
+    let (status, body) = send(&state, Method::POST, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["driver_id"].is_null());
+    assert_eq!(body["id"], journey.id.to_string());
+
+    let missing = format!("/api/admin/journeys/{}/unassign-driver", Uuid::new_v4());
+    let (status, _) = send(&state, Method::POST, &missing, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_driver_validates_name() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let create = |name: String| {
+        let token = token.clone();
+        let state = &state;
+        async move {
+            let body = json!({
+                "email": format!("driver-{}@test.example", Uuid::new_v4()),
+                "password": "driverpass",
+                "name": name,
+            });
+            send(
+                state,
+                Method::POST,
+                "/api/admin/drivers",
+                Some(&token),
+                Some(body),
+            )
+            .await
+        }
+    };
+
+    let (status, _) = create(" \t ".to_string()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = create("d".repeat(101)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = create(" Driver Dee ".to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "Driver Dee");
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, NaiveTime};
+
+use bus_travel_backend::{
+    AppState,
+    entities::{journey, user::UserRole},
+};
+
+use common::{
+    assign_driver, create_journey, create_user, send, test_state, token_for, unique_future_date,
+};
+
+/// Ids of the journeys the admin list returns for `uri`, in order
+async fn listed_ids(state: &AppState, token: &str, uri: &str) -> Vec<String> {
+    let (status, body) = send(state, Method::GET, uri, Some(token), None).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|j| j["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_filter_journeys_by_driver_assignment() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let driver = create_user(&state, UserRole::Driver).await;
+    let token = token_for(&state, &admin);
+
+    // A day of its own, so other tests' journeys stay out of the window
+    let day = unique_future_date().and_time(NaiveTime::MIN).and_utc();
+    let unassigned = create_journey(&state, 10, day + Duration::hours(8)).await;
+    let assigned = create_journey(&state, 10, day + Duration::hours(9)).await;
+    let assigned = assign_driver(&state, assigned, driver.id).await;
+    // Unassigned, but outside the window
+    let next_day = create_journey(&state, 10, day + Duration::hours(30)).await;
+
+    let window = format!(
+        "departure_from={}&departure_to={}",
+        day.to_rfc3339().replace('+', "%2B"),
+        (day + Duration::hours(23)).to_rfc3339().replace('+', "%2B"),
+    );
+    let id = |j: &journey::Model| j.id.to_string();
+
+    let all = listed_ids(&state, &token, &format!("/api/admin/journeys?{}", window)).await;
+    assert_eq!(all, vec![id(&unassigned), id(&assigned)]);
+
+    let uri = format!("/api/admin/journeys?has_driver=false&{}", window);
+    assert_eq!(
+        listed_ids(&state, &token, &uri).await,
+        vec![id(&unassigned)]
+    );
+
+    let uri = format!("/api/admin/journeys?has_driver=true&{}", window);
+    assert_eq!(listed_ids(&state, &token, &uri).await, vec![id(&assigned)]);
+
+    // Without the window the filter still applies to every journey
+    let uri = "/api/admin/journeys?has_driver=false&sort=created_at_desc&per_page=100";
+    let unassigned_anywhere = listed_ids(&state, &token, uri).await;
+    assert!(unassigned_anywhere.contains(&id(&next_day)));
+    assert!(!unassigned_anywhere.contains(&id(&assigned)));
+}
+
+#[tokio::test]
+async fn test_inverted_departure_window_rejected() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    let uri = "/api/admin/journeys?departure_from=2030-01-02T00:00:00Z\
+               &departure_to=2030-01-01T00:00:00Z";
+    let (status, _) = send(&state, Method::GET, uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
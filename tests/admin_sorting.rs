@@ -0,0 +1,137 @@
+mod common;
+
+use axum::extract::{Query, State};
+
+use bus_travel_backend::{
+    AppError, AppState,
+    entities::user::UserRole,
+    handlers::admin::{
+        AdminJourneyFilter, BookingFilter, BookingInfo, list_all_bookings, list_journeys,
+    },
+    utils::{
+        pagination::{CursorQuery, Pagination},
+        sort::SortQuery,
+    },
+};
+
+use common::{create_journey, create_user, insert_booking, test_state, tomorrow};
+
+fn sort(key: &str) -> Query<SortQuery> {
+    Query(SortQuery {
+        sort: Some(key.to_string()),
+    })
+}
+
+fn first_page() -> Query<Pagination> {
+    Query(Pagination {
+        page: Some(1),
+        per_page: Some(100),
+    })
+}
+
+/// Every booking in `key` order, following the cursor through all pages
+async fn all_bookings(state: &AppState, key: &str) -> Vec<BookingInfo> {
+    let mut bookings = Vec::new();
+    let mut after = None;
+    loop {
+        let page = CursorQuery {
+            after,
+            limit: Some(100),
+        };
+        let page = list_all_bookings(
+            State(state.clone()),
+            sort(key),
+            Query(page),
+            Query(BookingFilter::default()),
+        )
+        .await
+        .unwrap()
+        .0;
+        bookings.extend(page.items);
+
+        match page.next_cursor {
+            Some(cursor) => after = Some(cursor),
+            None => return bookings,
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_bookings_sorted_by_created_at() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let older = insert_booking(&state, &journey, &traveller, 1).await;
+    let newer = insert_booking(&state, &journey, &traveller, 2).await;
+
+    for (key, newest_first) in [("created_at_desc", true), ("created_at_asc", false)] {
+        let bookings = all_bookings(&state, key).await;
+
+        let times: Vec<_> = bookings.iter().map(|b| b.created_at).collect();
+        let mut expected = times.clone();
+        expected.sort();
+        if newest_first {
+            expected.reverse();
+        }
+        assert_eq!(times, expected, "{} is out of order", key);
+
+        let position = |id| bookings.iter().position(|b| b.id == id).unwrap();
+        assert_eq!(position(newer.id) < position(older.id), newest_first);
+    }
+}
+
+#[tokio::test]
+async fn test_journeys_sorted_by_departure_time() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    create_journey(&state, 10, tomorrow()).await;
+
+    for (key, latest_first) in [("departure_time_asc", false), ("departure_time_desc", true)] {
+        let page = list_journeys(
+            State(state.clone()),
+            first_page(),
+            sort(key),
+            Query(AdminJourneyFilter::default()),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        let times: Vec<_> = page.items.iter().map(|j| j.departure_time).collect();
+        let mut expected = times.clone();
+        expected.sort();
+        if latest_first {
+            expected.reverse();
+        }
+        assert!(!times.is_empty());
+        assert_eq!(times, expected, "{} is out of order", key);
+    }
+}
+
+#[tokio::test]
+async fn test_unknown_sort_key_rejected() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let result = list_all_bookings(
+        State(state.clone()),
+        sort("pickup_lat_asc"),
+        Query(CursorQuery::default()),
+        Query(BookingFilter::default()),
+    )
+    .await;
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+    let result = list_journeys(
+        State(state.clone()),
+        first_page(),
+        sort("seats"),
+        Query(AdminJourneyFilter::default()),
+    )
+    .await;
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
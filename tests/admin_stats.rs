@@ -0,0 +1,100 @@
+mod common;
+
+use axum::extract::State;
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+use bus_travel_backend::{
+    AppState,
+    entities::{
+        booking::{self, BookingStatus},
+        journey::{self, JourneyStatus},
+        user::UserRole,
+    },
+    handlers::admin::{StatsResponse, stats, utilization_percent},
+};
+
+use common::{create_journey, create_user, insert_booking, test_state, tomorrow};
+
+async fn snapshot(state: &AppState) -> StatsResponse {
+    stats(State(state.clone())).await.unwrap().0
+}
+
+/// Average utilization recomputed the slow way, from every row
+async fn expected_utilization(state: &AppState) -> Option<f64> {
+    let now = Utc::now();
+    let bookings = booking::Entity::find().all(&state.db).await.unwrap();
+    let ratios: Vec<f64> = journey::Entity::find()
+        .all(&state.db)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|j| j.departure_time >= now && j.status != JourneyStatus::Cancelled)
+        .filter(|j| j.total_seats > 0)
+        .map(|j| {
+            let booked: i32 = bookings
+                .iter()
+                .filter(|b| b.journey_id == j.id && b.status == BookingStatus::Confirmed)
+                .map(|b| b.seats)
+                .sum();
+            booked as f64 / j.total_seats as f64
+        })
+        .collect();
+
+    if ratios.is_empty() {
+        None
+    } else {
+        Some(utilization_percent(
+            ratios.iter().sum::<f64>() / ratios.len() as f64,
+        ))
+    }
+}
+
+// A single test so no other writer in this binary shifts the counts between snapshots
+#[tokio::test]
+async fn test_stats_aggregates() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let before = snapshot(&state).await;
+
+    create_user(&state, UserRole::Driver).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    create_user(&state, UserRole::Traveller).await;
+
+    // 2 of 3 seats taken
+    let partly_full = create_journey(&state, 3, tomorrow()).await;
+    insert_booking(&state, &partly_full, &traveller, 2).await;
+    // Waitlisted seats count as a booking but not as booked seats
+    let waitlisted = insert_booking(&state, &partly_full, &traveller, 1).await;
+    let mut waitlisted: booking::ActiveModel = waitlisted.into();
+    waitlisted.status = Set(BookingStatus::Waitlisted);
+    waitlisted.update(&state.db).await.unwrap();
+
+    // Empty upcoming journey
+    create_journey(&state, 4, tomorrow()).await;
+
+    // Cancelled journeys are left out of utilization but still counted as upcoming
+    let cancelled = create_journey(&state, 2, tomorrow()).await;
+    let mut cancelled: journey::ActiveModel = cancelled.into();
+    cancelled.status = Set(JourneyStatus::Cancelled);
+    cancelled.update(&state.db).await.unwrap();
+
+    let past = create_journey(&state, 5, Utc::now() - Duration::days(1)).await;
+    insert_booking(&state, &past, &traveller, 5).await;
+
+    let after = snapshot(&state).await;
+
+    assert_eq!(after.users.admin, before.users.admin);
+    assert_eq!(after.users.driver, before.users.driver + 1);
+    assert_eq!(after.users.traveller, before.users.traveller + 2);
+    assert_eq!(after.journeys.upcoming, before.journeys.upcoming + 3);
+    assert_eq!(after.journeys.past, before.journeys.past + 1);
+    assert_eq!(after.total_bookings, before.total_bookings + 3);
+    assert_eq!(after.seats_booked, before.seats_booked + 2 + 5);
+
+    let utilization = after.average_utilization_percent.unwrap();
+    assert_eq!(Some(utilization), expected_utilization(&state).await);
+    // One decimal at most
+    assert_eq!((utilization * 10.0).round() / 10.0, utilization);
+}
@@ -0,0 +1,422 @@
+mod common;
+
+use axum::{
+    extract::{Query, State},
+    http::{Method, StatusCode},
+};
+
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppState,
+    entities::{
+        booking::{self, BookingStatus},
+        role_change_audit,
+        user::UserRole,
+    },
+    handlers::admin::{UserFilter, UserListItem, list_all_users},
+    utils::pagination::Pagination,
+};
+
+use common::{create_journey, create_user, insert_booking, send, test_state, token_for, tomorrow};
+
+/// Walk every page of the user list
+async fn all_users(state: &AppState, filter: UserFilter) -> Vec<UserListItem> {
+    let mut users = Vec::new();
+    for page in 1.. {
+        let result = list_all_users(
+            State(state.clone()),
+            Query(Pagination {
+                page: Some(page),
+                per_page: Some(100),
+            }),
+            Query(UserFilter {
+                role: filter.role.clone(),
+                include_deleted: filter.include_deleted,
+            }),
+        )
+        .await
+        .unwrap()
+        .0;
+
+        let done = result.items.len() < 100;
+        users.extend(result.items);
+        if done {
+            assert_eq!(users.len() as u64, result.total);
+            return users;
+        }
+    }
+    unreachable!()
+}
+
+#[tokio::test]
+async fn test_role_filter() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let driver = create_user(&state, UserRole::Driver).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    let drivers = all_users(
+        &state,
+        UserFilter {
+            role: Some(UserRole::Driver),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(drivers.iter().all(|u| u.role == UserRole::Driver));
+    assert!(drivers.iter().any(|u| u.id == driver.id));
+    assert!(!drivers.iter().any(|u| u.id == traveller.id));
+
+    let travellers = all_users(
+        &state,
+        UserFilter {
+            role: Some(UserRole::Traveller),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(travellers.iter().all(|u| u.role == UserRole::Traveller));
+    assert!(travellers.iter().any(|u| u.id == traveller.id));
+
+    // The query string takes the lowercase role name
+    let admin = create_user(&state, UserRole::Admin).await;
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        "/api/admin/users?role=driver&per_page=5",
+        Some(&token_for(&state, &admin)),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["per_page"], 5);
+    assert!(
+        body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|u| u["role"] == "Driver")
+    );
+}
+
+#[tokio::test]
+async fn test_booking_counts() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let frequent = create_user(&state, UserRole::Traveller).await;
+    let newcomer = create_user(&state, UserRole::Traveller).await;
+    for _ in 0..3 {
+        insert_booking(&state, &journey, &frequent, 1).await;
+    }
+    // Cancelled bookings don't count, as in the booking stats
+    let quitter = create_user(&state, UserRole::Traveller).await;
+    for user in [&frequent, &quitter] {
+        let mut cancelled: booking::ActiveModel =
+            insert_booking(&state, &journey, user, 1).await.into();
+        cancelled.status = Set(BookingStatus::Cancelled);
+        cancelled.update(&state.db).await.unwrap();
+    }
+
+    let users = all_users(&state, UserFilter::default()).await;
+    let find = |id| users.iter().find(|u| u.id == id).unwrap();
+
+    assert_eq!(find(frequent.id).booking_count, 3);
+    assert_eq!(find(newcomer.id).booking_count, 0);
+    assert_eq!(find(quitter.id).booking_count, 0);
+    assert_eq!(
+        find(frequent.id).created_at.timestamp(),
+        frequent.created_at.timestamp()
+    );
+}
+
+#[tokio::test]
+async fn test_soft_deleted_users_hidden_by_default() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let driver = create_user(&state, UserRole::Driver).await;
+
+    // Past bookings survive the delete, upcoming ones are cancelled
+    let past = create_journey(&state, 10, Utc::now() - Duration::days(3)).await;
+    let upcoming = create_journey(&state, 10, tomorrow()).await;
+    let kept = insert_booking(&state, &past, &traveller, 1).await;
+    let released = insert_booking(&state, &upcoming, &traveller, 1).await;
+
+    for id in [traveller.id, driver.id] {
+        let uri = format!("/api/admin/users/{}", id);
+        let (status, _) = send(&state, Method::DELETE, &uri, Some(&token), None).await;
+        assert_eq!(status, StatusCode::OK);
+        // Already deleted
+        let (status, _) = send(&state, Method::DELETE, &uri, Some(&token), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    let listed = all_users(&state, UserFilter::default()).await;
+    assert!(
+        !listed
+            .iter()
+            .any(|u| u.id == traveller.id || u.id == driver.id)
+    );
+
+    let everyone = all_users(
+        &state,
+        UserFilter {
+            include_deleted: true,
+            ..Default::default()
+        },
+    )
+    .await;
+    let deleted = everyone.iter().find(|u| u.id == traveller.id).unwrap();
+    assert!(deleted.deleted_at.is_some());
+    assert_eq!(deleted.booking_count, 1);
+
+    assert!(
+        booking::Entity::find_by_id(kept.id)
+            .one(&state.db)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    // The upcoming booking is cancelled rather than deleted, and admins still see it
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        "/api/admin/bookings?status=cancelled",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let listed = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["id"] == released.id.to_string())
+        .unwrap();
+    assert_eq!(listed["status"], "cancelled");
+    assert!(listed["cancelled_at"].is_string());
+
+    let driver_listed = |body: &serde_json::Value| {
+        body.as_array()
+            .unwrap()
+            .iter()
+            .any(|d| d["id"] == driver.id.to_string())
+    };
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        "/api/admin/drivers",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!driver_listed(&body));
+
+    let (_, body) = send(
+        &state,
+        Method::GET,
+        "/api/admin/drivers?include_deleted=true",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert!(driver_listed(&body));
+}
+
+#[tokio::test]
+async fn test_list_user_bookings() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let other = create_user(&state, UserRole::Traveller).await;
+    let newcomer = create_user(&state, UserRole::Traveller).await;
+
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let first = insert_booking(&state, &journey, &traveller, 1).await;
+    let second = insert_booking(&state, &journey, &traveller, 2).await;
+    insert_booking(&state, &journey, &other, 1).await;
+
+    let uri = format!("/api/admin/users/{}/bookings", traveller.id);
+    let (status, body) = send(&state, Method::GET, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let bookings = body.as_array().unwrap();
+    let ids: Vec<&str> = bookings.iter().map(|b| b["id"].as_str().unwrap()).collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&first.id.to_string().as_str()));
+    assert!(ids.contains(&second.id.to_string().as_str()));
+    assert_eq!(bookings[0]["user_email"], traveller.email);
+    assert_eq!(bookings[0]["origin_city"], "Kupang");
+    assert_eq!(bookings[0]["destination_city"], "Soe");
+    assert!(bookings[0]["departure_time"].is_string());
+
+    let uri = format!("/api/admin/users/{}/bookings", newcomer.id);
+    let (status, body) = send(&state, Method::GET, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.as_array().unwrap().is_empty());
+
+    let uri = format!("/api/admin/users/{}/bookings", Uuid::new_v4());
+    let (status, _) = send(&state, Method::GET, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_reset_user_password() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &admin);
+    let uri = format!("/api/admin/users/{}/reset-password", traveller.id);
+
+    let body = json!({ "new_password": "weak" });
+    let (status, _) = send(&state, Method::POST, &uri, Some(&token), Some(body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let body = json!({ "new_password": "fresh-start-42" });
+    let (status, _) = send(&state, Method::POST, &uri, Some(&token), Some(body)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let login = json!({ "email": traveller.email, "password": "fresh-start-42" });
+    let (status, _) = send(&state, Method::POST, "/api/auth/login", None, Some(login)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_admin_password_cannot_be_reset() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let other_admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    let body = json!({ "new_password": "takeover-123" });
+    for target in [other_admin.id, admin.id] {
+        let uri = format!("/api/admin/users/{}/reset-password", target);
+        let (status, _) = send(&state, Method::POST, &uri, Some(&token), Some(body.clone())).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    let uri = format!("/api/admin/users/{}/reset-password", Uuid::new_v4());
+    let (status, _) = send(&state, Method::POST, &uri, Some(&token), Some(body)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_role_change_writes_audit_row() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &admin);
+    let uri = format!("/api/admin/users/{}/role", traveller.id);
+
+    let (status, _) = send(
+        &state,
+        Method::PUT,
+        &uri,
+        Some(&token),
+        Some(json!({ "role": "Driver" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Setting the role it already has is not a change
+    let (status, _) = send(
+        &state,
+        Method::PUT,
+        &uri,
+        Some(&token),
+        Some(json!({ "role": "Driver" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let audits = role_change_audit::Entity::find()
+        .filter(role_change_audit::Column::TargetUserId.eq(traveller.id))
+        .all(&state.db)
+        .await
+        .unwrap();
+    assert_eq!(audits.len(), 1);
+    assert_eq!(audits[0].actor_id, Some(admin.id));
+    assert_eq!(audits[0].old_role, UserRole::Traveller);
+    assert_eq!(audits[0].new_role, UserRole::Driver);
+
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        "/api/admin/audit/role-changes?per_page=100",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let item = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|i| i["id"] == audits[0].id.to_string())
+        .expect("audit row missing from the review list");
+    assert_eq!(item["actor_email"], admin.email.as_str());
+    assert_eq!(item["target_email"], traveller.email.as_str());
+    assert_eq!(item["old_role"], "Traveller");
+    assert_eq!(item["new_role"], "Driver");
+}
+
+#[tokio::test]
+async fn test_demote_admin_while_others_remain() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let other = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    let (status, body) = send(
+        &state,
+        Method::PUT,
+        &format!("/api/admin/users/{}/role", other.id),
+        Some(&token),
+        Some(json!({ "role": "Traveller" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["role"], "Traveller");
+}
+
+#[tokio::test]
+async fn test_unknown_role_is_bad_request() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &admin);
+
+    let (status, body) = send(
+        &state,
+        Method::PUT,
+        &format!("/api/admin/users/{}/role", traveller.id),
+        Some(&token),
+        Some(json!({ "role": "Superuser" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["message"], "Unknown role: Superuser");
+}
@@ -0,0 +1,573 @@
+mod common;
+
+use argon2::{Params, password_hash::PasswordHash};
+use axum::{
+    Json,
+    extract::State,
+    http::{Method, StatusCode},
+};
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set, SqlErr};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppError, AppState,
+    db::seed::seed_admin,
+    entities::user::{self, UserRole},
+    handlers::auth::{RegisterRequest, register},
+    utils::{
+        jwt::verify_access_token,
+        password::{HashCost, hash_password},
+    },
+};
+
+use common::{create_journey, create_user, insert_booking, send, test_state, token_for, tomorrow};
+
+fn registration(email: &str) -> Json<RegisterRequest> {
+    Json(RegisterRequest {
+        email: email.to_string(),
+        password: "password123".to_string(),
+        name: "Test Traveller".to_string(),
+    })
+}
+
+#[tokio::test]
+async fn test_register_normalizes_email_and_detects_case_insensitive_duplicates() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let local = format!("foo-{}", Uuid::new_v4());
+
+    let Json(created) = register(
+        State(state.clone()),
+        registration(&format!("{}@X.com", local)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(created.user.email, format!("{}@x.com", local));
+
+    let duplicate = register(
+        State(state.clone()),
+        registration(&format!("{}@X.COM", local.to_uppercase())),
+    )
+    .await;
+    assert!(matches!(duplicate, Err(AppError::Conflict(_))));
+}
+
+#[tokio::test]
+async fn test_register_conflicts_on_email_differing_only_in_case() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let domain = format!("Bar-{}.com", Uuid::new_v4());
+    let register_as = |email: String| {
+        send(
+            &state,
+            Method::POST,
+            "/api/auth/register",
+            None,
+            Some(json!({ "email": email, "password": "password123", "name": "Foo" })),
+        )
+    };
+
+    let (status, _) = register_as(format!("Foo@{}", domain)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = register_as(format!("foo@{}", domain.to_lowercase())).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "CONFLICT");
+}
+
+#[tokio::test]
+async fn test_email_unique_ignoring_case_in_database() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let local = format!("legacy-{}", Uuid::new_v4());
+    let insert = |email: String| {
+        user::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            email: Set(email),
+            name: Set("Legacy".to_string()),
+            role: Set(UserRole::Traveller),
+            ..Default::default()
+        }
+        .insert(&state.db)
+    };
+
+    // Bypasses the handlers' normalization, so only the index can catch it
+    insert(format!("{}@test.example", local)).await.unwrap();
+    let duplicate = insert(format!("{}@TEST.example", local.to_uppercase())).await;
+    assert!(matches!(
+        duplicate.unwrap_err().sql_err(),
+        Some(SqlErr::UniqueConstraintViolation(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_register_rejects_invalid_email() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let result = register(State(state.clone()), registration("notanemail")).await;
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_refresh_endpoint() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let email = format!("refresh-{}@test.example", Uuid::new_v4());
+
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        "/api/auth/register",
+        None,
+        Some(json!({ "email": email, "password": "password123", "name": "Refresher" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // A refresh token yields a new access token
+    let (status, refreshed) = send(
+        &state,
+        Method::POST,
+        "/api/auth/refresh",
+        None,
+        Some(json!({ "refresh_token": body["refresh_token"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(refreshed["token"].is_string());
+
+    // An access token is refused
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/auth/refresh",
+        None,
+        Some(json!({ "refresh_token": body["token"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_logged_out_tokens_are_refused() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let email = format!("logout-{}@test.example", Uuid::new_v4());
+
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        "/api/auth/register",
+        None,
+        Some(json!({ "email": email, "password": "password123", "name": "Leaver" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let token = body["token"].as_str().unwrap();
+
+    let (status, _) = send(&state, Method::GET, "/api/bookings", Some(token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/auth/logout",
+        Some(token),
+        Some(json!({ "refresh_token": body["refresh_token"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The access token no longer authenticates
+    let (status, _) = send(&state, Method::GET, "/api/bookings", Some(token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // Nor can the revoked refresh token mint a new one
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/auth/refresh",
+        None,
+        Some(json!({ "refresh_token": body["refresh_token"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+/// Register a traveller with "password123" and return their access token
+async fn register_with_password(state: &AppState, email: &str) -> String {
+    let (status, body) = send(
+        state,
+        Method::POST,
+        "/api/auth/register",
+        None,
+        Some(json!({ "email": email, "password": "password123", "name": "Changer" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    body["token"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_change_password() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let email = format!("change-{}@test.example", Uuid::new_v4());
+    let token = register_with_password(&state, &email).await;
+
+    let (status, _) = send(
+        &state,
+        Method::PUT,
+        "/api/auth/password",
+        Some(&token),
+        Some(json!({ "current_password": "password123", "new_password": "n3wSecret!" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let login = |password: &'static str| {
+        send(
+            &state,
+            Method::POST,
+            "/api/auth/login",
+            None,
+            Some(json!({ "email": email, "password": password })),
+        )
+    };
+    assert_eq!(login("password123").await.0, StatusCode::UNAUTHORIZED);
+    assert_eq!(login("n3wSecret!").await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_login_upgrades_weak_password_hash() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let weak = HashCost {
+        memory_kib: 1024,
+        iterations: 1,
+        parallelism: 1,
+    };
+    let mut traveller: user::ActiveModel = create_user(&state, UserRole::Traveller).await.into();
+    traveller.password_hash = Set(Some(hash_password("password123", &weak).unwrap()));
+    let traveller = traveller.update(&state.db).await.unwrap();
+
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": traveller.email, "password": "password123" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let stored = user::Entity::find_by_id(traveller.id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap()
+        .password_hash
+        .unwrap();
+    let params = Params::try_from(&PasswordHash::new(&stored).unwrap()).unwrap();
+    let cost = state.config.password_hash_cost();
+    assert_eq!(params.m_cost(), cost.memory_kib);
+    assert_eq!(params.t_cost(), cost.iterations);
+    assert_eq!(params.p_cost(), cost.parallelism);
+
+    // The upgraded hash still accepts the password
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": traveller.email, "password": "password123" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_soft_deleted_user_cannot_sign_in() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let email = format!("deleted-{}@test.example", Uuid::new_v4());
+    let (status, registered) = send(
+        &state,
+        Method::POST,
+        "/api/auth/register",
+        None,
+        Some(json!({ "email": email, "password": "password123", "name": "Leaver" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let admin = create_user(&state, UserRole::Admin).await;
+    let uri = format!(
+        "/api/admin/users/{}",
+        registered["user"]["id"].as_str().unwrap()
+    );
+    let (status, _) = send(
+        &state,
+        Method::DELETE,
+        &uri,
+        Some(&token_for(&state, &admin)),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The row is kept, only marked as deleted
+    let id: Uuid = registered["user"]["id"].as_str().unwrap().parse().unwrap();
+    let kept = user::Entity::find_by_id(id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(kept.deleted_at.is_some());
+
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": email, "password": "password123" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"]["message"], "Invalid email or password");
+
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/auth/refresh",
+        None,
+        Some(json!({ "refresh_token": registered["refresh_token"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // An access token issued before the deletion stops working too
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        "/api/bookings",
+        registered["token"].as_str(),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"]["message"], "Account no longer exists");
+
+    // The email stays taken
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/auth/register",
+        None,
+        Some(json!({ "email": email, "password": "password123", "name": "Returner" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_change_password_with_wrong_current_password() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let email = format!("change-{}@test.example", Uuid::new_v4());
+    let token = register_with_password(&state, &email).await;
+
+    let (status, body) = send(
+        &state,
+        Method::PUT,
+        "/api/auth/password",
+        Some(&token),
+        Some(json!({ "current_password": "wrongpass1", "new_password": "n3wSecret!" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"]["code"], "UNAUTHORIZED");
+}
+
+#[tokio::test]
+async fn test_change_password_rejects_weak_password() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let email = format!("change-{}@test.example", Uuid::new_v4());
+    let token = register_with_password(&state, &email).await;
+
+    let (status, _) = send(
+        &state,
+        Method::PUT,
+        "/api/auth/password",
+        Some(&token),
+        Some(json!({ "current_password": "password123", "new_password": "short" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_delete_own_account() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    // Past trips don't block deletion and go with the account
+    let past = create_journey(&state, 10, Utc::now() - Duration::days(3)).await;
+    insert_booking(&state, &past, &traveller, 1).await;
+    let token = token_for(&state, &traveller);
+
+    let (status, _) = send(
+        &state,
+        Method::DELETE,
+        "/api/auth/account",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let gone = user::Entity::find_by_id(traveller.id)
+        .one(&state.db)
+        .await
+        .unwrap();
+    assert!(gone.is_none());
+
+    // The token used to delete the account is revoked
+    let (status, _) = send(&state, Method::GET, "/api/bookings", Some(&token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_delete_account_blocked_by_upcoming_booking() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let upcoming = create_journey(&state, 10, tomorrow()).await;
+    insert_booking(&state, &upcoming, &traveller, 1).await;
+
+    let (status, body) = send(
+        &state,
+        Method::DELETE,
+        "/api/auth/account",
+        Some(&token_for(&state, &traveller)),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "CONFLICT");
+
+    let still_there = user::Entity::find_by_id(traveller.id)
+        .one(&state.db)
+        .await
+        .unwrap();
+    assert!(still_there.is_some());
+}
+
+#[tokio::test]
+async fn test_admin_cannot_delete_own_account() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+
+    let (status, _) = send(
+        &state,
+        Method::DELETE,
+        "/api/auth/account",
+        Some(&token_for(&state, &admin)),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_token_lifetime_depends_on_role() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    state.config.jwt_expiration_hours_admin = 1;
+    state.config.jwt_expiration_hours_traveller = 48;
+
+    let admin_email = format!("admin-{}@test.example", Uuid::new_v4());
+    seed_admin(
+        &state.db,
+        &admin_email,
+        "password123",
+        &state.config.password_hash_cost(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    let (status, admin) = send(
+        &state,
+        Method::POST,
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": admin_email, "password": "password123" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let Json(traveller) = register(
+        State(state.clone()),
+        registration(&format!("{}@test.example", Uuid::new_v4())),
+    )
+    .await
+    .unwrap();
+
+    let lifetime = |token: &str| {
+        let claims = verify_access_token(token, (&state.config).into()).unwrap();
+        Duration::seconds(claims.exp - claims.iat)
+    };
+    assert_eq!(
+        lifetime(admin["token"].as_str().unwrap()),
+        Duration::hours(1)
+    );
+    assert_eq!(lifetime(&traveller.token), Duration::hours(48));
+}
+
+#[tokio::test]
+async fn test_register_validates_name() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let register_named = |name: String| {
+        let state = &state;
+        async move {
+            let body = json!({
+                "email": format!("{}@test.example", Uuid::new_v4()),
+                "password": "password123",
+                "name": name,
+            });
+            send(state, Method::POST, "/api/auth/register", None, Some(body)).await
+        }
+    };
+
+    let (status, _) = register_named("   ".to_string()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = register_named("a".repeat(101)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = register_named("  Ann Lee  ".to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user"]["name"], "Ann Lee");
+}
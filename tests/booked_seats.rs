@@ -0,0 +1,49 @@
+mod common;
+
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+use bus_travel_backend::{
+    db::booked_seats_by_journey,
+    entities::{booking, user::UserRole},
+};
+
+use common::{create_journey, create_user, insert_booking, test_state, tomorrow};
+
+#[tokio::test]
+async fn test_grouped_counts_match_per_journey_sums() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let other = create_user(&state, UserRole::Traveller).await;
+
+    let busy = create_journey(&state, 40, tomorrow()).await;
+    insert_booking(&state, &busy, &traveller, 3).await;
+    insert_booking(&state, &busy, &other, 4).await;
+
+    let quiet = create_journey(&state, 40, tomorrow()).await;
+    insert_booking(&state, &quiet, &traveller, 1).await;
+
+    let empty = create_journey(&state, 40, tomorrow()).await;
+
+    let ids = [busy.id, quiet.id, empty.id];
+    let grouped = booked_seats_by_journey(&state.db, &ids).await.unwrap();
+
+    for id in ids {
+        // Previous behavior: one query per journey, summed in Rust
+        let per_journey: i32 = booking::Entity::find()
+            .filter(booking::Column::JourneyId.eq(id))
+            .all(&state.db)
+            .await
+            .unwrap()
+            .iter()
+            .map(|b| b.seats)
+            .sum();
+
+        assert_eq!(grouped.get(&id).copied().unwrap_or(0), per_journey);
+    }
+
+    assert_eq!(grouped.get(&busy.id), Some(&7));
+    assert_eq!(grouped.get(&empty.id), None);
+}
@@ -0,0 +1,716 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::{HeaderMap, Method, StatusCode, header},
+};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppError, AppState,
+    entities::{
+        booking::{self, BookingStatus},
+        city, journey,
+        user::{self, UserRole},
+    },
+    handlers::traveller::{
+        BookingResponse, CreateBookingRequest, UpdateBookingRequest, cancel_booking,
+        create_booking, update_booking,
+    },
+    utils::{etag::booking_etag, notify::Notifier},
+};
+
+use common::{
+    city_by_name, claims_for, create_journey, create_journey_between, create_user, insert_booking,
+    send, test_state, token_for, tomorrow,
+};
+
+async fn book(
+    state: &AppState,
+    journey: &journey::Model,
+    traveller: &user::Model,
+    seats: i32,
+    waitlist: bool,
+) -> Result<BookingResponse, AppError> {
+    create_booking(
+        State(state.clone()),
+        Extension(claims_for(traveller)),
+        HeaderMap::new(),
+        Json(CreateBookingRequest {
+            journey_id: journey.id,
+            seats,
+            pickup_lat: -10.1836,
+            pickup_lng: 123.6257,
+            waitlist,
+        }),
+    )
+    .await
+    .map(|(_, Json(b))| b)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_bookings_for_last_seat() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let journey = create_journey(&state, 1, tomorrow()).await;
+
+    let mut travellers = Vec::new();
+    for _ in 0..8 {
+        travellers.push(create_user(&state, UserRole::Traveller).await);
+    }
+
+    let attempts: Vec<_> = travellers
+        .iter()
+        .map(|t| {
+            let state = state.clone();
+            let claims = claims_for(t);
+            tokio::spawn(async move {
+                create_booking(
+                    State(state),
+                    Extension(claims),
+                    HeaderMap::new(),
+                    Json(CreateBookingRequest {
+                        journey_id: journey.id,
+                        seats: 1,
+                        pickup_lat: -10.1836,
+                        pickup_lng: 123.6257,
+                        waitlist: false,
+                    }),
+                )
+                .await
+            })
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for attempt in attempts {
+        results.push(attempt.await.unwrap());
+    }
+
+    assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+    assert!(
+        results
+            .iter()
+            .filter(|r| r.is_err())
+            .all(|r| matches!(r, Err(AppError::Conflict(_))))
+    );
+}
+
+async fn status_of(state: &AppState, booking_id: uuid::Uuid) -> BookingStatus {
+    booking::Entity::find_by_id(booking_id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap()
+        .status
+}
+
+#[tokio::test]
+async fn test_full_journey_waitlist() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 2, tomorrow()).await;
+    let first = create_user(&state, UserRole::Traveller).await;
+    insert_booking(&state, &journey, &first, 2).await;
+
+    // Without opting in, a full journey is still refused
+    let late = create_user(&state, UserRole::Traveller).await;
+    let refused = book(&state, &journey, &late, 1, false).await;
+    assert!(matches!(refused, Err(AppError::Conflict(_))));
+
+    let waiting = book(&state, &journey, &late, 1, true).await.unwrap();
+    assert_eq!(waiting.status, BookingStatus::Waitlisted);
+}
+
+#[tokio::test]
+async fn test_cancellation_promotes_earliest_waitlisted_booking() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 2, tomorrow()).await;
+    let holder = create_user(&state, UserRole::Traveller).await;
+    let held = insert_booking(&state, &journey, &holder, 2).await;
+
+    let earliest = create_user(&state, UserRole::Traveller).await;
+    let later = create_user(&state, UserRole::Traveller).await;
+    let earliest_booking = book(&state, &journey, &earliest, 2, true).await.unwrap();
+    let later_booking = book(&state, &journey, &later, 1, true).await.unwrap();
+
+    let Json(cancelled) = cancel_booking(
+        State(state.clone()),
+        Extension(claims_for(&holder)),
+        Path(held.id),
+    )
+    .await
+    .unwrap();
+    assert_eq!(cancelled["message"], "Booking cancelled");
+
+    // The two freed seats go to the earliest request; the later one keeps waiting
+    assert_eq!(
+        status_of(&state, earliest_booking.id).await,
+        BookingStatus::Confirmed
+    );
+    assert_eq!(
+        status_of(&state, later_booking.id).await,
+        BookingStatus::Waitlisted
+    );
+}
+
+/// Records every notification instead of sending it
+#[derive(Default)]
+struct RecordingNotifier {
+    sent: Mutex<Vec<(String, Uuid, BookingStatus)>>,
+}
+
+#[async_trait]
+impl Notifier for RecordingNotifier {
+    async fn booking_confirmed(&self, to: &str, booking: &BookingResponse) {
+        self.sent
+            .lock()
+            .unwrap()
+            .push((to.to_string(), booking.id, booking.status));
+    }
+
+    async fn journey_rescheduled(
+        &self,
+        _to: &str,
+        _booking_id: Uuid,
+        _old_departure: DateTime<Utc>,
+        _new_departure: DateTime<Utc>,
+    ) {
+    }
+}
+
+#[tokio::test]
+async fn test_booking_notifies_traveller() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    let notifier = Arc::new(RecordingNotifier::default());
+    state.notifier = notifier.clone();
+
+    let journey = create_journey(&state, 1, tomorrow()).await;
+    let first = create_user(&state, UserRole::Traveller).await;
+    let second = create_user(&state, UserRole::Traveller).await;
+    let confirmed = book(&state, &journey, &first, 1, false).await.unwrap();
+    let waiting = book(&state, &journey, &second, 1, true).await.unwrap();
+
+    // A rejected booking sends nothing
+    assert!(book(&state, &journey, &second, 1, false).await.is_err());
+
+    assert_eq!(
+        *notifier.sent.lock().unwrap(),
+        vec![
+            (first.email, confirmed.id, BookingStatus::Confirmed),
+            (second.email, waiting.id, BookingStatus::Waitlisted),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_cancel_someone_elses_booking_not_found() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 4, tomorrow()).await;
+    let owner = create_user(&state, UserRole::Traveller).await;
+    let other = create_user(&state, UserRole::Traveller).await;
+    let booking = insert_booking(&state, &journey, &owner, 1).await;
+
+    let cancel = |id| {
+        cancel_booking(
+            State(state.clone()),
+            Extension(claims_for(&other)),
+            Path(id),
+        )
+    };
+
+    // Indistinguishable from a booking that doesn't exist
+    let Err(AppError::NotFound(foreign)) = cancel(booking.id).await else {
+        panic!("expected 404 for another traveller's booking");
+    };
+    let Err(AppError::NotFound(missing)) = cancel(uuid::Uuid::new_v4()).await else {
+        panic!("expected 404 for an unknown booking");
+    };
+    assert_eq!(foreign, missing);
+
+    assert_eq!(
+        status_of(&state, booking.id).await,
+        BookingStatus::Confirmed
+    );
+}
+
+#[tokio::test]
+async fn test_get_booking() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 4, tomorrow()).await;
+    let owner = create_user(&state, UserRole::Traveller).await;
+    let other = create_user(&state, UserRole::Traveller).await;
+    let booking = insert_booking(&state, &journey, &owner, 2).await;
+    let get = |user: &user::Model, id: Uuid| {
+        let uri = format!("/api/bookings/{}", id);
+        let token = token_for(&state, user);
+        let state = &state;
+        async move { send(state, Method::GET, &uri, Some(&token), None).await }
+    };
+
+    let (status, body) = get(&owner, booking.id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], booking.id.to_string());
+    assert_eq!(body["seats"], 2);
+    assert_eq!(body["total_fare"], booking.total_fare);
+    assert_eq!(body["status"], "confirmed");
+    assert_eq!(body["journey_status"], "scheduled");
+
+    // Missing and foreign bookings are indistinguishable
+    let (status, missing) = get(&owner, Uuid::new_v4()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, foreign) = get(&other, booking.id).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(missing["error"]["message"], foreign["error"]["message"]);
+}
+
+#[tokio::test]
+async fn test_my_bookings_show_journey_occupancy() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let booking = insert_booking(&state, &journey, &traveller, 2).await;
+    for _ in 0..2 {
+        let other = create_user(&state, UserRole::Traveller).await;
+        insert_booking(&state, &journey, &other, 3).await;
+    }
+
+    let token = token_for(&state, &traveller);
+    let (status, body) = send(&state, Method::GET, "/api/bookings", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+
+    let item = &body["items"][0];
+    assert_eq!(item["id"], booking.id.to_string());
+    assert_eq!(item["seats"], 2);
+    assert_eq!(item["journey_total_seats"], 10);
+    // Everyone's seats count, not just the traveller's own
+    assert_eq!(item["journey_available_seats"], 2);
+}
+
+#[tokio::test]
+async fn test_cancellation_cutoff() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    state.config.cancellation_cutoff_hours = 2;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let cancel = |id| {
+        cancel_booking(
+            State(state.clone()),
+            Extension(claims_for(&traveller)),
+            Path(id),
+        )
+    };
+
+    // Well before departure
+    let later = create_journey(&state, 4, tomorrow()).await;
+    let booking = insert_booking(&state, &later, &traveller, 1).await;
+    assert!(cancel(booking.id).await.is_ok());
+
+    // Inside the cutoff window
+    let soon = create_journey(&state, 4, Utc::now() + Duration::hours(1)).await;
+    let booking = insert_booking(&state, &soon, &traveller, 1).await;
+    let result = cancel(booking.id).await;
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+    assert_eq!(
+        status_of(&state, booking.id).await,
+        BookingStatus::Confirmed
+    );
+}
+
+#[tokio::test]
+async fn test_pickup_closer_to_destination_rejected() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    // A town 8 km east of Kupang, so the edge of Kupang's 5 km pickup area is nearer to it
+    let origin = city_by_name(&state, "Kupang").await;
+    let dest = city::ActiveModel {
+        name: Set(format!("Oesao-{}", Uuid::new_v4())),
+        center_lat: Set(origin.center_lat),
+        center_lng: Set(origin.center_lng + 0.073),
+        pickup_radius_km: Set(5.0),
+        timezone: Set(origin.timezone.clone()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .unwrap();
+    let journey = create_journey_between(&state, &origin, &dest, 10, tomorrow()).await;
+
+    let book_at = |lng| {
+        let state = &state;
+        let journey_id = journey.id;
+        let lat = origin.center_lat;
+        async move {
+            let traveller = create_user(state, UserRole::Traveller).await;
+            create_booking(
+                State(state.clone()),
+                Extension(claims_for(&traveller)),
+                HeaderMap::new(),
+                Json(CreateBookingRequest {
+                    journey_id,
+                    seats: 1,
+                    pickup_lat: lat,
+                    pickup_lng: lng,
+                    waitlist: false,
+                }),
+            )
+            .await
+        }
+    };
+
+    // West of Kupang's center, away from the destination
+    let (_, Json(booked)) = book_at(origin.center_lng - 0.02).await.unwrap();
+    // Inside Kupang's radius, but most of the way to the destination
+    let result = book_at(origin.center_lng + 0.04).await;
+    assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("closer to")));
+
+    booking::Entity::delete_by_id(booked.id)
+        .exec(&state.db)
+        .await
+        .unwrap();
+    journey::Entity::delete_by_id(journey.id)
+        .exec(&state.db)
+        .await
+        .unwrap();
+    city::Entity::delete_by_id(dest.id)
+        .exec(&state.db)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_booking_close_time() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let departing_in_two_hours = |close_minutes: i32| {
+        let state = &state;
+        async move {
+            let journey = create_journey(state, 4, Utc::now() + Duration::hours(2)).await;
+            let mut active: journey::ActiveModel = journey.into();
+            active.booking_close_minutes = Set(close_minutes);
+            active.update(&state.db).await.unwrap()
+        }
+    };
+
+    // Booking closes an hour before departure: still open
+    let open = departing_in_two_hours(60).await;
+    assert!(book(&state, &open, &traveller, 1, false).await.is_ok());
+
+    // Booking closed three hours before departure
+    let closed = departing_in_two_hours(180).await;
+    let result = book(&state, &closed, &traveller, 1, false).await;
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_booking_over_seat_cap_rejected() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 50, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    let cap = state.config.max_seats_per_booking;
+    let result = book(&state, &journey, &traveller, cap + 1, false).await;
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+    let booking = book(&state, &journey, &traveller, cap, false)
+        .await
+        .unwrap();
+    assert_eq!(booking.seats, cap);
+}
+
+#[tokio::test]
+async fn test_second_booking_on_journey_rejected_by_default() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    book(&state, &journey, &traveller, 1, false).await.unwrap();
+    let result = book(&state, &journey, &traveller, 1, false).await;
+    assert!(matches!(result, Err(AppError::Conflict(_))));
+}
+
+#[tokio::test]
+async fn test_raised_booking_limit_allows_separate_bookings() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    state.config.max_bookings_per_user_per_journey = 2;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    let first = book(&state, &journey, &traveller, 2, false).await.unwrap();
+    let second = book(&state, &journey, &traveller, 3, false).await.unwrap();
+    assert_ne!(first.id, second.id);
+
+    let result = book(&state, &journey, &traveller, 1, false).await;
+    assert!(
+        matches!(result, Err(AppError::Conflict(msg)) if msg.contains("2 bookings")),
+        "third booking should exceed the limit"
+    );
+}
+
+#[tokio::test]
+async fn test_booking_with_out_of_range_coordinates_rejected() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    for (pickup_lat, pickup_lng) in [(91.0, 123.6257), (-10.1836, 181.0), (f64::NAN, 0.0)] {
+        let result = create_booking(
+            State(state.clone()),
+            Extension(claims_for(&traveller)),
+            HeaderMap::new(),
+            Json(CreateBookingRequest {
+                journey_id: journey.id,
+                seats: 1,
+                pickup_lat,
+                pickup_lng,
+                waitlist: false,
+            }),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(AppError::BadRequest(ref msg)) if msg.contains("must be between")),
+            "({}, {}) should be rejected",
+            pickup_lat,
+            pickup_lng
+        );
+    }
+}
+
+async fn modify_with(
+    state: &AppState,
+    traveller: &user::Model,
+    booking_id: Uuid,
+    headers: HeaderMap,
+    changes: UpdateBookingRequest,
+) -> Result<BookingResponse, AppError> {
+    update_booking(
+        State(state.clone()),
+        Extension(claims_for(traveller)),
+        Path(booking_id),
+        headers,
+        Json(changes),
+    )
+    .await
+    .map(|(_, Json(b))| b)
+}
+
+/// Modify a booking, sending its current ETag as `If-Match`
+async fn modify(
+    state: &AppState,
+    traveller: &user::Model,
+    booking_id: Uuid,
+    changes: UpdateBookingRequest,
+) -> Result<BookingResponse, AppError> {
+    let current = booking::Entity::find_by_id(booking_id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(header::IF_MATCH, booking_etag(&current).parse().unwrap());
+    modify_with(state, traveller, booking_id, headers, changes).await
+}
+
+#[tokio::test]
+async fn test_update_booking_seats_beyond_availability() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 5, tomorrow()).await;
+    let alice = create_user(&state, UserRole::Traveller).await;
+    let bob = create_user(&state, UserRole::Traveller).await;
+    let booking = book(&state, &journey, &alice, 2, false).await.unwrap();
+    book(&state, &journey, &bob, 2, false).await.unwrap();
+
+    // Alice's own 2 seats count towards what she can grow into: 5 - 2 (Bob) = 3
+    let grow_to = |seats| UpdateBookingRequest {
+        seats: Some(seats),
+        pickup_lat: None,
+        pickup_lng: None,
+    };
+    let result = modify(&state, &alice, booking.id, grow_to(4)).await;
+    assert!(matches!(result, Err(AppError::Conflict(_))));
+
+    let updated = modify(&state, &alice, booking.id, grow_to(3))
+        .await
+        .unwrap();
+    assert_eq!(updated.seats, 3);
+}
+
+#[tokio::test]
+async fn test_update_booking_moves_pickup() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 5, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let booking = book(&state, &journey, &traveller, 1, false).await.unwrap();
+
+    let updated = modify(
+        &state,
+        &traveller,
+        booking.id,
+        UpdateBookingRequest {
+            seats: None,
+            pickup_lat: Some(-10.17),
+            pickup_lng: Some(123.61),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!((updated.pickup_lat, updated.pickup_lng), (-10.17, 123.61));
+    assert_eq!(updated.seats, 1);
+
+    // Soe is far outside Kupang's pickup radius
+    let result = modify(
+        &state,
+        &traveller,
+        booking.id,
+        UpdateBookingRequest {
+            seats: None,
+            pickup_lat: Some(-9.8607),
+            pickup_lng: Some(124.2838),
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_update_booking_requires_current_if_match() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 5, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let booking = book(&state, &journey, &traveller, 1, false).await.unwrap();
+    let stale = booking::Entity::find_by_id(booking.id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap();
+    let two_seats = || UpdateBookingRequest {
+        seats: Some(2),
+        pickup_lat: None,
+        pickup_lng: None,
+    };
+
+    let result = modify_with(
+        &state,
+        &traveller,
+        booking.id,
+        HeaderMap::new(),
+        two_seats(),
+    )
+    .await;
+    assert!(matches!(result, Err(AppError::PreconditionRequired(_))));
+
+    modify(&state, &traveller, booking.id, two_seats())
+        .await
+        .unwrap();
+
+    // The tag read before that edit no longer matches
+    let mut headers = HeaderMap::new();
+    headers.insert(header::IF_MATCH, booking_etag(&stale).parse().unwrap());
+    let result = modify_with(&state, &traveller, booking.id, headers, two_seats()).await;
+    assert!(matches!(result, Err(AppError::PreconditionFailed(_))));
+}
+
+#[tokio::test]
+async fn test_update_someone_elses_booking_forbidden() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 5, tomorrow()).await;
+    let owner = create_user(&state, UserRole::Traveller).await;
+    let intruder = create_user(&state, UserRole::Traveller).await;
+    let booking = book(&state, &journey, &owner, 1, false).await.unwrap();
+
+    let result = modify(
+        &state,
+        &intruder,
+        booking.id,
+        UpdateBookingRequest {
+            seats: Some(2),
+            pickup_lat: None,
+            pickup_lng: None,
+        },
+    )
+    .await;
+    assert!(matches!(result, Err(AppError::Forbidden(_))));
+}
+
+#[tokio::test]
+async fn test_cancellation_frees_seats_but_keeps_history() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 3, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let other = create_user(&state, UserRole::Traveller).await;
+    let booking = insert_booking(&state, &journey, &traveller, 2).await;
+    insert_booking(&state, &journey, &other, 1).await;
+    let cancel = || {
+        cancel_booking(
+            State(state.clone()),
+            Extension(claims_for(&traveller)),
+            Path(booking.id),
+        )
+    };
+
+    assert!(cancel().await.is_ok());
+    assert_eq!(
+        status_of(&state, booking.id).await,
+        BookingStatus::Cancelled
+    );
+
+    // Still in the traveller's history, flagged, and no longer holding its seats
+    let token = token_for(&state, &traveller);
+    let (status, body) = send(&state, Method::GET, "/api/bookings", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+    let item = &body["items"][0];
+    assert_eq!(item["id"], booking.id.to_string());
+    assert_eq!(item["status"], "cancelled");
+    assert_eq!(item["cancelled"], true);
+    assert!(item["cancelled_at"].is_string());
+    assert_eq!(item["journey_available_seats"], 2);
+
+    // Cancelling twice is refused, but the traveller can book the journey again
+    assert!(matches!(cancel().await, Err(AppError::Conflict(_))));
+    let rebooked = book(&state, &journey, &traveller, 2, false).await.unwrap();
+    assert_eq!(rebooked.status, BookingStatus::Confirmed);
+    assert!(!rebooked.cancelled);
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use std::collections::HashSet;
+
+use axum::http::{Method, StatusCode};
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveModelTrait, Set};
+use serde_json::Value;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppState,
+    entities::{booking, user::UserRole},
+    utils::pagination::Cursor,
+};
+
+use common::{
+    create_journey, create_user, insert_booking, send, test_state, token_for, tomorrow,
+    unique_future_date,
+};
+
+/// Fetch one page of the admin booking list
+async fn page(state: &AppState, token: &str, query: &str) -> (Vec<Value>, Option<String>) {
+    let (status, body) = send(
+        state,
+        Method::GET,
+        &format!("/api/admin/bookings?{}", query),
+        Some(token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let items = body["items"].as_array().unwrap().clone();
+    let next = body["next_cursor"].as_str().map(str::to_string);
+    (items, next)
+}
+
+fn position(item: &Value) -> (DateTime<Utc>, Uuid) {
+    (
+        item["created_at"].as_str().unwrap().parse().unwrap(),
+        item["id"].as_str().unwrap().parse().unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_cursor_walk_has_no_duplicates_or_gaps() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 20, tomorrow()).await;
+    let token = token_for(&state, &admin);
+
+    let mut ours = HashSet::new();
+    for _ in 0..5 {
+        ours.insert(insert_booking(&state, &journey, &traveller, 1).await.id);
+    }
+
+    let mut seen = HashSet::new();
+    let mut positions = Vec::new();
+    let mut after: Option<String> = None;
+    loop {
+        let query = match &after {
+            Some(cursor) => format!("sort=created_at_asc&limit=100&after={}", cursor),
+            None => "sort=created_at_asc&limit=100".to_string(),
+        };
+        let (items, next) = page(&state, &token, &query).await;
+        assert!(items.len() <= 100);
+
+        for item in &items {
+            let id = position(item).1;
+            assert!(seen.insert(id), "booking {} returned twice", id);
+            positions.push(position(item));
+        }
+
+        match next {
+            Some(cursor) => after = Some(cursor),
+            None => break,
+        }
+    }
+
+    // Pages join up in strictly increasing (created_at, id) order
+    assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    assert!(ours.is_subset(&seen));
+}
+
+#[tokio::test]
+async fn test_cursor_breaks_created_at_ties_by_id() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 20, tomorrow()).await;
+    let token = token_for(&state, &admin);
+
+    // Five bookings sharing one far-future timestamp
+    let created_at = unique_future_date()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+        .and_utc();
+    let mut ours = Vec::new();
+    for _ in 0..5 {
+        let booking = insert_booking(&state, &journey, &traveller, 1).await;
+        let mut active: booking::ActiveModel = booking.into();
+        active.created_at = Set(created_at.into());
+        ours.push(active.update(&state.db).await.unwrap().id);
+    }
+    ours.sort();
+    ours.reverse();
+
+    // Newest first, starting just above the shared timestamp so earlier runs' rows don't
+    // come first; pages of two split the tied bookings across three pages
+    let mut after = Some(
+        Cursor {
+            timestamp: created_at,
+            id: Uuid::max(),
+        }
+        .encode(),
+    );
+    let mut listed = Vec::new();
+    while listed.len() < ours.len() {
+        let cursor = after.expect("ran out of pages early");
+        let (items, next) = page(&state, &token, &format!("limit=2&after={}", cursor)).await;
+        listed.extend(items.iter().map(position));
+        after = next;
+    }
+
+    let ids: Vec<Uuid> = listed[..ours.len()].iter().map(|p| p.1).collect();
+    assert_eq!(ids, ours);
+    assert!(listed[..ours.len()].iter().all(|p| p.0 == created_at));
+}
+
+#[tokio::test]
+async fn test_invalid_cursor_is_bad_request() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    let (status, _) = send(
+        &state,
+        Method::GET,
+        "/api/admin/bookings?after=not-a-cursor",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use sea_orm::EntityTrait;
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::entities::{booking, user::UserRole};
+
+use common::{create_journey, create_user, insert_booking, send, test_state, token_for, tomorrow};
+
+const URI: &str = "/api/admin/bookings/bulk-delete";
+
+#[tokio::test]
+async fn test_bulk_delete_mixed_ids() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let first = insert_booking(&state, &journey, &traveller, 1).await;
+    let second = insert_booking(&state, &journey, &traveller, 2).await;
+    let kept = insert_booking(&state, &journey, &traveller, 1).await;
+    let missing = Uuid::new_v4();
+
+    let token = token_for(&state, &admin);
+    let body = json!({ "booking_ids": [first.id, missing, second.id, first.id] });
+    let (status, json) = send(&state, Method::POST, URI, Some(&token), Some(body)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["deleted"], 2);
+    assert_eq!(json["not_found"], 1);
+    assert_eq!(json["not_found_ids"], json!([missing]));
+
+    for id in [first.id, second.id] {
+        let gone = booking::Entity::find_by_id(id)
+            .one(&state.db)
+            .await
+            .unwrap();
+        assert!(gone.is_none());
+    }
+    let still = booking::Entity::find_by_id(kept.id)
+        .one(&state.db)
+        .await
+        .unwrap();
+    assert!(still.is_some());
+}
+
+#[tokio::test]
+async fn test_bulk_delete_rejects_empty_and_oversized_lists() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+
+    let body = json!({ "booking_ids": [] });
+    let (status, _) = send(&state, Method::POST, URI, Some(&token), Some(body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let ids: Vec<Uuid> = (0..501).map(|_| Uuid::new_v4()).collect();
+    let body = json!({ "booking_ids": ids });
+    let (status, _) = send(&state, Method::POST, URI, Some(&token), Some(body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_bulk_delete_requires_admin() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &traveller);
+
+    let body = json!({ "booking_ids": [Uuid::new_v4()] });
+    let (status, _) = send(&state, Method::POST, URI, Some(&token), Some(body)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, IntoActiveModel, Set};
+use serde_json::Value;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppState,
+    entities::{city, journey::JourneyStatus},
+};
+
+use common::{create_journey_between, send, test_state, tomorrow};
+
+/// Insert a throwaway city far from the seeded ones, so nearest-city lookups are unaffected
+async fn insert_city(state: &AppState, name: &str, lat: f64) -> city::Model {
+    city::ActiveModel {
+        name: Set(format!("{}-{}", name, Uuid::new_v4())),
+        center_lat: Set(lat),
+        center_lng: Set(-150.0),
+        pickup_radius_km: Set(10.0),
+        timezone: Set("Pacific/Honolulu".to_string()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .unwrap()
+}
+
+fn find_city(cities: &Value, id: i32) -> &Value {
+    cities
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["id"] == id)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_list_cities_with_journey_counts() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let a = insert_city(&state, "Alpha", 10.0).await;
+    let b = insert_city(&state, "Beta", 10.5).await;
+    let c = insert_city(&state, "Gamma", 11.0).await;
+
+    create_journey_between(&state, &a, &b, 10, tomorrow()).await;
+    create_journey_between(&state, &a, &c, 10, tomorrow()).await;
+    create_journey_between(&state, &b, &a, 10, tomorrow()).await;
+    // Neither departed nor cancelled journeys count as upcoming
+    create_journey_between(&state, &a, &b, 10, Utc::now() - Duration::hours(1)).await;
+    let cancelled = create_journey_between(&state, &c, &a, 10, tomorrow()).await;
+    let mut cancelled = cancelled.into_active_model();
+    cancelled.status = Set(JourneyStatus::Cancelled);
+    cancelled.update(&state.db).await.unwrap();
+
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        "/api/cities?with_journey_counts=true",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    for (city, origin, destination) in [(&a, 2, 1), (&b, 1, 1), (&c, 0, 1)] {
+        let entry = find_city(&body, city.id);
+        assert_eq!(entry["name"], city.name);
+        assert_eq!(entry["upcoming_origin_count"], origin);
+        assert_eq!(entry["upcoming_destination_count"], destination);
+    }
+}
+
+#[tokio::test]
+async fn test_list_cities_without_counts() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let city = insert_city(&state, "Delta", 12.0).await;
+
+    let (status, body) = send(&state, Method::GET, "/api/cities", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let entry = find_city(&body, city.id);
+    assert_eq!(entry["timezone"], "Pacific/Honolulu");
+    assert!(entry.get("upcoming_origin_count").is_none());
+    assert!(entry.get("upcoming_destination_count").is_none());
+}
+
+#[tokio::test]
+async fn test_city_list_envelope() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let (status, raw) = send(&state, Method::GET, "/api/cities", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(raw.is_array());
+
+    let (status, wrapped) =
+        send(&state, Method::GET, "/api/cities?envelope=true", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(wrapped["data"], raw);
+    assert!(wrapped["meta"]["request_id"].is_string());
+
+    // Errors keep their own shape either way
+    let (status, error) = send(
+        &state,
+        Method::GET,
+        "/api/journeys/not-a-uuid?envelope=true",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error.get("data").is_none());
+}
@@ -0,0 +1,291 @@
+//! Shared setup for database-backed integration tests.
+//!
+//! Tests run against the database in `TEST_DATABASE_URL` and are skipped when it is unset.
+#![allow(dead_code)]
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::{Body, Bytes},
+    extract::ConnectInfo,
+    http::{HeaderMap, Method, Request, StatusCode, header},
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, Database, EntityTrait, QueryFilter, Set};
+use sea_orm_migration::MigratorTrait;
+use serde_json::Value;
+use tokio::sync::OnceCell;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppState, Config,
+    config::LogFormat,
+    db,
+    entities::{
+        booking, city, journey,
+        user::{self, UserRole},
+    },
+    routes::create_router,
+    utils::{
+        geocode::NoGeocoder,
+        jwt::{Claims, TokenType, create_token},
+        notify::LogNotifier,
+        reference::generate_reference_code,
+    },
+};
+
+static MIGRATED: OnceCell<()> = OnceCell::const_new();
+
+/// Connect to the test database and run migrations, or return `None` to skip the test
+pub async fn test_state() -> Option<AppState> {
+    let Ok(database_url) = std::env::var("TEST_DATABASE_URL") else {
+        eprintln!("TEST_DATABASE_URL not set; skipping database test");
+        return None;
+    };
+
+    let db = Database::connect(&database_url)
+        .await
+        .expect("Failed to connect to test database");
+
+    MIGRATED
+        .get_or_init(|| async {
+            migration::Migrator::up(&db, None)
+                .await
+                .expect("Failed to run migrations");
+        })
+        .await;
+
+    Some(AppState {
+        db,
+        config: test_config(database_url),
+        notifier: Arc::new(LogNotifier),
+        geocoder: Arc::new(NoGeocoder),
+    })
+}
+
+const TEST_ISSUER: &str = "bus-travel-backend";
+const TEST_AUDIENCE: &str = "bus-travel-api";
+
+fn test_config(database_url: String) -> Config {
+    Config {
+        database_url,
+        db_max_connections: 10,
+        db_min_connections: 0,
+        db_connect_timeout_secs: 30,
+        db_idle_timeout_secs: 600,
+        jwt_secret: "integration-test-secret-at-least-32-bytes".to_string(),
+        argon2_memory_kib: 19456,
+        argon2_iterations: 2,
+        argon2_parallelism: 1,
+        jwt_issuer: TEST_ISSUER.to_string(),
+        jwt_audience: TEST_AUDIENCE.to_string(),
+        jwt_expiration_hours: 24,
+        jwt_expiration_hours_admin: 24,
+        jwt_expiration_hours_driver: 24,
+        jwt_expiration_hours_traveller: 24,
+        refresh_token_expiration_days: 30,
+        server_host: "127.0.0.1".to_string(),
+        server_port: 0,
+        oauth_client_id: "test-client-id".to_string(),
+        force_https: false,
+        https_redirect: false,
+        allowed_origins: None,
+        rate_limit_traveller_per_min: 100,
+        rate_limit_driver_per_min: 500,
+        rate_limit_global_burst: 1000,
+        request_timeout_secs: 30,
+        max_seats_per_booking: 10,
+        max_bookings_per_user_per_journey: 1,
+        max_journey_km: 1500.0,
+        cancellation_cutoff_hours: 0,
+        log_format: LogFormat::Pretty,
+        admin_email: None,
+        admin_password: None,
+        metrics_addr: None,
+    }
+}
+
+/// Look up a seeded city by name
+pub async fn city_by_name(state: &AppState, name: &str) -> city::Model {
+    city::Entity::find()
+        .filter(city::Column::Name.eq(name))
+        .one(&state.db)
+        .await
+        .unwrap()
+        .expect("Seeded city missing")
+}
+
+/// Insert a user with a unique email
+pub async fn create_user(state: &AppState, role: UserRole) -> user::Model {
+    let id = Uuid::new_v4();
+    user::ActiveModel {
+        id: Set(id),
+        email: Set(format!("{}@test.example", id)),
+        password_hash: Set(None),
+        google_id: Set(None),
+        name: Set(format!("Test {:?}", role)),
+        role: Set(role),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .unwrap()
+}
+
+/// Insert a Kupang -> Soe journey
+pub async fn create_journey(
+    state: &AppState,
+    total_seats: i32,
+    departure_time: DateTime<Utc>,
+) -> journey::Model {
+    let origin = city_by_name(state, "Kupang").await;
+    let dest = city_by_name(state, "Soe").await;
+    create_journey_between(state, &origin, &dest, total_seats, departure_time).await
+}
+
+/// Insert a journey between two cities
+pub async fn create_journey_between(
+    state: &AppState,
+    origin: &city::Model,
+    dest: &city::Model,
+    total_seats: i32,
+    departure_time: DateTime<Utc>,
+) -> journey::Model {
+    journey::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        origin_city_id: Set(origin.id),
+        destination_city_id: Set(dest.id),
+        departure_time: Set(departure_time.into()),
+        total_seats: Set(total_seats),
+        driver_id: Set(None),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .unwrap()
+}
+
+/// Make `driver_id` the journey's driver
+pub async fn assign_driver(
+    state: &AppState,
+    journey: journey::Model,
+    driver_id: Uuid,
+) -> journey::Model {
+    let mut active: journey::ActiveModel = journey.into();
+    active.driver_id = Set(Some(driver_id));
+    active.update(&state.db).await.unwrap()
+}
+
+/// Insert a booking directly, bypassing handler validation
+pub async fn insert_booking(
+    state: &AppState,
+    journey: &journey::Model,
+    user: &user::Model,
+    seats: i32,
+) -> booking::Model {
+    let booking = booking::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        journey_id: Set(journey.id),
+        user_id: Set(user.id),
+        seats: Set(seats),
+        pickup_lat: Set(-10.1836),
+        pickup_lng: Set(123.6257),
+        ..Default::default()
+    };
+    db::insert_booking(&state.db, booking, generate_reference_code)
+        .await
+        .unwrap()
+}
+
+/// A departure time safely in the future
+pub fn tomorrow() -> DateTime<Utc> {
+    Utc::now() + Duration::days(1)
+}
+
+/// A far-future day no other test uses, so date-filtered queries only see this test's data
+pub fn unique_future_date() -> NaiveDate {
+    let offset = Uuid::new_v4().as_u128() % 300_000;
+    NaiveDate::from_ymd_opt(2200, 1, 1).unwrap() + Duration::days(offset as i64)
+}
+
+/// Claims as the auth middleware would insert them for `user`
+pub fn claims_for(user: &user::Model) -> Claims {
+    let now = Utc::now();
+    Claims {
+        sub: user.id,
+        email: user.email.clone(),
+        name: user.name.clone(),
+        role: user.role.clone(),
+        exp: (now + Duration::hours(1)).timestamp(),
+        iat: now.timestamp(),
+        nbf: now.timestamp(),
+        jti: Uuid::new_v4(),
+        iss: TEST_ISSUER.to_string(),
+        aud: TEST_AUDIENCE.to_string(),
+        token_type: TokenType::Access,
+    }
+}
+
+/// A valid bearer token for `user`
+pub fn token_for(state: &AppState, user: &user::Model) -> String {
+    create_token(
+        user.id,
+        &user.email,
+        &user.name,
+        user.role.clone(),
+        (&state.config).into(),
+        state.config.jwt_expiration_hours_for(&user.role),
+    )
+    .unwrap()
+}
+
+/// Send a request through the full router and return the status and JSON body
+pub async fn send(
+    state: &AppState,
+    method: Method,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+
+    let (status, _, bytes) = send_raw(state, request).await;
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+
+    (status, json)
+}
+
+/// Send a prepared request through the full router and return the status, headers and raw body
+pub async fn send_raw(
+    state: &AppState,
+    mut request: Request<Body>,
+) -> (StatusCode, HeaderMap, Bytes) {
+    let app: Router = create_router(state.clone());
+
+    // Public routes are rate limited per peer IP
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0))));
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (status, headers, bytes)
+}
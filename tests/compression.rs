@@ -0,0 +1,65 @@
+mod common;
+
+use std::net::SocketAddr;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::ConnectInfo,
+    http::{Request, StatusCode, header},
+    response::Response,
+};
+use tower::ServiceExt;
+
+use bus_travel_backend::{
+    AppState, entities::user::UserRole, middleware::compression::create_compression_layer,
+    routes::create_router,
+};
+
+use common::{create_journey, create_user, test_state, token_for, tomorrow};
+
+/// Send a request through the router wrapped in the same compression layer as `main`
+async fn send_compressed(state: &AppState, uri: &str, token: Option<&str>) -> Response {
+    let app: Router = create_router(state.clone()).layer(create_compression_layer());
+
+    let mut request = Request::get(uri).header(header::ACCEPT_ENCODING, "gzip");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let mut request = request.body(Body::empty()).unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0))));
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_large_list_is_gzipped() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    for _ in 0..10 {
+        create_journey(&state, 10, tomorrow()).await;
+    }
+
+    let token = token_for(&state, &admin);
+    let response = send_compressed(&state, "/api/admin/journeys", Some(&token)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_ENCODING).unwrap(),
+        "gzip"
+    );
+}
+
+#[tokio::test]
+async fn test_small_response_not_compressed() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let response = send_compressed(&state, "/health", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+}
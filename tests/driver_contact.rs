@@ -0,0 +1,135 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{NaiveTime, Utc};
+use sea_orm::{ActiveModelTrait, Set};
+
+use bus_travel_backend::{
+    AppState,
+    entities::{
+        booking::{self, BookingStatus},
+        journey,
+        user::{self, UserRole},
+    },
+};
+
+use common::{
+    assign_driver, create_journey, create_user, insert_booking, send, test_state, token_for,
+    tomorrow, unique_future_date,
+};
+
+/// A journey driven by a driver with a phone number on file
+async fn driven_journey(state: &AppState) -> (journey::Model, user::Model) {
+    let mut driver: user::ActiveModel = create_user(state, UserRole::Driver).await.into();
+    driver.phone = Set(Some("+62 812-3456-7890".to_string()));
+    let driver = driver.update(&state.db).await.unwrap();
+
+    let journey = assign_driver(
+        state,
+        create_journey(state, 10, tomorrow()).await,
+        driver.id,
+    )
+    .await;
+    (journey, driver)
+}
+
+#[tokio::test]
+async fn test_driver_name_is_public() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let (journey, driver) = driven_journey(&state).await;
+
+    let uri = format!("/api/journeys/{}", journey.id);
+    let (status, body) = send(&state, Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["driver_name"], driver.name);
+    assert!(body.get("phone").is_none());
+    assert!(!body.to_string().contains(&driver.email));
+
+    let unassigned = create_journey(&state, 10, tomorrow()).await;
+    let uri = format!("/api/journeys/{}", unassigned.id);
+    let (_, body) = send(&state, Method::GET, &uri, None, None).await;
+    assert!(body["driver_name"].is_null());
+}
+
+#[tokio::test]
+async fn test_deleted_driver_name_is_hidden() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    let departure = date
+        .and_time(NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+        .and_utc();
+    let driver = create_user(&state, UserRole::Driver).await;
+    let journey = assign_driver(
+        &state,
+        create_journey(&state, 10, departure).await,
+        driver.id,
+    )
+    .await;
+
+    // Deleted while still assigned, as happens to drivers of past journeys
+    let mut deleted: user::ActiveModel = driver.into();
+    deleted.deleted_at = Set(Some(Utc::now().into()));
+    deleted.update(&state.db).await.unwrap();
+
+    let uri = format!("/api/journeys/{}", journey.id);
+    let (status, body) = send(&state, Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["driver_name"].is_null());
+
+    let uri = format!("/api/journeys?departure_date={}", date);
+    let (status, body) = send(&state, Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|j| j["id"] == journey.id.to_string())
+        .unwrap();
+    assert!(listed["driver_name"].is_null());
+}
+
+#[tokio::test]
+async fn test_confirmed_passenger_sees_driver_contact() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let (journey, driver) = driven_journey(&state).await;
+    let passenger = create_user(&state, UserRole::Traveller).await;
+    let booking = insert_booking(&state, &journey, &passenger, 1).await;
+
+    let uri = format!("/api/bookings/{}/driver-contact", booking.id);
+    let token = token_for(&state, &passenger);
+    let (status, body) = send(&state, Method::GET, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], driver.name);
+    assert_eq!(body["phone"], "+62 812-3456-7890");
+}
+
+#[tokio::test]
+async fn test_driver_contact_hidden_from_others() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let (journey, _) = driven_journey(&state).await;
+    let passenger = create_user(&state, UserRole::Traveller).await;
+    let booking = insert_booking(&state, &journey, &passenger, 1).await;
+    let uri = format!("/api/bookings/{}/driver-contact", booking.id);
+
+    // Another traveller can't use someone else's booking
+    let stranger = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &stranger);
+    let (status, _) = send(&state, Method::GET, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Nor can the passenger while only on the waitlist
+    let mut waitlisted: booking::ActiveModel = booking.into();
+    waitlisted.status = Set(BookingStatus::Waitlisted);
+    waitlisted.update(&state.db).await.unwrap();
+    let token = token_for(&state, &passenger);
+    let (status, _) = send(&state, Method::GET, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, Set};
+use serde_json::Value;
+
+use bus_travel_backend::{
+    AppState,
+    entities::{
+        booking::{self, BookingStatus},
+        journey::{self, JourneyStatus},
+        user::{self, UserRole},
+    },
+};
+
+use common::{
+    assign_driver, create_journey, create_user, insert_booking, send, test_state, token_for,
+};
+
+async fn assigned_journey(state: &AppState, driver: &user::Model, minutes: i64) -> journey::Model {
+    let journey = create_journey(state, 10, Utc::now() + Duration::minutes(minutes)).await;
+    assign_driver(state, journey, driver.id).await
+}
+
+async fn dashboard(state: &AppState, driver: &user::Model) -> Value {
+    let token = token_for(state, driver);
+    let (status, body) = send(
+        state,
+        Method::GET,
+        "/api/driver/dashboard",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    body
+}
+
+#[tokio::test]
+async fn test_dashboard_summarises_assigned_journeys() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let driver = create_user(&state, UserRole::Driver).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    // Departing in a few minutes: today (barring a run right at local midnight) and next
+    let next = assigned_journey(&state, &driver, 5).await;
+    assigned_journey(&state, &driver, 3 * 24 * 60).await;
+    assigned_journey(&state, &driver, 10 * 24 * 60).await;
+    // Neither counts as upcoming
+    assigned_journey(&state, &driver, -3 * 24 * 60).await;
+    let cancelled = assigned_journey(&state, &driver, 2 * 24 * 60).await;
+    let mut active: journey::ActiveModel = cancelled.into();
+    active.status = Set(JourneyStatus::Cancelled);
+    active.update(&state.db).await.unwrap();
+
+    insert_booking(&state, &next, &traveller, 2).await;
+    insert_booking(&state, &next, &traveller, 1).await;
+    // Waitlisted passengers aren't coming
+    let waitlisted = insert_booking(&state, &next, &traveller, 4).await;
+    let mut active: booking::ActiveModel = waitlisted.into();
+    active.status = Set(BookingStatus::Waitlisted);
+    active.update(&state.db).await.unwrap();
+
+    let summary = dashboard(&state, &driver).await;
+    assert_eq!(summary["upcoming_journeys"], 3);
+    assert_eq!(summary["journeys_today"], 1);
+    assert_eq!(summary["next_journey"]["id"], next.id.to_string());
+    assert_eq!(summary["next_journey"]["passenger_count"], 2);
+    assert_eq!(summary["next_journey"]["booked_seats"], 3);
+    assert_eq!(summary["next_journey"]["total_seats"], 10);
+}
+
+#[tokio::test]
+async fn test_dashboard_next_journey_beyond_today() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let driver = create_user(&state, UserRole::Driver).await;
+    let later = assigned_journey(&state, &driver, 5 * 24 * 60).await;
+
+    let summary = dashboard(&state, &driver).await;
+    assert_eq!(summary["upcoming_journeys"], 1);
+    assert_eq!(summary["journeys_today"], 0);
+    assert_eq!(summary["next_journey"]["id"], later.id.to_string());
+    assert_eq!(summary["next_journey"]["passenger_count"], 0);
+    assert_eq!(summary["next_journey"]["booked_seats"], 0);
+}
+
+#[tokio::test]
+async fn test_dashboard_without_journeys() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let driver = create_user(&state, UserRole::Driver).await;
+
+    let summary = dashboard(&state, &driver).await;
+    assert_eq!(summary["upcoming_journeys"], 0);
+    assert_eq!(summary["journeys_today"], 0);
+    assert!(summary["next_journey"].is_null());
+}
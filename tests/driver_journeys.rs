@@ -0,0 +1,47 @@
+mod common;
+
+use axum::{Extension, extract::State};
+use chrono::{Duration, Utc};
+
+use bus_travel_backend::{
+    AppState,
+    entities::{journey, user::UserRole},
+    handlers::driver::my_journeys,
+};
+
+use common::{assign_driver, claims_for, create_journey, create_user, insert_booking, test_state};
+
+async fn assigned_journey(state: &AppState, driver_id: uuid::Uuid, hours: i64) -> journey::Model {
+    let journey = create_journey(state, 10, Utc::now() + Duration::hours(hours)).await;
+    assign_driver(state, journey, driver_id).await
+}
+
+#[tokio::test]
+async fn test_journeys_split_into_upcoming_and_past() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let driver = create_user(&state, UserRole::Driver).await;
+
+    // Created out of order on purpose
+    let later = assigned_journey(&state, driver.id, 48).await;
+    let long_ago = assigned_journey(&state, driver.id, -48).await;
+    let soon = assigned_journey(&state, driver.id, 2).await;
+    let recently = assigned_journey(&state, driver.id, -2).await;
+
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    insert_booking(&state, &soon, &traveller, 3).await;
+
+    let journeys = my_journeys(State(state.clone()), Extension(claims_for(&driver)))
+        .await
+        .unwrap()
+        .0;
+
+    let upcoming: Vec<_> = journeys.upcoming.iter().map(|j| j.id).collect();
+    let past: Vec<_> = journeys.past.iter().map(|j| j.id).collect();
+    assert_eq!(upcoming, vec![soon.id, later.id]);
+    assert_eq!(past, vec![recently.id, long_ago.id]);
+
+    assert_eq!(journeys.upcoming[0].booked_seats, 3);
+    assert_eq!(journeys.upcoming[1].booked_seats, 0);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+use bus_travel_backend::{
+    entities::{booking, journey, user::UserRole},
+    handlers::{
+        admin::{UpdateJourneyRequest, update_journey},
+        traveller::{CreateBookingRequest, create_booking},
+    },
+};
+
+use common::{claims_for, create_journey, create_user, test_state, tomorrow};
+
+#[tokio::test]
+async fn test_booking_fare_is_fixed_at_booking_time() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let mut active: journey::ActiveModel = journey.into();
+    active.base_fare = Set(15_000);
+    let journey = active.update(&state.db).await.unwrap();
+
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let (_, Json(booked)) = create_booking(
+        State(state.clone()),
+        Extension(claims_for(&traveller)),
+        HeaderMap::new(),
+        Json(CreateBookingRequest {
+            journey_id: journey.id,
+            seats: 3,
+            pickup_lat: -10.1836,
+            pickup_lng: 123.6257,
+            waitlist: false,
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(booked.total_fare, 45_000);
+
+    // A later price change only affects new bookings
+    let Json(updated) = update_journey(
+        State(state.clone()),
+        Path(journey.id),
+        Json(UpdateJourneyRequest {
+            origin_city_id: None,
+            destination_city_id: None,
+            departure_time: None,
+            total_seats: None,
+            base_fare: Some(20_000),
+            booking_close_minutes: None,
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(updated.base_fare, 20_000);
+
+    let stored = booking::Entity::find_by_id(booked.id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.total_fare, 45_000);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+
+use sea_orm::EntityTrait;
+
+use bus_travel_backend::{
+    AppState,
+    entities::user::{self, UserRole},
+    handlers::auth::sign_in_with_google,
+    utils::google::GoogleIdentity,
+};
+
+use common::{create_user, send, test_state};
+
+// Stands in for an identity that already passed `verify_google_id_token`
+fn identity(email: &str) -> GoogleIdentity {
+    GoogleIdentity {
+        sub: format!("google-{}", Uuid::new_v4()),
+        email: email.to_string(),
+        email_verified: Some(true),
+        name: Some("Google Traveller".to_string()),
+    }
+}
+
+async fn find_user(state: &AppState, id: Uuid) -> user::Model {
+    user::Entity::find_by_id(id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_google_sign_in_creates_then_reuses_user() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let google = identity(&format!("g-{}@Gmail.com", Uuid::new_v4()));
+
+    let first = sign_in_with_google(&state, google.clone()).await.unwrap();
+    assert_eq!(first.user.email, google.email.to_lowercase());
+    assert_eq!(first.user.role, UserRole::Traveller);
+
+    let stored = find_user(&state, first.user.id).await;
+    assert_eq!(stored.google_id.as_deref(), Some(google.sub.as_str()));
+    assert!(stored.password_hash.is_none());
+
+    let second = sign_in_with_google(&state, google).await.unwrap();
+    assert_eq!(second.user.id, first.user.id);
+}
+
+#[tokio::test]
+async fn test_google_sign_in_links_existing_email_account() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let existing = create_user(&state, UserRole::Traveller).await;
+    let google = identity(&existing.email);
+
+    let response = sign_in_with_google(&state, google.clone()).await.unwrap();
+    assert_eq!(response.user.id, existing.id);
+
+    let stored = find_user(&state, existing.id).await;
+    assert_eq!(stored.google_id.as_deref(), Some(google.sub.as_str()));
+}
+
+#[tokio::test]
+async fn test_password_login_rejects_google_only_account() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let email = format!("g-{}@gmail.com", Uuid::new_v4());
+    sign_in_with_google(&state, identity(&email)).await.unwrap();
+
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": email, "password": "whatever123" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(
+        body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Google")
+    );
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::{send, test_state};
+
+#[tokio::test]
+async fn test_health_without_auth() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let (status, body) = send(&state, Method::GET, "/health", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+
+    let (status, body) = send(&state, Method::GET, "/health/ready", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+}
+
+#[tokio::test]
+async fn test_readiness_reports_closed_database() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    // Clones share the pool, so closing one makes every query fail
+    state.db.clone().close().await.unwrap();
+
+    let (status, body) = send(&state, Method::GET, "/health/ready", None, None).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "db_unavailable");
+
+    // Liveness doesn't depend on the database
+    let (status, _) = send(&state, Method::GET, "/health", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+}
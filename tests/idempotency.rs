@@ -0,0 +1,113 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::{Value, json};
+
+use bus_travel_backend::{
+    AppState,
+    entities::{
+        booking, journey,
+        user::{self, UserRole},
+    },
+};
+
+use common::{create_journey, create_user, send_raw, test_state, token_for, tomorrow};
+
+/// POST /api/bookings for `journey`, optionally with an Idempotency-Key
+async fn book(
+    state: &AppState,
+    traveller: &user::Model,
+    journey: &journey::Model,
+    key: Option<&str>,
+) -> (StatusCode, Value) {
+    let mut request = Request::post("/api/bookings")
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token_for(state, traveller)),
+        )
+        .header(header::CONTENT_TYPE, "application/json");
+    if let Some(key) = key {
+        request = request.header("idempotency-key", key);
+    }
+    let body = json!({
+        "journey_id": journey.id,
+        "seats": 1,
+        "pickup_lat": -10.1836,
+        "pickup_lng": 123.6257
+    });
+
+    let (status, _, bytes) =
+        send_raw(state, request.body(Body::from(body.to_string())).unwrap()).await;
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn bookings_of(state: &AppState, traveller: &user::Model) -> u64 {
+    booking::Entity::find()
+        .filter(booking::Column::UserId.eq(traveller.id))
+        .count(&state.db)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_retry_with_same_key_returns_original_booking() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 5, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    let (status, first) = book(&state, &traveller, &journey, Some("retry-1")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Without the key this would be a duplicate-booking conflict
+    let (status, retry) = book(&state, &traveller, &journey, Some("retry-1")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(retry["id"], first["id"]);
+    assert_eq!(bookings_of(&state, &traveller).await, 1);
+
+    let (status, _) = book(&state, &traveller, &journey, None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_different_keys_create_distinct_bookings() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let morning = create_journey(&state, 5, tomorrow()).await;
+    let evening = create_journey(&state, 5, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    let (_, first) = book(&state, &traveller, &morning, Some("key-a")).await;
+    let (status, second) = book(&state, &traveller, &evening, Some("key-b")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(first["id"], second["id"]);
+    assert_eq!(bookings_of(&state, &traveller).await, 2);
+
+    // Keys are per user: someone else's identical key books for them
+    let other = create_user(&state, UserRole::Traveller).await;
+    let (status, theirs) = book(&state, &other, &morning, Some("key-a")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(theirs["id"], first["id"]);
+}
+
+#[tokio::test]
+async fn test_invalid_idempotency_key_rejected() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 5, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    let too_long = "k".repeat(256);
+    for key in ["", "has space", too_long.as_str()] {
+        let (status, _) = book(&state, &traveller, &journey, Some(key)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "key {:?}", key);
+    }
+    assert_eq!(bookings_of(&state, &traveller).await, 0);
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::entities::user::UserRole;
+
+use chrono::NaiveTime;
+
+use common::{
+    create_journey, create_user, insert_booking, send, test_state, tomorrow, unique_future_date,
+};
+
+#[tokio::test]
+async fn test_availability_of_partially_booked_journey() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    insert_booking(&state, &journey, &traveller, 3).await;
+    insert_booking(&state, &journey, &traveller, 2).await;
+
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        &format!("/api/journeys/{}/availability", journey.id),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        json!({
+            "journey_id": journey.id,
+            "total_seats": 10,
+            "booked_seats": 5,
+            "available_seats": 5,
+            "is_sold_out": false
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_availability_of_sold_out_journey() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 4, tomorrow()).await;
+    insert_booking(&state, &journey, &traveller, 4).await;
+
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        &format!("/api/journeys/{}/availability", journey.id),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["booked_seats"], 4);
+    assert_eq!(body["available_seats"], 0);
+    assert_eq!(body["is_sold_out"], true);
+}
+
+#[tokio::test]
+async fn test_overbooked_journey_reports_zero_everywhere() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let date = unique_future_date();
+    let departure = date
+        .and_time(NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+        .and_utc();
+    let journey = create_journey(&state, 4, departure).await;
+    // More confirmed seats than the bus has, as after an admin shrinks the journey
+    insert_booking(&state, &journey, &traveller, 4).await;
+    insert_booking(&state, &journey, &traveller, 2).await;
+
+    let uri = format!("/api/journeys/{}/availability", journey.id);
+    let (_, availability) = send(&state, Method::GET, &uri, None, None).await;
+    let uri = format!("/api/journeys/{}", journey.id);
+    let (_, detail) = send(&state, Method::GET, &uri, None, None).await;
+    let uri = format!(
+        "/api/journeys?departure_date={}&include_sold_out=true",
+        date
+    );
+    let (_, list) = send(&state, Method::GET, &uri, None, None).await;
+    let listed = list["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|j| j["id"] == journey.id.to_string())
+        .unwrap();
+
+    for body in [&availability, &detail, listed] {
+        assert_eq!(body["available_seats"], 0);
+        assert_eq!(body["is_sold_out"], true);
+    }
+}
+
+#[tokio::test]
+async fn test_availability_of_unknown_journey() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let (status, _) = send(
+        &state,
+        Method::GET,
+        &format!("/api/journeys/{}/availability", Uuid::new_v4()),
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
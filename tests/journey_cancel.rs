@@ -0,0 +1,85 @@
+mod common;
+
+use std::collections::HashSet;
+
+use axum::http::{Method, StatusCode};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+use bus_travel_backend::entities::{booking, user::UserRole};
+
+use common::{create_journey, create_user, insert_booking, send, test_state, token_for, tomorrow};
+
+#[tokio::test]
+async fn test_cancel_journey_reports_and_keeps_bookings() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let alice = create_user(&state, UserRole::Traveller).await;
+    let bob = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let alice_booking = insert_booking(&state, &journey, &alice, 2).await;
+    let bob_booking = insert_booking(&state, &journey, &bob, 1).await;
+
+    let uri = format!("/api/admin/journeys/{}/cancel", journey.id);
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        &uri,
+        Some(&token_for(&state, &admin)),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["journey"]["status"], "cancelled");
+
+    let reported: HashSet<(String, String)> = body["affected_bookings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|b| {
+            (
+                b["booking_id"].as_str().unwrap().to_string(),
+                b["passenger_email"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    let expected: HashSet<(String, String)> = [
+        (alice_booking.id.to_string(), alice.email.clone()),
+        (bob_booking.id.to_string(), bob.email.clone()),
+    ]
+    .into();
+    assert_eq!(reported, expected);
+
+    // Bookings are preserved
+    let remaining = booking::Entity::find()
+        .filter(booking::Column::JourneyId.eq(journey.id))
+        .all(&state.db)
+        .await
+        .unwrap();
+    assert_eq!(remaining.len(), 2);
+
+    // The traveller sees the cancellation instead of a vanished booking
+    let (status, mine) = send(
+        &state,
+        Method::GET,
+        "/api/bookings",
+        Some(&token_for(&state, &alice)),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(mine["items"][0]["id"], alice_booking.id.to_string());
+    assert_eq!(mine["items"][0]["journey_status"], "cancelled");
+
+    // Cancelling twice is a conflict
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        &uri,
+        Some(&token_for(&state, &admin)),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
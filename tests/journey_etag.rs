@@ -0,0 +1,54 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, Request, StatusCode, header},
+};
+use chrono::NaiveTime;
+
+use bus_travel_backend::{AppState, entities::user::UserRole};
+
+use common::{
+    create_journey, create_user, insert_booking, send_raw, test_state, unique_future_date,
+};
+
+async fn get_journeys(state: &AppState, uri: &str, etag: Option<&str>) -> (StatusCode, HeaderMap) {
+    let mut request = Request::get(uri);
+    if let Some(etag) = etag {
+        request = request.header(header::IF_NONE_MATCH, etag);
+    }
+    let (status, headers, _) = send_raw(state, request.body(Body::empty()).unwrap()).await;
+    (status, headers)
+}
+
+#[tokio::test]
+async fn test_unchanged_listing_is_not_modified() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    // A day nobody else books on keeps the listing stable between requests
+    let date = unique_future_date();
+    let departure = date
+        .and_time(NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+        .and_utc();
+    let journey = create_journey(&state, 10, departure).await;
+    let uri = format!("/api/journeys?departure_date={}", date);
+
+    let (status, headers) = get_journeys(&state, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let etag = headers[header::ETAG].to_str().unwrap().to_string();
+    assert!(etag.starts_with("W/\""), "{}", etag);
+
+    let (status, headers) = get_journeys(&state, &uri, Some(&etag)).await;
+    assert_eq!(status, StatusCode::NOT_MODIFIED);
+    assert_eq!(headers[header::ETAG], etag.as_str());
+
+    // A booking changes the available seats, and with them the ETag
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    insert_booking(&state, &journey, &traveller, 2).await;
+
+    let (status, headers) = get_journeys(&state, &uri, Some(&etag)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(headers[header::ETAG], etag.as_str());
+}
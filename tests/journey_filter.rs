@@ -0,0 +1,344 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, NaiveDate, NaiveTime};
+use sea_orm::{ActiveModelTrait, ModelTrait, Set};
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppState,
+    entities::{city, journey, user::UserRole},
+    handlers::traveller::{JourneyFilter, available_journeys},
+    utils::pagination::Pagination,
+};
+
+use common::{
+    city_by_name, create_journey_between, create_user, insert_booking, send, test_state,
+    unique_future_date,
+};
+
+async fn search(state: &AppState, filter: JourneyFilter) -> usize {
+    available_journeys(state, &Pagination::default(), &filter)
+        .await
+        .unwrap()
+        .total as usize
+}
+
+/// Seed two Kupang -> Soe journeys and one Soe -> Kupang journey on `date`
+async fn seed(state: &AppState, date: NaiveDate) -> (i32, i32) {
+    let kupang = city_by_name(state, "Kupang").await;
+    let soe = city_by_name(state, "Soe").await;
+    let at = |h| {
+        date.and_time(NaiveTime::from_hms_opt(h, 0, 0).unwrap())
+            .and_utc()
+    };
+
+    create_journey_between(state, &kupang, &soe, 10, at(8)).await;
+    create_journey_between(state, &kupang, &soe, 10, at(15)).await;
+    create_journey_between(state, &soe, &kupang, 10, at(9)).await;
+
+    (kupang.id, soe.id)
+}
+
+#[tokio::test]
+async fn test_filter_by_date() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    seed(&state, date).await;
+
+    let filter = JourneyFilter {
+        departure_date: Some(date),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, filter).await, 3);
+}
+
+#[tokio::test]
+async fn test_filter_by_date_and_origin() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    let (kupang, soe) = seed(&state, date).await;
+
+    let from_kupang = JourneyFilter {
+        origin_city_id: Some(kupang),
+        departure_date: Some(date),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, from_kupang).await, 2);
+
+    let from_soe = JourneyFilter {
+        origin_city_id: Some(soe),
+        departure_date: Some(date),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, from_soe).await, 1);
+}
+
+#[tokio::test]
+async fn test_filter_by_date_and_destination() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    let (kupang, _) = seed(&state, date).await;
+
+    let filter = JourneyFilter {
+        destination_city_id: Some(kupang),
+        departure_date: Some(date),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, filter).await, 1);
+}
+
+#[tokio::test]
+async fn test_filter_by_all_fields() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    let (kupang, soe) = seed(&state, date).await;
+
+    let filter = JourneyFilter {
+        origin_city_id: Some(kupang),
+        destination_city_id: Some(soe),
+        departure_date: Some(date),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, filter).await, 2);
+}
+
+#[tokio::test]
+async fn test_filter_with_no_matches() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    let (kupang, _) = seed(&state, date).await;
+
+    // Seeded journeys never go Kupang -> Kupang
+    let same_city = JourneyFilter {
+        origin_city_id: Some(kupang),
+        destination_city_id: Some(kupang),
+        departure_date: Some(date),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, same_city).await, 0);
+
+    // Nothing departs the following day
+    let next_day = JourneyFilter {
+        departure_date: date.succ_opt(),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, next_day).await, 0);
+}
+
+#[tokio::test]
+async fn test_no_filters_lists_future_journeys() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    seed(&state, unique_future_date()).await;
+
+    assert!(search(&state, JourneyFilter::default()).await >= 3);
+}
+
+#[tokio::test]
+async fn test_sold_out_journeys_hidden_unless_requested() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    let kupang = city_by_name(&state, "Kupang").await;
+    let soe = city_by_name(&state, "Soe").await;
+    let departure = date
+        .and_time(NaiveTime::from_hms_opt(7, 0, 0).unwrap())
+        .and_utc();
+    let full = create_journey_between(&state, &kupang, &soe, 2, departure).await;
+    let open = create_journey_between(&state, &kupang, &soe, 2, departure).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    insert_booking(&state, &full, &traveller, 2).await;
+    insert_booking(&state, &open, &traveller, 1).await;
+
+    let list = |include_sold_out| {
+        let filter = JourneyFilter {
+            departure_date: Some(date),
+            include_sold_out,
+            ..Default::default()
+        };
+        let state = state.clone();
+        async move {
+            available_journeys(&state, &Pagination::default(), &filter)
+                .await
+                .unwrap()
+        }
+    };
+
+    let default = list(false).await;
+    assert_eq!(default.total, 1);
+    assert_eq!(default.items[0].id, open.id);
+    assert!(!default.items[0].is_sold_out);
+    assert_eq!(default.items[0].total_seats, 2);
+    assert_eq!(default.items[0].available_seats, 1);
+
+    let everything = list(true).await;
+    assert_eq!(everything.total, 2);
+    let sold_out = everything.items.iter().find(|j| j.id == full.id).unwrap();
+    assert!(sold_out.is_sold_out);
+    assert_eq!(sold_out.available_seats, 0);
+}
+
+#[tokio::test]
+async fn test_filter_by_departure_window() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    let (kupang, _) = seed(&state, date).await;
+    let at = |h, m| {
+        date.and_time(NaiveTime::from_hms_opt(h, m, 0).unwrap())
+            .and_utc()
+    };
+
+    // 09:00 and 15:00 fall inside, 08:00 doesn't; both bounds are inclusive
+    let window = JourneyFilter {
+        departure_from: Some(at(8, 30)),
+        departure_to: Some(at(15, 0)),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, window).await, 2);
+
+    let from_kupang = JourneyFilter {
+        origin_city_id: Some(kupang),
+        departure_from: Some(at(8, 30)),
+        departure_to: Some(at(15, 0)),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, from_kupang).await, 1);
+
+    // Each bound also works alone; the date keeps other tests' journeys out
+    let from_only = JourneyFilter {
+        departure_date: Some(date),
+        departure_from: Some(at(8, 30)),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, from_only).await, 2);
+
+    let to_only = JourneyFilter {
+        departure_date: Some(date),
+        departure_to: Some(at(9, 0)),
+        ..Default::default()
+    };
+    assert_eq!(search(&state, to_only).await, 2);
+}
+
+#[tokio::test]
+async fn test_inverted_departure_window_rejected() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    let from = date
+        .and_time(NaiveTime::from_hms_opt(12, 0, 0).unwrap())
+        .and_utc();
+    let to = from - Duration::hours(1);
+
+    let uri = format!(
+        "/api/journeys?departure_from={}&departure_to={}",
+        from.format("%Y-%m-%dT%H:%M:%SZ"),
+        to.format("%Y-%m-%dT%H:%M:%SZ"),
+    );
+    let (status, _) = send(&state, Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // The same bounds the right way round are accepted
+    let uri = format!(
+        "/api/journeys?departure_from={}&departure_to={}",
+        to.format("%Y-%m-%dT%H:%M:%SZ"),
+        from.format("%Y-%m-%dT%H:%M:%SZ"),
+    );
+    let (status, _) = send(&state, Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_search_by_city_name() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let date = unique_future_date();
+    let at = |h| {
+        date.and_time(NaiveTime::from_hms_opt(h, 0, 0).unwrap())
+            .and_utc()
+    };
+    let kupang = city_by_name(&state, "Kupang").await;
+    let soe = city_by_name(&state, "Soe").await;
+    let tag = Uuid::new_v4().simple().to_string();
+    let bandung = city::ActiveModel {
+        name: Set(format!("Bandung-{}", tag)),
+        center_lat: Set(-6.9175),
+        center_lng: Set(107.6191),
+        pickup_radius_km: Set(7.0),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .unwrap();
+
+    let outbound = create_journey_between(&state, &bandung, &soe, 10, at(8)).await;
+    let inbound = create_journey_between(&state, &kupang, &bandung, 10, at(9)).await;
+    let unrelated = create_journey_between(&state, &kupang, &soe, 10, at(10)).await;
+
+    let search = |q: String| {
+        let uri = format!("/api/journeys?departure_date={}&q={}", date, q);
+        let state = &state;
+        async move {
+            let (status, body) = send(state, Method::GET, &uri, None, None).await;
+            assert_eq!(status, StatusCode::OK);
+            let mut ids: Vec<String> = body["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|j| j["id"].as_str().unwrap().to_string())
+                .collect();
+            ids.sort();
+            ids
+        }
+    };
+    let ids = |journeys: &[&journey::Model]| {
+        let mut ids: Vec<String> = journeys.iter().map(|j| j.id.to_string()).collect();
+        ids.sort();
+        ids
+    };
+
+    // Origin or destination, in any case
+    assert_eq!(
+        search(tag.to_uppercase()).await,
+        ids(&[&outbound, &inbound])
+    );
+    assert_eq!(
+        search("bandung".to_string()).await,
+        ids(&[&outbound, &inbound])
+    );
+    // Only the origin matches
+    assert_eq!(
+        search("KUPANG".to_string()).await,
+        ids(&[&inbound, &unrelated])
+    );
+    // Only the destination matches
+    assert_eq!(
+        search("sOe".to_string()).await,
+        ids(&[&outbound, &unrelated])
+    );
+    // Neither end matches; wildcards are taken literally
+    assert!(search("Atlantis".to_string()).await.is_empty());
+    assert!(search("%25".to_string()).await.is_empty());
+
+    for journey in [outbound, inbound, unrelated] {
+        journey.delete(&state.db).await.unwrap();
+    }
+    bandung.delete(&state.db).await.unwrap();
+}
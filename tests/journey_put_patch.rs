@@ -0,0 +1,94 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde_json::json;
+
+use bus_travel_backend::entities::user::UserRole;
+
+use common::{city_by_name, create_journey, create_user, send, test_state, token_for, tomorrow};
+
+#[tokio::test]
+async fn test_patch_leaves_other_fields() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let uri = format!("/api/admin/journeys/{}", journey.id);
+
+    let (status, patched) = send(
+        &state,
+        Method::PATCH,
+        &uri,
+        Some(&token),
+        Some(json!({ "base_fare": 42_000 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", patched);
+    assert_eq!(patched["base_fare"], 42_000);
+    assert_eq!(patched["total_seats"], 10);
+    assert_eq!(patched["origin_city_id"], journey.origin_city_id);
+    assert_eq!(patched["destination_city_id"], journey.destination_city_id);
+    let departure: DateTime<Utc> = patched["departure_time"].as_str().unwrap().parse().unwrap();
+    assert_eq!(departure, journey.departure_time);
+}
+
+#[tokio::test]
+async fn test_put_replaces_all_fields() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let uri = format!("/api/admin/journeys/{}", journey.id);
+    let kupang = city_by_name(&state, "Kupang").await;
+    let soe = city_by_name(&state, "Soe").await;
+    // Whole seconds, as the database stores microseconds
+    let departure = (tomorrow() + Duration::hours(2))
+        .with_nanosecond(0)
+        .unwrap();
+
+    // Soe -> Kupang, the reverse of the original route
+    let (status, replaced) = send(
+        &state,
+        Method::PUT,
+        &uri,
+        Some(&token),
+        Some(json!({
+            "origin_city_id": soe.id,
+            "destination_city_id": kupang.id,
+            "departure_time": departure,
+            "total_seats": 20,
+            "base_fare": 30_000,
+            "booking_close_minutes": 15
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", replaced);
+    assert_eq!(replaced["id"], journey.id.to_string());
+    assert_eq!(replaced["origin_city_id"], soe.id);
+    assert_eq!(replaced["destination_city_id"], kupang.id);
+    assert_eq!(replaced["total_seats"], 20);
+    assert_eq!(replaced["base_fare"], 30_000);
+    assert_eq!(replaced["booking_close_minutes"], 15);
+    let stored: DateTime<Utc> = replaced["departure_time"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(stored, departure);
+
+    // A partial body is not a full replacement
+    let (status, _) = send(
+        &state,
+        Method::PUT,
+        &uri,
+        Some(&token),
+        Some(json!({ "base_fare": 42_000 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::http::{Method, StatusCode};
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    entities::user::UserRole, handlers::traveller::BookingResponse, utils::notify::Notifier,
+};
+
+use common::{create_journey, create_user, insert_booking, send, test_state, token_for, tomorrow};
+
+/// Records reschedule notices instead of sending them
+#[derive(Default)]
+struct RecordingNotifier {
+    rescheduled: Mutex<Vec<(String, Uuid, DateTime<Utc>)>>,
+}
+
+#[async_trait]
+impl Notifier for RecordingNotifier {
+    async fn booking_confirmed(&self, _to: &str, _booking: &BookingResponse) {}
+
+    async fn journey_rescheduled(
+        &self,
+        to: &str,
+        booking_id: Uuid,
+        _old_departure: DateTime<Utc>,
+        new_departure: DateTime<Utc>,
+    ) {
+        self.rescheduled
+            .lock()
+            .unwrap()
+            .push((to.to_string(), booking_id, new_departure));
+    }
+}
+
+#[tokio::test]
+async fn test_reschedule_notifies_passengers() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    let notifier = Arc::new(RecordingNotifier::default());
+    state.notifier = notifier.clone();
+
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let booking = insert_booking(&state, &journey, &traveller, 1).await;
+    let uri = format!("/api/admin/journeys/{}/reschedule", journey.id);
+
+    // A departure in the past is rejected before anyone is told
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        &uri,
+        Some(&token),
+        Some(json!({ "departure_time": Utc::now() - Duration::hours(1) })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(notifier.rescheduled.lock().unwrap().is_empty());
+
+    let new_time = journey.departure_time.with_timezone(&Utc) + Duration::hours(2);
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        &uri,
+        Some(&token),
+        Some(json!({ "departure_time": new_time })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["notified_passengers"], 1);
+    assert_eq!(
+        *notifier.rescheduled.lock().unwrap(),
+        vec![(traveller.email, booking.id, new_time)]
+    );
+}
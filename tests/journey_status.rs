@@ -0,0 +1,47 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use bus_travel_backend::entities::user::UserRole;
+
+use common::{assign_driver, create_journey, create_user, send, test_state, token_for, tomorrow};
+
+#[tokio::test]
+async fn test_assigned_driver_completes_journey_once() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let driver = create_user(&state, UserRole::Driver).await;
+    let token = token_for(&state, &driver);
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let journey = assign_driver(&state, journey, driver.id).await;
+    let uri = format!("/api/driver/journeys/{}/complete", journey.id);
+
+    let (status, body) = send(&state, Method::POST, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "completed");
+
+    let (status, _) = send(&state, Method::POST, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_unassigned_driver_cannot_complete_journey() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let driver = create_user(&state, UserRole::Driver).await;
+    let other_driver = create_user(&state, UserRole::Driver).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let journey = assign_driver(&state, journey, driver.id).await;
+
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        &format!("/api/driver/journeys/{}/complete", journey.id),
+        Some(&token_for(&state, &other_driver)),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
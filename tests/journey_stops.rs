@@ -0,0 +1,159 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::Duration;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use bus_travel_backend::{AppState, entities::user::UserRole};
+
+use common::{city_by_name, create_user, send, test_state, token_for, tomorrow};
+
+/// Create a throwaway stop city, returning its id. It sits far from the seeded cities so
+/// nearest-city lookups elsewhere are unaffected.
+async fn create_stop_city(state: &AppState, token: &str, name: &str) -> i64 {
+    let (status, city) = send(
+        state,
+        Method::POST,
+        "/api/admin/cities",
+        Some(token),
+        Some(json!({
+            "name": format!("{}-{}", name, Uuid::new_v4()),
+            "center_lat": -8.5,
+            "center_lng": 121.5,
+            "pickup_radius_km": 3.0
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", city);
+    city["id"].as_i64().unwrap()
+}
+
+/// Delete a journey (and with it its stops), then the stop cities it used
+async fn clean_up(state: &AppState, token: &str, journey_id: Option<&str>, cities: &[i64]) {
+    if let Some(id) = journey_id {
+        let uri = format!("/api/admin/journeys/{}", id);
+        let (status, _) = send(state, Method::DELETE, &uri, Some(token), None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    for id in cities {
+        let uri = format!("/api/admin/cities/{}", id);
+        let (status, _) = send(state, Method::DELETE, &uri, Some(token), None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
+
+async fn create_journey_with_stops(
+    state: &AppState,
+    token: &str,
+    stops: Value,
+) -> (StatusCode, Value) {
+    let kupang = city_by_name(state, "Kupang").await;
+    let soe = city_by_name(state, "Soe").await;
+    send(
+        state,
+        Method::POST,
+        "/api/admin/journeys",
+        Some(token),
+        Some(json!({
+            "origin_city_id": kupang.id,
+            "destination_city_id": soe.id,
+            "departure_time": tomorrow(),
+            "total_seats": 10,
+            "base_fare": 15_000,
+            "stops": stops
+        })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_journey_with_two_stops() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let first = create_stop_city(&state, &token, "Camplong").await;
+    let second = create_stop_city(&state, &token, "Takari").await;
+
+    let (status, created) = create_journey_with_stops(
+        &state,
+        &token,
+        json!([
+            { "city_id": first, "scheduled_time": tomorrow() + Duration::minutes(45) },
+            { "city_id": second, "scheduled_time": tomorrow() + Duration::minutes(90) }
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", created);
+    let stops = created["stops"].as_array().unwrap();
+    assert_eq!(stops.len(), 2);
+    assert_eq!(stops[0]["stop_order"], 1);
+    assert_eq!(stops[0]["city_id"], first);
+    assert_eq!(stops[1]["stop_order"], 2);
+    assert_eq!(stops[1]["city_id"], second);
+    assert!(
+        stops[0]["city_name"]
+            .as_str()
+            .unwrap()
+            .starts_with("Camplong-")
+    );
+
+    // Travellers see the same stops in route order
+    let uri = format!("/api/journeys/{}", created["id"].as_str().unwrap());
+    let (status, journey) = send(&state, Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<i64> = journey["stops"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["city_id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, [first, second]);
+
+    // Stops keep their cities in use
+    let uri = format!("/api/admin/cities/{}", first);
+    let (status, _) = send(&state, Method::DELETE, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    clean_up(&state, &token, created["id"].as_str(), &[first, second]).await;
+}
+
+#[tokio::test]
+async fn test_rejects_duplicate_and_unordered_stops() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let token = token_for(&state, &admin);
+    let first = create_stop_city(&state, &token, "Camplong").await;
+    let second = create_stop_city(&state, &token, "Takari").await;
+    let soe = city_by_name(&state, "Soe").await;
+    let at = |minutes| tomorrow() + Duration::minutes(minutes);
+
+    let cases = [
+        // Same city twice
+        json!([
+            { "city_id": first, "scheduled_time": at(30) },
+            { "city_id": first, "scheduled_time": at(60) }
+        ]),
+        // Second stop scheduled before the first
+        json!([
+            { "city_id": first, "scheduled_time": at(60) },
+            { "city_id": second, "scheduled_time": at(30) }
+        ]),
+        // Before departure
+        json!([{ "city_id": first, "scheduled_time": at(-30) }]),
+        // The destination itself
+        json!([{ "city_id": soe.id, "scheduled_time": at(30) }]),
+        // No such city
+        json!([{ "city_id": i32::MAX, "scheduled_time": at(30) }]),
+    ];
+    for stops in cases {
+        let (status, body) = create_journey_with_stops(&state, &token, stops.clone()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{} -> {}", stops, body);
+    }
+
+    clean_up(&state, &token, None, &[first, second]).await;
+}
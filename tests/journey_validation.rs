@@ -0,0 +1,213 @@
+mod common;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+};
+use chrono::{Duration, Utc};
+
+use sea_orm::{ActiveModelTrait, Set};
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppError,
+    entities::user::{self, UserRole},
+    handlers::admin::{
+        AdminJourneyFilter, CreateJourneyRequest, UpdateJourneyRequest,
+        create_journey as create_journey_handler, list_journeys, update_journey,
+    },
+    utils::{pagination::Pagination, sort::SortQuery},
+};
+
+use common::{
+    city_by_name, claims_for, create_journey, create_user, insert_booking, test_state, tomorrow,
+};
+
+#[tokio::test]
+async fn test_create_journey_rejects_past_departure_and_bad_seat_counts() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let origin = city_by_name(&state, "Kupang").await;
+    let dest = city_by_name(&state, "Soe").await;
+
+    let cases = [
+        (Utc::now() - Duration::hours(1), 10),
+        (tomorrow(), 0),
+        (tomorrow(), -5),
+    ];
+    for (departure_time, total_seats) in cases {
+        let result = create_journey_handler(
+            State(state.clone()),
+            Extension(claims_for(&admin)),
+            Json(CreateJourneyRequest {
+                origin_city_id: origin.id,
+                destination_city_id: dest.id,
+                departure_time,
+                total_seats,
+                base_fare: 15_000,
+                booking_close_minutes: 0,
+                stops: vec![],
+            }),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(AppError::BadRequest(_))),
+            "{} seats at {}",
+            total_seats,
+            departure_time
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_update_journey_rejects_past_departure_and_bad_seat_counts() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+
+    let cases = [
+        (Some(Utc::now() - Duration::hours(1)), None),
+        (None, Some(0)),
+        (None, Some(-5)),
+    ];
+    for (departure_time, total_seats) in cases {
+        let result = update_journey(
+            State(state.clone()),
+            Path(journey.id),
+            Json(UpdateJourneyRequest {
+                origin_city_id: None,
+                destination_city_id: None,
+                departure_time,
+                total_seats,
+                base_fare: None,
+                booking_close_minutes: None,
+            }),
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+}
+
+#[tokio::test]
+async fn test_update_journey_rejects_total_seats_below_booked() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    insert_booking(&state, &journey, &traveller, 3).await;
+
+    let shrink = |total_seats| UpdateJourneyRequest {
+        origin_city_id: None,
+        destination_city_id: None,
+        departure_time: None,
+        total_seats: Some(total_seats),
+        base_fare: None,
+        booking_close_minutes: None,
+    };
+
+    let result = update_journey(State(state.clone()), Path(journey.id), Json(shrink(2))).await;
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+    // Exactly the booked count is still allowed
+    let Json(updated) = update_journey(State(state.clone()), Path(journey.id), Json(shrink(3)))
+        .await
+        .unwrap();
+    assert_eq!(updated.total_seats, 3);
+}
+
+#[tokio::test]
+async fn test_create_journey_checks_distance() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let origin = city_by_name(&state, "Kupang").await;
+    let dest = city_by_name(&state, "Soe").await;
+    let request = || {
+        Json(CreateJourneyRequest {
+            origin_city_id: origin.id,
+            destination_city_id: dest.id,
+            departure_time: tomorrow(),
+            total_seats: 10,
+            base_fare: 15_000,
+            booking_close_minutes: 0,
+            stops: vec![],
+        })
+    };
+
+    let Json(created) = create_journey_handler(
+        State(state.clone()),
+        Extension(claims_for(&admin)),
+        request(),
+    )
+    .await
+    .unwrap();
+    assert!(created.distance_km > 50.0 && created.distance_km < 150.0);
+
+    // Kupang to Soe is too far once the limit drops below it
+    let mut strict = state.clone();
+    strict.config.max_journey_km = 10.0;
+    let result =
+        create_journey_handler(State(strict), Extension(claims_for(&admin)), request()).await;
+    assert!(matches!(result, Err(AppError::BadRequest(_))));
+}
+
+#[tokio::test]
+async fn test_created_journey_reports_creator() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let name = format!("Admin {}", Uuid::new_v4());
+    let mut admin: user::ActiveModel = create_user(&state, UserRole::Admin).await.into();
+    admin.name = Set(name.clone());
+    let admin = admin.update(&state.db).await.unwrap();
+    let origin = city_by_name(&state, "Kupang").await;
+    let dest = city_by_name(&state, "Soe").await;
+
+    let Json(created) = create_journey_handler(
+        State(state.clone()),
+        Extension(claims_for(&admin)),
+        Json(CreateJourneyRequest {
+            origin_city_id: origin.id,
+            destination_city_id: dest.id,
+            departure_time: tomorrow(),
+            total_seats: 10,
+            base_fare: 15_000,
+            booking_close_minutes: 0,
+            stops: vec![],
+        }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(created.journey.created_by, Some(admin.id));
+
+    // Newest first, so the journey is on the first page
+    let page = list_journeys(
+        State(state.clone()),
+        Query(Pagination {
+            page: Some(1),
+            per_page: Some(100),
+        }),
+        Query(SortQuery {
+            sort: Some("created_at_desc".to_string()),
+        }),
+        Query(AdminJourneyFilter::default()),
+    )
+    .await
+    .unwrap()
+    .0;
+    let listed = page
+        .items
+        .iter()
+        .find(|j| j.id == created.journey.id)
+        .unwrap();
+    assert_eq!(listed.created_by_name.as_deref(), Some(name.as_str()));
+
+    // Journeys created outside the admin API have no known creator
+    let seeded = create_journey(&state, 10, tomorrow()).await;
+    assert_eq!(seeded.created_by, None);
+}
@@ -0,0 +1,196 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, Set};
+
+use bus_travel_backend::entities::{
+    booking, journey,
+    user::{self, UserRole},
+};
+
+use common::{
+    assign_driver, create_journey, create_user, insert_booking, send, send_raw, test_state,
+    token_for, tomorrow,
+};
+
+#[tokio::test]
+async fn test_passenger_manifest_csv() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let mut active: user::ActiveModel = traveller.into();
+    active.name = Set("Doe, \"Johnny\" John".to_string());
+    let traveller = active.update(&state.db).await.unwrap();
+
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let booking = insert_booking(&state, &journey, &traveller, 2).await;
+
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(format!("/api/admin/journeys/{}/passengers", journey.id))
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token_for(&state, &admin)),
+        )
+        .header(header::ACCEPT, "text/csv")
+        .body(Body::empty())
+        .unwrap();
+    let (status, headers, body) = send_raw(&state, request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        headers[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/csv")
+    );
+    assert!(
+        headers[header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .starts_with("attachment")
+    );
+
+    let csv = String::from_utf8(body.to_vec()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "booking_id,passenger_name,passenger_email,seats,pickup_lat,pickup_lng"
+    );
+    assert_eq!(
+        lines[1],
+        format!(
+            "{},\"Doe, \"\"Johnny\"\" John\",{},2,{},{}",
+            booking.id, traveller.email, booking.pickup_lat, booking.pickup_lng
+        )
+    );
+    assert_eq!(lines.len(), 2);
+
+    // The query parameter works without an Accept header
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(format!(
+            "/api/admin/journeys/{}/passengers?format=csv",
+            journey.id
+        ))
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token_for(&state, &admin)),
+        )
+        .body(Body::empty())
+        .unwrap();
+    let (status, _, body) = send_raw(&state, request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.starts_with(b"booking_id,"));
+}
+
+#[tokio::test]
+async fn test_driver_manifest_pickup_clusters() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let driver = create_user(&state, UserRole::Driver).await;
+    let token = token_for(&state, &driver);
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let journey = assign_driver(&state, journey, driver.id).await;
+
+    // Two travellers at the same spot, one across town
+    for _ in 0..2 {
+        let traveller = create_user(&state, UserRole::Traveller).await;
+        insert_booking(&state, &journey, &traveller, 1).await;
+    }
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let mut across: booking::ActiveModel =
+        insert_booking(&state, &journey, &traveller, 2).await.into();
+    across.pickup_lat = Set(-10.1500);
+    across.update(&state.db).await.unwrap();
+
+    let uri = format!("/api/driver/journeys/{}/passengers", journey.id);
+    let (status, body) = send(&state, Method::GET, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["passengers"].as_array().unwrap().len(), 3);
+    assert!(body.get("clusters").is_none());
+
+    let clustered = format!("{}?cluster_radius_km=1", uri);
+    let (status, body) = send(&state, Method::GET, &clustered, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let mut sizes: Vec<(usize, i64)> = body["clusters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| {
+            (
+                c["passengers"].as_array().unwrap().len(),
+                c["seats"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    sizes.sort();
+    assert_eq!(sizes, vec![(1, 2), (2, 2)]);
+
+    let invalid = format!("{}?cluster_radius_km=0", uri);
+    let (status, _) = send(&state, Method::GET, &invalid, Some(&token), None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_driver_manifest_upcoming_only() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let driver = create_user(&state, UserRole::Driver).await;
+    let token = token_for(&state, &driver);
+    let past = create_journey(&state, 10, Utc::now() - Duration::hours(2)).await;
+    let past = assign_driver(&state, past, driver.id).await;
+    let upcoming = create_journey(&state, 10, Utc::now() + Duration::hours(3)).await;
+    let upcoming = assign_driver(&state, upcoming, driver.id).await;
+    let uri = |journey: &journey::Model, query: &str| {
+        format!("/api/driver/journeys/{}/passengers{}", journey.id, query)
+    };
+
+    // Without the flag a past journey's manifest is still available
+    let (status, body) = send(&state, Method::GET, &uri(&past, ""), Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["departure_in_minutes"].as_i64().unwrap() < 0);
+
+    let (status, _) = send(
+        &state,
+        Method::GET,
+        &uri(&past, "?upcoming_only=true"),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        &uri(&upcoming, "?upcoming_only=true"),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let minutes = body["departure_in_minutes"].as_i64().unwrap();
+    assert!((178..=180).contains(&minutes), "{}", minutes);
+
+    // Within the first minute after departure the countdown is already negative
+    let just_left = create_journey(&state, 10, Utc::now() - Duration::seconds(20)).await;
+    let just_left = assign_driver(&state, just_left, driver.id).await;
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        &uri(&just_left, ""),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["departure_in_minutes"], -1);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+};
+use sea_orm::{EntityTrait, QuerySelect, TransactionTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppState,
+    entities::{journey, user::UserRole},
+};
+
+use common::{create_journey, create_user, send, send_raw, test_state, token_for, tomorrow};
+
+async fn scrape(state: &AppState) -> String {
+    let request = Request::get("/metrics").body(Body::empty()).unwrap();
+    let (status, _, body) = send_raw(state, request).await;
+    assert_eq!(status, StatusCode::OK);
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+/// Value of the request counter for `method` and `path`, or 0 if it hasn't been recorded yet
+fn request_count(metrics: &str, method: &str, path: &str, status: u16) -> u64 {
+    let series = format!(
+        "http_requests_total{{method=\"{}\",path=\"{}\",status=\"{}\"}} ",
+        method, path, status
+    );
+    metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(&series))
+        .map(|value| value.trim().parse().unwrap())
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_metrics_count_requests_by_route() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let before = request_count(&scrape(&state).await, "GET", "/api/journeys/{id}", 404);
+
+    let uri = format!("/api/journeys/{}", Uuid::new_v4());
+    let (status, _) = send(&state, Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Labelled with the route template, not the concrete id
+    let metrics = scrape(&state).await;
+    assert_eq!(
+        request_count(&metrics, "GET", "/api/journeys/{id}", 404),
+        before + 1
+    );
+    assert!(!metrics.contains(&uri));
+    assert!(metrics.contains("http_request_duration_seconds_bucket"));
+}
+
+#[tokio::test]
+async fn test_metrics_count_timeouts() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    state.config.request_timeout_secs = 1;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let before = request_count(&scrape(&state).await, "POST", "/api/bookings", 504);
+
+    // Booking waits on the journey row lock held here until the deadline passes
+    let txn = state.db.begin().await.unwrap();
+    journey::Entity::find_by_id(journey.id)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .unwrap();
+    let body = json!({
+        "journey_id": journey.id,
+        "seats": 1,
+        "pickup_lat": -10.1836,
+        "pickup_lng": 123.6257,
+    });
+    let token = token_for(&state, &traveller);
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/bookings",
+        Some(&token),
+        Some(body),
+    )
+    .await;
+    txn.rollback().await.unwrap();
+    assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+
+    let metrics = scrape(&state).await;
+    assert_eq!(
+        request_count(&metrics, "POST", "/api/bookings", 504),
+        before + 1
+    );
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use uuid::Uuid;
+
+use bus_travel_backend::entities::city::{self, PickupPolygon};
+
+use common::{send, test_state};
+
+#[tokio::test]
+async fn test_nearest_city_endpoint() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    // Just off Kupang's center, well inside its pickup radius
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        "/api/cities/nearest?lat=-10.19&lng=123.62",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["city"]["name"], "Kupang");
+    assert!(body["distance_km"].as_f64().unwrap() < 2.0);
+    assert_eq!(body["within_pickup_radius"], true);
+
+    // Between the two towns: nearest is Soe, but too far out to be picked up
+    let (status, body) = send(
+        &state,
+        Method::GET,
+        "/api/cities/nearest?lat=-9.95&lng=124.1",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["city"]["name"], "Soe");
+    assert_eq!(body["within_pickup_radius"], false);
+
+    let (status, _) = send(
+        &state,
+        Method::GET,
+        "/api/cities/nearest?lat=95&lng=124.1",
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_nearest_city_uses_pickup_polygon() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    // Far from the seeded towns, with a small polygon inside a generous radius
+    let town = city::ActiveModel {
+        name: Set(format!("Atambua-{}", Uuid::new_v4())),
+        center_lat: Set(-9.1061),
+        center_lng: Set(124.8925),
+        pickup_radius_km: Set(20.0),
+        pickup_polygon: Set(Some(PickupPolygon(vec![
+            [-9.09, 124.88],
+            [-9.09, 124.91],
+            [-9.12, 124.91],
+            [-9.12, 124.88],
+        ]))),
+        timezone: Set("Asia/Makassar".to_string()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await
+    .unwrap();
+
+    let nearest = |lat: f64, lng: f64| {
+        let state = &state;
+        async move {
+            let uri = format!("/api/cities/nearest?lat={}&lng={}", lat, lng);
+            let (status, body) = send(state, Method::GET, &uri, None, None).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["city"]["id"], town.id);
+            body["within_pickup_radius"].as_bool().unwrap()
+        }
+    };
+
+    assert!(nearest(-9.1061, 124.8925).await);
+    // About 6 km out: inside the radius, but outside the polygon
+    assert!(!nearest(-9.1061, 124.95).await);
+
+    city::Entity::delete_by_id(town.id)
+        .exec(&state.db)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,28 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+
+use common::{send, test_state};
+
+#[tokio::test]
+async fn test_openapi_spec_lists_documented_paths() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let (status, spec) = send(&state, Method::GET, "/api/docs/openapi.json", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+
+    let login = &spec["paths"]["/api/auth/login"]["post"];
+    assert!(login.is_object());
+    assert!(login["responses"]["200"].is_object());
+
+    // Protected endpoints point at the bearer scheme
+    assert!(spec["paths"]["/api/bookings"]["post"]["security"].is_array());
+    assert_eq!(
+        spec["components"]["securitySchemes"]["bearer_auth"]["scheme"],
+        "bearer"
+    );
+    assert!(spec["components"]["schemas"]["BookingResponse"].is_object());
+}
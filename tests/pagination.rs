@@ -0,0 +1,81 @@
+mod common;
+
+use axum::{
+    Extension,
+    extract::{Query, State},
+};
+
+use bus_travel_backend::{
+    entities::user::UserRole,
+    handlers::traveller::{JourneyFilter, available_journeys, my_bookings},
+    utils::pagination::Pagination,
+};
+
+use common::{claims_for, create_journey, create_user, insert_booking, test_state, tomorrow};
+
+fn page(page: u64, per_page: u64) -> Query<Pagination> {
+    Query(Pagination {
+        page: Some(page),
+        per_page: Some(per_page),
+    })
+}
+
+#[tokio::test]
+async fn test_my_bookings_paginates_with_total() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    for _ in 0..3 {
+        let journey = create_journey(&state, 10, tomorrow()).await;
+        insert_booking(&state, &journey, &traveller, 1).await;
+    }
+
+    let first = my_bookings(
+        State(state.clone()),
+        Extension(claims_for(&traveller)),
+        page(1, 2),
+    )
+    .await
+    .unwrap();
+    assert_eq!(first.total, 3);
+    assert_eq!(first.items.len(), 2);
+
+    let second = my_bookings(
+        State(state.clone()),
+        Extension(claims_for(&traveller)),
+        page(2, 2),
+    )
+    .await
+    .unwrap();
+    assert_eq!(second.total, 3);
+    assert_eq!(second.items.len(), 1);
+}
+
+#[tokio::test]
+async fn test_out_of_range_page_is_empty() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    insert_booking(&state, &journey, &traveller, 1).await;
+
+    let bookings = my_bookings(
+        State(state.clone()),
+        Extension(claims_for(&traveller)),
+        page(50, 20),
+    )
+    .await
+    .unwrap();
+    assert_eq!(bookings.total, 1);
+    assert!(bookings.items.is_empty());
+
+    let journeys = available_journeys(&state, &page(1_000_000, 100), &JourneyFilter::default())
+        .await
+        .unwrap();
+    assert!(journeys.total >= 1);
+    assert!(journeys.items.is_empty());
+}
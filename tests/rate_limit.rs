@@ -0,0 +1,90 @@
+mod common;
+
+use std::net::SocketAddr;
+
+use axum::{
+    Router,
+    body::Body,
+    extract::ConnectInfo,
+    http::{HeaderMap, Request, StatusCode, header},
+};
+use tower::ServiceExt;
+
+use bus_travel_backend::{entities::user::UserRole, routes::create_router};
+use common::{create_user, test_state, token_for};
+
+/// Send a GET through `app`, keeping the router (and its governors) alive between calls
+async fn get(app: &Router, uri: &str, token: Option<&str>) -> (StatusCode, HeaderMap) {
+    let mut builder = Request::builder().uri(uri);
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let mut request = builder.body(Body::empty()).unwrap();
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0))));
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    (response.status(), response.headers().clone())
+}
+
+fn remaining(headers: &HeaderMap) -> u64 {
+    headers["x-ratelimit-remaining"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_public_rate_limit_headers() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    state.config.rate_limit_traveller_per_min = 3;
+    let app = create_router(state);
+
+    let mut previous = u64::MAX;
+    for _ in 0..3 {
+        let (status, headers) = get(&app, "/api/cities", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers["x-ratelimit-limit"], "3");
+        assert!(remaining(&headers) < previous);
+        previous = remaining(&headers);
+    }
+    assert_eq!(previous, 0);
+
+    let (status, headers) = get(&app, "/api/cities", None).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(remaining(&headers), 0);
+    let retry_after: u64 = headers[header::RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after > 0);
+}
+
+#[tokio::test]
+async fn test_role_rate_limit_headers() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    state.config.rate_limit_traveller_per_min = 2;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &traveller);
+    let app = create_router(state);
+
+    let (status, headers) = get(&app, "/api/bookings", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["x-ratelimit-limit"], "2");
+    assert_eq!(remaining(&headers), 1);
+
+    let (status, headers) = get(&app, "/api/bookings", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(remaining(&headers), 0);
+
+    let (status, headers) = get(&app, "/api/bookings", Some(&token)).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(headers.contains_key(header::RETRY_AFTER));
+}
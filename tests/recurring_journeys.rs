@@ -0,0 +1,101 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Datelike, Duration, NaiveTime, Utc, Weekday};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::entities::{journey, user::UserRole};
+
+use common::{city_by_name, create_user, send, test_state, token_for, unique_future_date};
+
+#[tokio::test]
+async fn test_recurring_monday_friday_week() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let kupang = city_by_name(&state, "Kupang").await;
+    let soe = city_by_name(&state, "Soe").await;
+
+    // A Monday-to-Sunday week nobody else schedules journeys in
+    let date = unique_future_date();
+    let monday = date - Duration::days(date.weekday().num_days_from_monday().into());
+    let sunday = monday + Duration::days(6);
+
+    let (status, body) = send(
+        &state,
+        Method::POST,
+        "/api/admin/journeys/recurring",
+        Some(&token_for(&state, &admin)),
+        Some(json!({
+            "origin_city_id": kupang.id,
+            "destination_city_id": soe.id,
+            "total_seats": 30,
+            "base_fare": 75000,
+            "start_date": monday,
+            "end_date": sunday,
+            "time_of_day": "08:30:00",
+            "days_of_week": ["Mon", "Fri"]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let ids: Vec<Uuid> = body["journey_ids"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| id.as_str().unwrap().parse().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 2);
+
+    let created = journey::Entity::find()
+        .filter(journey::Column::Id.is_in(ids))
+        .order_by_asc(journey::Column::DepartureTime)
+        .all(&state.db)
+        .await
+        .unwrap();
+
+    // 08:30 in Kupang's timezone (Asia/Jakarta, UTC+7) is 01:30 UTC
+    let departures: Vec<_> = created.iter().map(|j| j.departure_time).collect();
+    assert_eq!(departures[0].date_naive(), monday);
+    assert_eq!(departures[0].weekday(), Weekday::Mon);
+    assert_eq!(departures[1].weekday(), Weekday::Fri);
+    for (j, departure) in created.iter().zip(&departures) {
+        assert_eq!(departure.time(), NaiveTime::from_hms_opt(1, 30, 0).unwrap());
+        assert_eq!(j.total_seats, 30);
+        assert_eq!(j.base_fare, 75000);
+    }
+}
+
+#[tokio::test]
+async fn test_recurring_rejects_departures_in_the_past() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let admin = create_user(&state, UserRole::Admin).await;
+    let kupang = city_by_name(&state, "Kupang").await;
+    let soe = city_by_name(&state, "Soe").await;
+
+    let today = Utc::now().date_naive();
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/admin/journeys/recurring",
+        Some(&token_for(&state, &admin)),
+        Some(json!({
+            "origin_city_id": kupang.id,
+            "destination_city_id": soe.id,
+            "total_seats": 30,
+            "base_fare": 0,
+            "start_date": today - Duration::days(14),
+            "end_date": today + Duration::days(14),
+            "time_of_day": "08:30:00",
+            "days_of_week": ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
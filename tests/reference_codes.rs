@@ -0,0 +1,115 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use sea_orm::{ActiveModelTrait, Set};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    db,
+    entities::{booking, user::UserRole},
+    utils::reference::{generate_reference_code, normalize_reference_code},
+};
+
+use common::{create_journey, create_user, insert_booking, send, test_state, token_for, tomorrow};
+
+#[tokio::test]
+async fn test_booking_gets_reference_code() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &traveller);
+
+    let body = json!({
+        "journey_id": journey.id,
+        "seats": 1,
+        "pickup_lat": -10.1836,
+        "pickup_lng": 123.6257,
+    });
+    let (status, booking) = send(
+        &state,
+        Method::POST,
+        "/api/bookings",
+        Some(&token),
+        Some(body),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let code = booking["reference_code"].as_str().unwrap();
+    assert_eq!(code.len(), 6);
+    assert_eq!(normalize_reference_code(code).as_deref(), Some(code));
+
+    let (_, listed) = send(&state, Method::GET, "/api/bookings", Some(&token), None).await;
+    assert_eq!(listed["items"][0]["reference_code"], code);
+}
+
+#[tokio::test]
+async fn test_lookup_by_reference_code() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let booking = insert_booking(&state, &journey, &traveller, 2).await;
+
+    // Codes are matched case-insensitively
+    let uri = format!(
+        "/api/bookings/by-ref/{}",
+        booking.reference_code.to_lowercase()
+    );
+    let token = token_for(&state, &traveller);
+    let (status, body) = send(&state, Method::GET, &uri, Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], booking.id.to_string());
+    assert_eq!(body["reference_code"], booking.reference_code);
+
+    // Someone else's code looks like an unknown one
+    let stranger = create_user(&state, UserRole::Traveller).await;
+    let stranger_token = token_for(&state, &stranger);
+    let (status, _) = send(&state, Method::GET, &uri, Some(&stranger_token), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    for code in ["ZZZZZZ", "not-a-code"] {
+        let uri = format!("/api/bookings/by-ref/{}", code);
+        let (status, _) = send(&state, Method::GET, &uri, Some(&token), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
+
+#[tokio::test]
+async fn test_reference_codes_are_unique() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let existing = insert_booking(&state, &journey, &traveller, 1).await;
+
+    let new_booking = || booking::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        journey_id: Set(journey.id),
+        user_id: Set(traveller.id),
+        seats: Set(1),
+        pickup_lat: Set(-10.1836),
+        pickup_lng: Set(123.6257),
+        ..Default::default()
+    };
+
+    // The database refuses a second booking with the same code
+    let mut duplicate = new_booking();
+    duplicate.reference_code = Set(existing.reference_code.clone());
+    assert!(duplicate.insert(&state.db).await.is_err());
+
+    // A clash is retried with a fresh code
+    let mut codes = vec![existing.reference_code.clone(), generate_reference_code()];
+    let fresh = codes[1].clone();
+    codes.reverse();
+    let inserted = db::insert_booking(&state.db, new_booking(), || codes.pop().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(inserted.reference_code, fresh);
+    assert!(codes.is_empty());
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use sea_orm::{EntityTrait, QuerySelect, TransactionTrait};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::entities::{journey, user::UserRole};
+
+use common::{create_journey, create_user, send_raw, test_state, token_for, tomorrow};
+
+#[tokio::test]
+async fn test_request_id_echoed_in_header_and_error_body() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let request = Request::get(format!("/api/journeys/{}", Uuid::new_v4()))
+        .header("x-request-id", "trace-me-123")
+        .body(Body::empty())
+        .unwrap();
+    let (status, headers, bytes) = send_raw(&state, request).await;
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(headers.get("x-request-id").unwrap(), "trace-me-123");
+    assert_eq!(body["error"]["request_id"], "trace-me-123");
+}
+
+#[tokio::test]
+async fn test_request_id_generated_for_auth_failures() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+
+    let request = Request::get("/api/bookings")
+        .header("authorization", "Bearer not-a-jwt")
+        .body(Body::empty())
+        .unwrap();
+    let (status, headers, bytes) = send_raw(&state, request).await;
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let id = headers.get("x-request-id").unwrap().to_str().unwrap();
+    assert!(Uuid::parse_str(id).is_ok());
+    assert_eq!(body["error"]["request_id"], id);
+}
+
+#[tokio::test]
+async fn test_request_id_on_timeouts() {
+    let Some(mut state) = test_state().await else {
+        return;
+    };
+    state.config.request_timeout_secs = 1;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let journey = create_journey(&state, 10, tomorrow()).await;
+
+    // Booking waits on the journey row lock held here until the deadline passes
+    let txn = state.db.begin().await.unwrap();
+    journey::Entity::find_by_id(journey.id)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .unwrap();
+    let body = json!({
+        "journey_id": journey.id,
+        "seats": 1,
+        "pickup_lat": -10.1836,
+        "pickup_lng": 123.6257,
+    });
+    let request = Request::post("/api/bookings")
+        .header("x-request-id", "slow-one")
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token_for(&state, &traveller)),
+        )
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let (status, headers, bytes) = send_raw(&state, request).await;
+    txn.rollback().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+    assert_eq!(headers.get("x-request-id").unwrap(), "slow-one");
+    assert_eq!(body["error"]["request_id"], "slow-one");
+}
@@ -0,0 +1,209 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{Duration, Utc};
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    AppState,
+    entities::{booking, journey, seat_hold, user::UserRole},
+};
+
+use common::{create_journey, create_user, send, test_state, token_for, tomorrow};
+
+async fn hold(
+    state: &AppState,
+    token: &str,
+    journey: &journey::Model,
+    seats: i32,
+) -> (StatusCode, Value) {
+    let body = json!({
+        "journey_id": journey.id,
+        "seats": seats,
+        "pickup_lat": -10.1836,
+        "pickup_lng": 123.6257,
+    });
+    send(
+        state,
+        Method::POST,
+        "/api/bookings/hold",
+        Some(token),
+        Some(body),
+    )
+    .await
+}
+
+async fn confirm(state: &AppState, token: &str, hold_id: &Value) -> (StatusCode, Value) {
+    let body = json!({ "hold_id": hold_id });
+    send(
+        state,
+        Method::POST,
+        "/api/bookings/confirm",
+        Some(token),
+        Some(body),
+    )
+    .await
+}
+
+async fn available_seats(state: &AppState, journey: &journey::Model) -> Value {
+    let uri = format!("/api/journeys/{}", journey.id);
+    let (status, body) = send(state, Method::GET, &uri, None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    body["available_seats"].clone()
+}
+
+/// Push a hold's expiry into the past, as if the checkout window ran out
+async fn expire(state: &AppState, hold_id: &Value) {
+    let id: Uuid = serde_json::from_value(hold_id.clone()).unwrap();
+    let mut active = seat_hold::Entity::find_by_id(id)
+        .one(&state.db)
+        .await
+        .unwrap()
+        .unwrap()
+        .into_active_model();
+    active.expires_at = Set((Utc::now() - Duration::seconds(1)).into());
+    active.update(&state.db).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_hold_reduces_availability() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 3, tomorrow()).await;
+    let holder = create_user(&state, UserRole::Traveller).await;
+    let other = create_user(&state, UserRole::Traveller).await;
+
+    let (status, held) = hold(&state, &token_for(&state, &holder), &journey, 2).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(held["seats"], 2);
+    assert_eq!(available_seats(&state, &journey).await, 1);
+
+    // The held seats aren't for sale to anyone else
+    let other_token = token_for(&state, &other);
+    let (status, _) = hold(&state, &other_token, &journey, 2).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let body = json!({
+        "journey_id": journey.id,
+        "seats": 2,
+        "pickup_lat": -10.1836,
+        "pickup_lng": 123.6257,
+    });
+    let (status, _) = send(
+        &state,
+        Method::POST,
+        "/api/bookings",
+        Some(&other_token),
+        Some(body),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Holding again replaces the earlier hold rather than stacking on it
+    let (status, _) = hold(&state, &token_for(&state, &holder), &journey, 3).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(available_seats(&state, &journey).await, 0);
+}
+
+#[tokio::test]
+async fn test_confirm_hold_creates_booking() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 3, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &traveller);
+
+    let (_, held) = hold(&state, &token, &journey, 2).await;
+    let (status, booking) = confirm(&state, &token, &held["id"]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(booking["seats"], 2);
+    assert_eq!(booking["status"], "confirmed");
+
+    // The booking now takes the seats the hold did, without counting twice
+    assert_eq!(available_seats(&state, &journey).await, 1);
+    let id: Uuid = serde_json::from_value(booking["id"].clone()).unwrap();
+    assert!(
+        booking::Entity::find_by_id(id)
+            .one(&state.db)
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    // A hold can only be used once
+    let (status, _) = confirm(&state, &token, &held["id"]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_expired_hold_releases_seats() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 3, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &traveller);
+
+    let (_, held) = hold(&state, &token, &journey, 3).await;
+    assert_eq!(available_seats(&state, &journey).await, 0);
+
+    expire(&state, &held["id"]).await;
+    assert_eq!(available_seats(&state, &journey).await, 3);
+
+    let (status, body) = confirm(&state, &token, &held["id"]).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body["error"]["message"].as_str().unwrap().contains("expired"));
+}
+
+#[tokio::test]
+async fn test_confirm_someone_elses_hold_not_found() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 3, tomorrow()).await;
+    let holder = create_user(&state, UserRole::Traveller).await;
+    let other = create_user(&state, UserRole::Traveller).await;
+
+    let (_, held) = hold(&state, &token_for(&state, &holder), &journey, 1).await;
+    let (status, _) = confirm(&state, &token_for(&state, &other), &held["id"]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_booking_directly_replaces_own_hold() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 3, tomorrow()).await;
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let token = token_for(&state, &traveller);
+
+    let (_, held) = hold(&state, &token, &journey, 3).await;
+    assert_eq!(available_seats(&state, &journey).await, 0);
+
+    // The traveller's own hold doesn't stand in the way of their booking
+    let body = json!({
+        "journey_id": journey.id,
+        "seats": 2,
+        "pickup_lat": -10.1836,
+        "pickup_lng": 123.6257,
+    });
+    let (status, booking) = send(
+        &state,
+        Method::POST,
+        "/api/bookings",
+        Some(&token),
+        Some(body),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", booking);
+    assert_eq!(booking["status"], "confirmed");
+
+    // and the hold is gone, so its third seat is for sale again
+    assert_eq!(available_seats(&state, &journey).await, 1);
+    let (status, _) = confirm(&state, &token, &held["id"]).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+use bus_travel_backend::{
+    db::seed::seed_admin,
+    entities::user::{self, UserRole},
+};
+
+use common::{send, test_state};
+
+#[tokio::test]
+async fn test_seed_admin_is_idempotent() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let email = format!("admin-{}@example.com", Uuid::new_v4());
+    let cost = state.config.password_hash_cost();
+
+    let admin = seed_admin(&state.db, &email, "first-password", &cost)
+        .await
+        .unwrap()
+        .expect("admin created");
+    assert_eq!(admin.email, email);
+    assert_eq!(admin.role, UserRole::Admin);
+
+    // A second run (even with another password) leaves the account alone
+    let again = seed_admin(&state.db, &email.to_uppercase(), "second-password", &cost)
+        .await
+        .unwrap();
+    assert!(again.is_none());
+
+    let login = |password: &str| {
+        let body = json!({ "email": email, "password": password });
+        let state = &state;
+        async move { send(state, Method::POST, "/api/auth/login", None, Some(body)).await }
+    };
+    let (status, body) = login("first-password").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["user"]["role"], "Admin");
+    let (status, _) = login("second-password").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_seeding_creates_one_admin() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let email = format!("admin-{}@example.com", Uuid::new_v4());
+    let cost = state.config.password_hash_cost();
+
+    // Both runs can pass the existence check before either inserts
+    let (first, second) = tokio::join!(
+        seed_admin(&state.db, &email, "first-password", &cost),
+        seed_admin(&state.db, &email, "second-password", &cost),
+    );
+    let created = [first.unwrap(), second.unwrap()];
+    assert_eq!(created.iter().filter(|c| c.is_some()).count(), 1);
+
+    let admins = user::Entity::find()
+        .filter(user::Column::Email.eq(&email))
+        .count(&state.db)
+        .await
+        .unwrap();
+    assert_eq!(admins, 1);
+}
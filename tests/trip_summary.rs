@@ -0,0 +1,79 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{ActiveModelTrait, Set};
+
+use bus_travel_backend::entities::{
+    booking::{self, BookingStatus},
+    journey::{self, JourneyStatus},
+    user::UserRole,
+};
+
+use common::{
+    city_by_name, create_journey, create_journey_between, create_user, insert_booking, send,
+    test_state, token_for,
+};
+
+#[tokio::test]
+async fn test_summary_counts_completed_trips() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+    let kupang = city_by_name(&state, "Kupang").await;
+    let soe = city_by_name(&state, "Soe").await;
+    let now = Utc::now();
+    let first = now - Duration::days(30);
+    let last = now - Duration::days(1);
+
+    let outbound = create_journey(&state, 10, first).await;
+    insert_booking(&state, &outbound, &traveller, 2).await;
+    let outbound = create_journey(&state, 10, now - Duration::days(10)).await;
+    insert_booking(&state, &outbound, &traveller, 1).await;
+    let back = create_journey_between(&state, &soe, &kupang, 10, last).await;
+    insert_booking(&state, &back, &traveller, 3).await;
+
+    // Upcoming trips, cancelled bookings and cancelled journeys are not counted
+    let upcoming = create_journey(&state, 10, now + Duration::days(2)).await;
+    insert_booking(&state, &upcoming, &traveller, 4).await;
+    let dropped = create_journey(&state, 10, now - Duration::days(5)).await;
+    let booking = insert_booking(&state, &dropped, &traveller, 1).await;
+    let mut active: booking::ActiveModel = booking.into();
+    active.status = Set(BookingStatus::Cancelled);
+    active.update(&state.db).await.unwrap();
+    let called_off = create_journey(&state, 10, now - Duration::days(3)).await;
+    insert_booking(&state, &called_off, &traveller, 1).await;
+    let mut active: journey::ActiveModel = called_off.into();
+    active.status = Set(JourneyStatus::Cancelled);
+    active.update(&state.db).await.unwrap();
+
+    let token = token_for(&state, &traveller);
+    let (status, body) = send(&state, Method::GET, "/api/auth/summary", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_trips"], 3);
+    assert_eq!(body["total_seats"], 6);
+    assert_eq!(body["most_traveled_route"]["origin_city"], "Kupang");
+    assert_eq!(body["most_traveled_route"]["destination_city"], "Soe");
+    assert_eq!(body["most_traveled_route"]["trips"], 2);
+    let first_trip: DateTime<Utc> = body["first_trip"].as_str().unwrap().parse().unwrap();
+    assert_eq!(first_trip.timestamp_micros(), first.timestamp_micros());
+    let last_trip: DateTime<Utc> = body["last_trip"].as_str().unwrap().parse().unwrap();
+    assert_eq!(last_trip.timestamp_micros(), last.timestamp_micros());
+}
+
+#[tokio::test]
+async fn test_summary_without_trips() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let traveller = create_user(&state, UserRole::Traveller).await;
+
+    let token = token_for(&state, &traveller);
+    let (status, body) = send(&state, Method::GET, "/api/auth/summary", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_trips"], 0);
+    assert_eq!(body["total_seats"], 0);
+    assert!(body["most_traveled_route"].is_null());
+    assert!(body["first_trip"].is_null());
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use std::time::Duration;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+
+use bus_travel_backend::handlers::admin::{UpdateJourneyRequest, update_journey};
+
+use common::{create_journey, test_state, tomorrow};
+
+#[tokio::test]
+async fn test_updating_journey_advances_updated_at_only() {
+    let Some(state) = test_state().await else {
+        return;
+    };
+    let journey = create_journey(&state, 10, tomorrow()).await;
+    assert_eq!(journey.created_at, journey.updated_at);
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    let Json(updated) = update_journey(
+        State(state.clone()),
+        Path(journey.id),
+        Json(UpdateJourneyRequest {
+            origin_city_id: None,
+            destination_city_id: None,
+            departure_time: None,
+            total_seats: Some(12),
+            base_fare: None,
+            booking_close_minutes: None,
+        }),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(updated.total_seats, 12);
+    assert_eq!(updated.created_at, journey.created_at);
+    assert!(updated.updated_at > journey.updated_at);
+}